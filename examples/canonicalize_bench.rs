@@ -0,0 +1,72 @@
+//! Count heap allocations for canonicalizing a batch of claims one by one
+//! versus with `canonicalize_many`.
+//!
+//! ```sh
+//! cargo run --release --example canonicalize_bench
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use provn_sdk::{canonicalize_many, Claim};
+
+/// Counts every allocation and reallocation made through the global allocator.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const CLAIMS: u64 = 10_000;
+
+fn measure<R>(label: &str, f: impl FnOnce() -> R) -> R {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!(
+        "{:<20} {:>6.2} allocations/claim {:>8.2} us/claim",
+        label,
+        allocations as f64 / CLAIMS as f64,
+        elapsed.as_secs_f64() * 1e6 / CLAIMS as f64
+    );
+    result
+}
+
+fn main() {
+    let claims: Vec<Claim> = (0..CLAIMS)
+        .map(|i| {
+            let mut claim = Claim::new_with_timestamp(format!("sensor 7 reading {}: 21.4C", i), i);
+            claim.metadata = Some("site=berlin;rack=12;firmware=4.2.1".to_string());
+            claim
+        })
+        .collect();
+
+    let looped = measure("to_signable_bytes", || {
+        claims
+            .iter()
+            .map(|claim| claim.to_signable_bytes().unwrap())
+            .collect::<Vec<_>>()
+    });
+    let batched = measure("canonicalize_many", || canonicalize_many(&claims).unwrap());
+    assert_eq!(looped, batched);
+}
@@ -0,0 +1,77 @@
+//! Compare verifying a signed claim from JSON and from a FlatBuffer.
+//!
+//! The Ed25519 check itself costs the same either way, so the "decode" rows
+//! isolate what the FlatBuffer saves when reading. Verification still parses
+//! the claim bytes once to check they are a canonical claim.
+//!
+//! ```sh
+//! cargo run --release --example flatbuffer_bench --features flatbuffers
+//! ```
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use provn_sdk::{
+    generate_keypair, sign_claim, verify_claim, verify_flatbuffer, Claim, FlatSignedClaim,
+    SignedClaim,
+};
+
+const ITERATIONS: u32 = 20_000;
+
+fn time(label: &str, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<22} {:>8.2} us/op",
+        label,
+        elapsed.as_secs_f64() * 1e6 / f64::from(ITERATIONS)
+    );
+    elapsed
+}
+
+fn ratio(slow: Duration, fast: Duration) -> f64 {
+    slow.as_secs_f64() / fast.as_secs_f64()
+}
+
+fn main() {
+    let mut claim = Claim::new_with_timestamp("Sensor 7: 21.4C".to_string(), 1_700_000_000);
+    claim.metadata = Some("site=berlin;rack=12;firmware=4.2.1".to_string());
+    let signed = sign_claim(&claim, &generate_keypair()).expect("signing failed");
+
+    let json = serde_json::to_vec(&signed).unwrap();
+    let flat = signed.to_flatbuffer().unwrap();
+    println!(
+        "JSON {} bytes, FlatBuffer {} bytes, {} iterations",
+        json.len(),
+        flat.len(),
+        ITERATIONS
+    );
+
+    // Everything but the Ed25519 check: getting from wire bytes to the signed message
+    let json_decode = time("JSON decode", || {
+        let decoded: SignedClaim = serde_json::from_slice(black_box(&json)).unwrap();
+        black_box(decoded.signed_message().unwrap());
+    });
+    let flat_decode = time("Flat decode", || {
+        black_box(FlatSignedClaim::from_slice(black_box(&flat)).unwrap());
+    });
+    println!(
+        "speedup                {:>8.2}x",
+        ratio(json_decode, flat_decode)
+    );
+
+    let json_verify = time("JSON verify", || {
+        let decoded: SignedClaim = serde_json::from_slice(black_box(&json)).unwrap();
+        assert!(verify_claim(&decoded).unwrap());
+    });
+    let flat_verify = time("Flat verify", || {
+        assert!(verify_flatbuffer(black_box(&flat)).unwrap());
+    });
+    println!(
+        "speedup                {:>8.2}x",
+        ratio(json_verify, flat_verify)
+    );
+}
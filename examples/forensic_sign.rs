@@ -1,4 +1,4 @@
-use provn_sdk::{generate_keypair, sign_claim, verify_claim, Claim};
+use provn_sdk::{generate_keypair, public_key_hex, sign_claim, verify_claim, Claim};
 
 /// Provncloud Signing Example
 ///
@@ -12,7 +12,7 @@ fn main() {
 
     // 1. SECURE IDENTITY: Generate a unique Ed25519 keypair
     let signing_key = generate_keypair();
-    let public_key = hex::encode(signing_key.verifying_key().as_bytes());
+    let public_key = public_key_hex(&signing_key);
     println!("[1] Identity Generated: ed25519:{}", public_key);
 
     // 2. TRUTH STATEMENT: Define the data to be anchored.
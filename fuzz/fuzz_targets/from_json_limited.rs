@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use provn_sdk::{verify_claim, ParseLimits, SignedClaim};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(json) = core::str::from_utf8(data) {
+        if let Ok(signed) = SignedClaim::<String>::from_json_limited(json, ParseLimits::default()) {
+            let _ = verify_claim(&signed);
+        }
+    }
+});
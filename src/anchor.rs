@@ -0,0 +1,157 @@
+//! Records of where a signed claim has been anchored.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+use crate::{verify_claim_strict, ClaimId, Result, SdkError, SignedClaim};
+
+/// One anchoring transaction on one chain.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AnchorEntry {
+    /// When the transaction was confirmed (UTC seconds)
+    pub anchored_at: u64,
+    /// Chain identifier (e.g. "solana", "arweave")
+    pub chain: String,
+    /// Transaction id on that chain
+    pub tx_id: String,
+}
+
+/// A signed claim anchored to one or more chains for redundancy.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, MultiAnchor, sign_claim, generate_keypair};
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// let mut anchor = MultiAnchor::new(&signed).unwrap();
+/// anchor.add_anchor("solana", "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnb", signed.claim.timestamp).unwrap();
+/// anchor.add_anchor("arweave", "bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U", signed.claim.timestamp).unwrap();
+/// assert!(anchor.add_anchor("solana", "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnb", 0).is_err());
+/// assert!(anchor.verify(&signed).unwrap());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MultiAnchor {
+    /// The anchoring transactions, in the order they were added
+    pub anchors: Vec<AnchorEntry>,
+    /// Hash of the anchored signed claim (see [`SignedClaim::signed_id`])
+    pub signed_claim_hash: ClaimId,
+}
+
+fn check_entry(entry: &AnchorEntry, previous: &[AnchorEntry]) -> Result<()> {
+    if entry.chain.is_empty() || entry.tx_id.is_empty() {
+        return Err(SdkError::ProofError(
+            "anchor entries need a chain and a transaction id".into(),
+        ));
+    }
+    if previous
+        .iter()
+        .any(|e| e.chain == entry.chain && e.tx_id == entry.tx_id)
+    {
+        return Err(SdkError::ProofError(format!(
+            "duplicate anchor {}:{}",
+            entry.chain, entry.tx_id
+        )));
+    }
+    Ok(())
+}
+
+impl MultiAnchor {
+    /// Start an anchor record for a signed claim, with no anchors yet
+    pub fn new(signed_claim: &SignedClaim) -> Result<Self> {
+        Ok(Self {
+            anchors: Vec::new(),
+            signed_claim_hash: signed_claim.signed_id()?,
+        })
+    }
+
+    /// Record an anchoring transaction
+    ///
+    /// Empty chain or transaction ids and duplicate `(chain, tx_id)` pairs are rejected.
+    pub fn add_anchor(
+        &mut self,
+        chain: impl Into<String>,
+        tx_id: impl Into<String>,
+        anchored_at: u64,
+    ) -> Result<()> {
+        let entry = AnchorEntry {
+            anchored_at,
+            chain: chain.into(),
+            tx_id: tx_id.into(),
+        };
+        check_entry(&entry, &self.anchors)?;
+        self.anchors.push(entry);
+        Ok(())
+    }
+
+    /// Verify the record against the signed claim it describes
+    ///
+    /// Checks that the claim's signature is valid (strictly, see
+    /// [`verify_claim_strict`]), that its hash matches `signed_claim_hash`,
+    /// and that every entry is well-formed: non-empty, unique, and not
+    /// anchored before the claim's own timestamp.
+    pub fn verify(&self, signed_claim: &SignedClaim) -> Result<bool> {
+        verify_claim_strict(signed_claim)?;
+        if signed_claim.signed_id()? != self.signed_claim_hash {
+            return Err(SdkError::ProofError(
+                "anchor record is for a different signed claim".into(),
+            ));
+        }
+        for (i, entry) in self.anchors.iter().enumerate() {
+            check_entry(entry, &self.anchors[..i])?;
+            if entry.anchored_at < signed_claim.claim.timestamp {
+                return Err(SdkError::ProofError(format!(
+                    "anchor {}:{} predates the claim",
+                    entry.chain, entry.tx_id
+                )));
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+    use alloc::string::ToString;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_multi_anchor_checks() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("a".to_string(), 100), &key).unwrap();
+        let other = sign_claim(&Claim::new_with_timestamp("b".to_string(), 100), &key).unwrap();
+
+        let mut anchor = MultiAnchor::new(&signed).unwrap();
+        anchor.add_anchor("solana", "tx1", 100).unwrap();
+        anchor.add_anchor("arweave", "tx1", 150).unwrap();
+        assert!(anchor.add_anchor("", "tx2", 150).is_err());
+        assert!(anchor.verify(&signed).unwrap());
+        assert!(matches!(
+            anchor.verify(&other),
+            Err(SdkError::ProofError(_))
+        ));
+
+        // Entries injected after deserialization are still checked
+        let mut tampered = anchor.clone();
+        tampered.anchors.push(anchor.anchors[0].clone());
+        assert!(tampered.verify(&signed).is_err());
+
+        let mut early = anchor.clone();
+        early.anchors[1].anchored_at = 99;
+        assert!(early.verify(&signed).is_err());
+
+        // A small-order key and signature that lenient verification accepts
+        let mut forged = signed.clone();
+        forged.public_key = format!("01{}", "00".repeat(31));
+        forged.signature = format!("01{}", "00".repeat(63));
+        assert!(crate::verify_claim(&forged).unwrap());
+        let mut forged_anchor = MultiAnchor::new(&forged).unwrap();
+        forged_anchor.add_anchor("solana", "tx1", 100).unwrap();
+        assert!(matches!(
+            forged_anchor.verify(&forged),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+}
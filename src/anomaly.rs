@@ -0,0 +1,167 @@
+//! Forensic comparison of two signatures over the same claim.
+//!
+//! Ed25519 signing is deterministic (RFC 8032): the nonce is derived from the
+//! secret key and the message, so one key signing one message always yields
+//! one signature. Two *different* signatures from the same public key over
+//! the same signed message therefore cannot come from a conforming signer
+//! holding the only copy of the key. Either one was tampered with, the
+//! signer uses non-standard (random or faulty) nonces, or someone else holds
+//! the key and signs with a different implementation. None of these should
+//! happen, and all of them warrant investigation.
+//!
+//! The comparison is over [`SignedClaim::signed_message`], not the bare claim:
+//! one claim signed with and without a domain, over different field lists or
+//! under a metadata commitment is several messages, and legitimately has
+//! several signatures. Claims signed with
+//! [`sign_claim_unique`](crate::sign_claim_unique) carry distinct salts, so
+//! their messages differ too.
+//!
+//! Associated data passed to [`sign_claim_aad`](crate::sign_claim_aad) is the
+//! exception: it is signed but not stored in the claim, so it is not part of
+//! the signed message this module compares. Two AAD signatures of one claim
+//! under different AAD are reported as anomalous, and neither counts as
+//! valid; check them with [`verify_claim_aad`](crate::verify_claim_aad)
+//! instead.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decode_public_key_bytes, decode_signature_bytes, verify_claim_fields, Result, SignedClaim,
+};
+
+/// The outcome of [`detect_signature_anomaly`].
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct AnomalyReport {
+    /// Whether both claims have identical signed messages
+    pub same_claim: bool,
+    /// Whether both claims name the same public key (compared as raw bytes)
+    pub same_key: bool,
+    /// Whether the two signatures differ (compared as raw bytes)
+    pub signatures_differ: bool,
+    /// Whether the first signature verifies over its signed message
+    pub first_valid: bool,
+    /// Whether the second signature verifies over its signed message
+    pub second_valid: bool,
+    /// Same message, same key, different signatures: impossible for a
+    /// deterministic Ed25519 signer
+    pub anomalous: bool,
+}
+
+/// Compare two signed claims for signatures that deterministic Ed25519
+/// could not have produced
+///
+/// The claims are anomalous when their signed messages and public keys are
+/// identical but their signatures are not. [`AnomalyReport::first_valid`]
+/// and [`AnomalyReport::second_valid`] help tell the causes apart: if only
+/// one verifies, the other was tampered with; if both verify, someone made
+/// two valid signatures over the same message with the key, which points at
+/// a signer with random or faulty nonces or at a second holder of the key.
+/// This does not hold for claims signed with
+/// [`sign_claim_aad`](crate::sign_claim_aad): the associated data is not
+/// stored in the claim, so signatures over different AAD look anomalous and
+/// neither verifies here.
+/// Keys and signatures are compared after decoding, so an upper-case and a
+/// lower-case hex encoding of the same signature is not an anomaly. Fails
+/// only if a key or signature cannot be decoded or a signed message cannot
+/// be built.
+///
+/// # Example
+/// ```
+/// use ed25519_dalek::Signer;
+/// use provn_sdk::{Claim, sign_claim, detect_signature_anomaly, generate_keypair};
+/// let key = generate_keypair();
+/// let claim = Claim::new_with_timestamp("Vault opened".to_string(), 1700000000);
+/// let first = sign_claim(&claim, &key).unwrap();
+///
+/// let report = detect_signature_anomaly(&first, &first.clone()).unwrap();
+/// assert!(!report.anomalous);
+///
+/// // Someone swaps in another signature made with the same key
+/// let mut second = first.clone();
+/// second.signature = hex::encode(key.sign(b"something else").to_bytes());
+/// let report = detect_signature_anomaly(&first, &second).unwrap();
+/// assert!(report.anomalous);
+/// assert!(report.first_valid && !report.second_valid);
+/// ```
+pub fn detect_signature_anomaly<T: Serialize>(
+    a: &SignedClaim<T>,
+    b: &SignedClaim<T>,
+) -> Result<AnomalyReport> {
+    let same_claim = a.signed_message()? == b.signed_message()?;
+    let same_key =
+        decode_public_key_bytes(&a.public_key)? == decode_public_key_bytes(&b.public_key)?;
+    let signatures_differ =
+        decode_signature_bytes(&a.signature)? != decode_signature_bytes(&b.signature)?;
+    Ok(AnomalyReport {
+        same_claim,
+        same_key,
+        signatures_differ,
+        first_valid: verify_claim_fields(a).is_ok(),
+        second_valid: verify_claim_fields(b).is_ok(),
+        anomalous: same_claim && same_key && signatures_differ,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, sign_claim_aad, sign_claim_with_domain, verify_claim_aad, Claim};
+    use alloc::string::ToString;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn test_only_same_claim_same_key_is_anomalous() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let signed = sign_claim(&claim, &key).unwrap();
+
+        let mut upper = signed.clone();
+        upper.signature = upper.signature.to_uppercase();
+        assert!(!detect_signature_anomaly(&signed, &upper).unwrap().anomalous);
+
+        let other = sign_claim(&Claim::new_with_timestamp("y".to_string(), 1), &key).unwrap();
+        let report = detect_signature_anomaly(&signed, &other).unwrap();
+        assert!(!report.same_claim && report.signatures_differ && !report.anomalous);
+
+        let other_key = sign_claim(&claim, &SigningKey::from_bytes(&[1u8; 32])).unwrap();
+        let report = detect_signature_anomaly(&signed, &other_key).unwrap();
+        assert!(!report.same_key && !report.anomalous);
+
+        // A valid signature over different bytes, transplanted onto the claim
+        let mut forged = signed.clone();
+        forged.signature = hex::encode(key.sign(b"other").to_bytes());
+        let report = detect_signature_anomaly(&signed, &forged).unwrap();
+        assert!(report.anomalous && report.first_valid && !report.second_valid);
+
+        forged.signature = "zz".to_string();
+        assert!(detect_signature_anomaly(&signed, &forged).is_err());
+    }
+
+    #[test]
+    fn test_different_messages_for_one_claim_are_not_anomalous() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let plain = sign_claim(&claim, &key).unwrap();
+        let bound = sign_claim_with_domain(&claim, &key, "provn-v1").unwrap();
+
+        let report = detect_signature_anomaly(&plain, &bound).unwrap();
+        assert!(report.first_valid && report.second_valid);
+        assert!(report.same_key && report.signatures_differ);
+        assert!(!report.same_claim && !report.anomalous);
+    }
+
+    #[test]
+    fn test_associated_data_is_not_compared() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let acme = sign_claim_aad(&claim, &key, b"tenant:acme").unwrap();
+        let other = sign_claim_aad(&claim, &key, b"tenant:other").unwrap();
+        assert!(verify_claim_aad(&acme, b"tenant:acme").unwrap());
+        assert!(verify_claim_aad(&other, b"tenant:other").unwrap());
+
+        // The documented limitation: a false positive with neither side valid
+        let report = detect_signature_anomaly(&acme, &other).unwrap();
+        assert!(report.anomalous);
+        assert!(!report.first_valid && !report.second_valid);
+    }
+}
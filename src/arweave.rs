@@ -0,0 +1,185 @@
+//! Arweave bundle export (ANS-104 DataItems).
+//!
+//! A DataItem is laid out as
+//! `signature type (u16 LE) || signature || owner || target flag || anchor flag ||
+//! tag count (u64 LE) || tag byte length (u64 LE) || Avro tags || data`,
+//! and signed over the ANS-104 "deep hash" (SHA-384) of its fields. Items
+//! produced here use Ed25519 (signature type 2), no target and no anchor, and
+//! carry the canonical signed claim as data.
+
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use sha2::{Digest, Sha384};
+
+use crate::{parse, verify_claim, Result, SdkError, SignedClaim};
+
+/// ANS-104 signature type for Ed25519.
+const ED25519_SIGNATURE_TYPE: u16 = 2;
+/// Content type tag attached to exported claims.
+pub const ANS104_CONTENT_TYPE: &str = "application/provn-claim";
+
+fn deep_hash_blob(data: &[u8]) -> [u8; 48] {
+    let tag = Sha384::digest(format!("blob{}", data.len()));
+    let mut hasher = Sha384::new();
+    hasher.update(tag);
+    hasher.update(Sha384::digest(data));
+    hasher.finalize().into()
+}
+
+/// ANS-104 deep hash of a flat list of blobs.
+fn deep_hash_list(items: &[&[u8]]) -> [u8; 48] {
+    let mut acc: [u8; 48] = Sha384::digest(format!("list{}", items.len())).into();
+    for item in items {
+        let mut hasher = Sha384::new();
+        hasher.update(acc);
+        hasher.update(deep_hash_blob(item));
+        acc = hasher.finalize().into();
+    }
+    acc
+}
+
+/// Avro `long`: zig-zag encoded varint.
+fn avro_long(out: &mut Vec<u8>, n: i64) {
+    let mut v = ((n << 1) ^ (n >> 63)) as u64;
+    while v >= 0x80 {
+        out.push((v as u8) | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+/// Avro encoding of an array of `{name: bytes, value: bytes}` records.
+fn avro_tags(tags: &[(&str, &str)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    if tags.is_empty() {
+        return out;
+    }
+    avro_long(&mut out, tags.len() as i64);
+    for (name, value) in tags {
+        for field in [name, value] {
+            avro_long(&mut out, field.len() as i64);
+            out.extend_from_slice(field.as_bytes());
+        }
+    }
+    avro_long(&mut out, 0);
+    out
+}
+
+fn signature_message(owner: &[u8], tags: &[u8], data: &[u8]) -> [u8; 48] {
+    deep_hash_list(&[
+        b"dataitem",
+        b"1",
+        ED25519_SIGNATURE_TYPE.to_string().as_bytes(),
+        owner,
+        b"",
+        b"",
+        tags,
+        data,
+    ])
+}
+
+/// Export a signed claim as an ANS-104 DataItem signed by `owner`
+///
+/// The item is tagged `Content-Type: application/provn-claim` and its data is
+/// [`SignedClaim::to_canonical_bytes`].
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, to_ans104_dataitem, verify_ans104_dataitem, generate_keypair};
+/// let key = generate_keypair();
+/// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &key).unwrap();
+/// let item = to_ans104_dataitem(&signed, &key).unwrap();
+/// assert_eq!(verify_ans104_dataitem(&item).unwrap().claim, signed.claim);
+/// ```
+pub fn to_ans104_dataitem(signed: &SignedClaim, owner: &SigningKey) -> Result<Vec<u8>> {
+    let data = signed.to_canonical_bytes()?;
+    let tags = [("Content-Type", ANS104_CONTENT_TYPE)];
+    let tag_bytes = avro_tags(&tags);
+    let owner_bytes = owner.verifying_key().to_bytes();
+    let signature = owner.sign(&signature_message(&owner_bytes, &tag_bytes, &data));
+
+    let mut item = Vec::with_capacity(2 + 64 + 32 + 2 + 16 + tag_bytes.len() + data.len());
+    item.extend_from_slice(&ED25519_SIGNATURE_TYPE.to_le_bytes());
+    item.extend_from_slice(&signature.to_bytes());
+    item.extend_from_slice(&owner_bytes);
+    item.push(0); // no target
+    item.push(0); // no anchor
+    item.extend_from_slice(&(tags.len() as u64).to_le_bytes());
+    item.extend_from_slice(&(tag_bytes.len() as u64).to_le_bytes());
+    item.extend_from_slice(&tag_bytes);
+    item.extend_from_slice(&data);
+    Ok(item)
+}
+
+fn malformed(reason: &str) -> SdkError {
+    SdkError::SerializationError(format!("malformed ANS-104 data item: {}", reason))
+}
+
+/// Verify a DataItem produced by [`to_ans104_dataitem`] and return its claim
+///
+/// Both the DataItem signature and the embedded claim signature are checked.
+/// Only Ed25519 items without target or anchor are supported.
+pub fn verify_ans104_dataitem(item: &[u8]) -> Result<SignedClaim> {
+    const HEADER_LEN: usize = 2 + 64 + 32 + 2 + 16;
+    if item.len() < HEADER_LEN {
+        return Err(malformed("too short"));
+    }
+    if item[..2] != ED25519_SIGNATURE_TYPE.to_le_bytes() {
+        return Err(malformed("unsupported signature type"));
+    }
+    let signature = Signature::from_slice(&item[2..66])?;
+    let owner = &item[66..98];
+    if item[98] != 0 || item[99] != 0 {
+        return Err(malformed("target and anchor are not supported"));
+    }
+    let tag_len = u64::from_le_bytes(item[108..116].try_into().expect("slice is 8 bytes"));
+    let rest = &item[HEADER_LEN..];
+    let tag_len = usize::try_from(tag_len)
+        .ok()
+        .filter(|&len| len <= rest.len())
+        .ok_or_else(|| malformed("tag length out of bounds"))?;
+    let (tags, data) = rest.split_at(tag_len);
+
+    let owner_key = VerifyingKey::from_bytes(owner.try_into().expect("slice is 32 bytes"))?;
+    owner_key.verify(&signature_message(owner, tags, data), &signature)?;
+
+    let signed: SignedClaim = parse::from_slice_unique(data)?;
+    verify_claim(&signed)?;
+    Ok(signed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+
+    #[test]
+    fn test_avro_tag_encoding() {
+        // count 1 (zig-zag 2), "ab" (4), "c" (2), end of array
+        assert_eq!(avro_tags(&[("ab", "c")]), [2, 4, b'a', b'b', 2, b'c', 0]);
+        assert!(avro_tags(&[]).is_empty());
+        let mut long = Vec::new();
+        avro_long(&mut long, 64);
+        assert_eq!(long, [0x80, 0x01]);
+    }
+
+    #[test]
+    fn test_dataitem_tamper_detection() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("ar".to_string(), 1), &key).unwrap();
+        let item = to_ans104_dataitem(&signed, &key).unwrap();
+        assert!(item
+            .windows(ANS104_CONTENT_TYPE.len())
+            .any(|w| w == ANS104_CONTENT_TYPE.as_bytes()));
+
+        let mut tampered = item.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            verify_ans104_dataitem(&tampered),
+            Err(SdkError::SignatureError(_))
+        ));
+        assert!(verify_ans104_dataitem(&item[..50]).is_err());
+    }
+}
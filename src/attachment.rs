@@ -0,0 +1,166 @@
+//! External artifacts bound to a claim by hash.
+//!
+//! [`Claim::attachments`] lists named artifacts stored elsewhere, each with
+//! its digest. The list is part of the canonical claim, so the signature
+//! authenticates every file it names; claims without attachments serialize
+//! exactly as before.
+
+use alloc::format;
+use alloc::string::String;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    compute_hash_multi, verify_claim_strict, Claim, HashAlg, Result, SdkError, SignedClaim,
+};
+
+/// An artifact referenced by a claim.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    /// Hash algorithm of `hash`
+    pub alg: HashAlg,
+    /// Digest of the artifact (Hex encoded)
+    pub hash: String,
+    /// Name identifying the artifact within the claim, e.g. a file name
+    pub name: String,
+    /// Optional size of the artifact in bytes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+impl Attachment {
+    /// Describe `data` as an attachment named `name`, recording its size
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Attachment, HashAlg};
+    /// let attachment = Attachment::new("report.pdf", b"%PDF-1.7", HashAlg::Sha256);
+    /// assert_eq!(attachment.size, Some(8));
+    /// ```
+    pub fn new(name: impl Into<String>, data: &[u8], alg: HashAlg) -> Self {
+        Self {
+            alg,
+            hash: compute_hash_multi(&[data], alg),
+            name: name.into(),
+            size: Some(data.len() as u64),
+        }
+    }
+}
+
+/// Verify a signed claim strictly (see [`verify_claim_strict`]) and check
+/// that `data` is its attachment `name`
+///
+/// Fails with [`SdkError::ProofError`] if the claim has no attachment of
+/// that name (or more than one), or if `data` does not match its size or hash.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Attachment, Claim, HashAlg, sign_claim, verify_attachment, generate_keypair};
+/// let mut claim = Claim::new_with_timestamp("Audit bundle".to_string(), 1);
+/// claim.attachments = Some(vec![Attachment::new("log.txt", b"all clear", HashAlg::Sha256)]);
+/// let signed = sign_claim(&claim, &generate_keypair()).unwrap();
+/// assert!(verify_attachment(&signed, "log.txt", b"all clear").unwrap());
+/// assert!(verify_attachment(&signed, "log.txt", b"all good").is_err());
+/// ```
+pub fn verify_attachment<T: Serialize>(
+    signed_claim: &SignedClaim<T>,
+    name: &str,
+    data: &[u8],
+) -> Result<bool> {
+    verify_claim_strict(signed_claim)?;
+    let attachment = find(&signed_claim.claim, name)?;
+    if let Some(size) = attachment.size.filter(|&size| size != data.len() as u64) {
+        return Err(SdkError::ProofError(format!(
+            "attachment {} is {} bytes, but {} were signed",
+            name,
+            data.len(),
+            size
+        )));
+    }
+    if !compute_hash_multi(&[data], attachment.alg).eq_ignore_ascii_case(&attachment.hash) {
+        return Err(SdkError::ProofError(format!(
+            "attachment {} does not match the signed hash",
+            name
+        )));
+    }
+    Ok(true)
+}
+
+fn find<'a, T>(claim: &'a Claim<T>, name: &str) -> Result<&'a Attachment> {
+    let mut matches = claim
+        .attachments
+        .iter()
+        .flatten()
+        .filter(|attachment| attachment.name == name);
+    match (matches.next(), matches.next()) {
+        (Some(attachment), None) => Ok(attachment),
+        (None, _) => Err(SdkError::ProofError(format!(
+            "claim has no attachment named {}",
+            name
+        ))),
+        (Some(_), Some(_)) => Err(SdkError::ProofError(format!(
+            "claim has several attachments named {}",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sign_claim;
+    use alloc::string::ToString;
+    use alloc::vec;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_attachments_are_signed() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let legacy = claim.to_signable_bytes().unwrap();
+        claim.attachments = Some(vec![
+            Attachment::new("a", b"first", HashAlg::Sha256),
+            Attachment {
+                size: None,
+                ..Attachment::new("b", b"second", HashAlg::Sha512)
+            },
+        ]);
+        assert_eq!(legacy, br#"{"data":"x","timestamp":1}"#);
+        let canonical = String::from_utf8(claim.to_signable_bytes().unwrap()).unwrap();
+        assert!(canonical.starts_with(r#"{"attachments":[{"alg":"sha256","hash":""#));
+
+        let mut signed = sign_claim(&claim, &key).unwrap();
+        assert!(verify_attachment(&signed, "a", b"first").unwrap());
+        assert!(verify_attachment(&signed, "b", b"second").unwrap());
+        assert!(verify_attachment(&signed, "a", b"secnd").is_err());
+        assert!(verify_attachment(&signed, "c", b"first").is_err());
+
+        signed.claim.attachments.as_mut().unwrap()[0].hash =
+            compute_hash_multi(&[b"other"], HashAlg::Sha256);
+        assert!(matches!(
+            verify_attachment(&signed, "a", b"other"),
+            Err(SdkError::SignatureError(_))
+        ));
+
+        // A small-order key and signature that lenient verification accepts
+        signed.public_key = format!("01{}", "00".repeat(31));
+        signed.signature = format!("01{}", "00".repeat(63));
+        assert!(crate::verify_claim(&signed).unwrap());
+        assert!(matches!(
+            verify_attachment(&signed, "a", b"other"),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_duplicate_names_are_ambiguous() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let attachment = Attachment::new("a", b"first", HashAlg::Sha256);
+        claim.attachments = Some(vec![attachment.clone(), attachment]);
+        let signed = sign_claim(&claim, &key).unwrap();
+        assert!(matches!(
+            verify_attachment(&signed, "a", b"first"),
+            Err(SdkError::ProofError(_))
+        ));
+    }
+}
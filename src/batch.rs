@@ -0,0 +1,330 @@
+//! Filtering and grouping collections of signed claims by validity.
+//!
+//! All helpers verify with [`verify_claim_strict`], so malleable signatures
+//! and small-order keys count as invalid, and preserve the input order.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::Serialize;
+
+use crate::{
+    content_id, decode_public_key_bytes, verify_claim_strict, Result, SdkError, SignedClaim,
+};
+
+/// Verify every claim, returning one result per claim in input order
+///
+/// With the `metrics` feature, each claim's outcome is counted like a
+/// [`verify_claim_strict`] call.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_batch, generate_keypair};
+/// let key = generate_keypair();
+/// let good = sign_claim(&Claim::new_with_timestamp("a".to_string(), 1), &key).unwrap();
+/// let mut bad = good.clone();
+/// bad.claim.data = "forged".to_string();
+/// let results = verify_batch(&[good, bad]);
+/// assert!(results[0].is_ok() && results[1].is_err());
+/// ```
+pub fn verify_batch<T: Serialize>(claims: &[SignedClaim<T>]) -> Vec<Result<bool>> {
+    claims.iter().map(verify_claim_strict).collect()
+}
+
+/// Keep only the claims whose signature verifies
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, retain_valid, sign_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let good = sign_claim(&Claim::new_with_timestamp("a".to_string(), 1), &key).unwrap();
+/// let mut bad = good.clone();
+/// bad.claim.data = "forged".to_string();
+/// let kept = retain_valid(vec![bad, good.clone()]);
+/// assert_eq!(kept.len(), 1);
+/// assert_eq!(kept[0].claim, good.claim);
+/// ```
+pub fn retain_valid<T: Serialize>(mut claims: Vec<SignedClaim<T>>) -> Vec<SignedClaim<T>> {
+    claims.retain(|signed| verify_claim_strict(signed).is_ok());
+    claims
+}
+
+/// Borrow the claims whose signature verifies
+pub fn filter_valid<T: Serialize>(claims: &[SignedClaim<T>]) -> Vec<&SignedClaim<T>> {
+    claims
+        .iter()
+        .filter(|signed| verify_claim_strict(*signed).is_ok())
+        .collect()
+}
+
+/// A claim that failed verification, with the reason.
+pub type InvalidClaim<T = String> = (SignedClaim<T>, SdkError);
+
+/// Split claims into the valid ones and the invalid ones with the reason each failed
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, partition_valid, sign_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let good = sign_claim(&Claim::new_with_timestamp("a".to_string(), 1), &key).unwrap();
+/// let mut bad = good.clone();
+/// bad.signature = "00".to_string();
+/// let (valid, invalid) = partition_valid(vec![good, bad]);
+/// assert_eq!(valid.len(), 1);
+/// eprintln!("rejected: {}", invalid[0].1);
+/// ```
+pub fn partition_valid<T: Serialize>(
+    claims: Vec<SignedClaim<T>>,
+) -> (Vec<SignedClaim<T>>, Vec<InvalidClaim<T>>) {
+    let mut valid = Vec::new();
+    let mut invalid = Vec::new();
+    for signed in claims {
+        match verify_claim_strict(&signed) {
+            Ok(_) => valid.push(signed),
+            Err(e) => invalid.push((signed, e)),
+        }
+    }
+    (valid, invalid)
+}
+
+/// Group claims by signer, keyed by lowercase hex public key
+///
+/// Claims that fail verification are left out unless `include_unverified`
+/// is set; an unverified claim whose key does not decode is then grouped
+/// under its `public_key` string as given. Within a group, claims keep their
+/// input order.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, group_by_signer, sign_claim, generate_keypair};
+/// let (alice, bob) = (generate_keypair(), generate_keypair());
+/// let claims = vec![
+///     sign_claim(&Claim::new_with_timestamp("a1".to_string(), 1), &alice).unwrap(),
+///     sign_claim(&Claim::new_with_timestamp("b1".to_string(), 2), &bob).unwrap(),
+///     sign_claim(&Claim::new_with_timestamp("a2".to_string(), 3), &alice).unwrap(),
+/// ];
+/// let groups = group_by_signer(&claims, false);
+/// let alice_hex = hex::encode(alice.verifying_key().as_bytes());
+/// assert_eq!(groups.len(), 2);
+/// assert_eq!(groups[&alice_hex].len(), 2);
+/// ```
+pub fn group_by_signer<T: Serialize>(
+    claims: &[SignedClaim<T>],
+    include_unverified: bool,
+) -> BTreeMap<String, Vec<&SignedClaim<T>>> {
+    let mut groups: BTreeMap<String, Vec<&SignedClaim<T>>> = BTreeMap::new();
+    for signed in claims {
+        if !include_unverified && verify_claim_strict(signed).is_err() {
+            continue;
+        }
+        let signer = decode_public_key_bytes(&signed.public_key)
+            .map(hex::encode)
+            .unwrap_or_else(|_| signed.public_key.clone());
+        groups.entry(signer).or_default().push(signed);
+    }
+    groups
+}
+
+/// Verify claims and keep the first valid one per [`content_id`]
+///
+/// Claims that fail verification are dropped, as are later claims with the
+/// same data and metadata, whatever their timestamp, signer or other fields.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, dedup_by_content, sign_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let claims = vec![
+///     sign_claim(&Claim::new_with_timestamp("door 4 opened".to_string(), 20), &key).unwrap(),
+///     sign_claim(&Claim::new_with_timestamp("door 4 opened".to_string(), 10), &key).unwrap(),
+///     sign_claim(&Claim::new_with_timestamp("door 5 opened".to_string(), 30), &key).unwrap(),
+/// ];
+/// let unique = dedup_by_content(claims);
+/// assert_eq!(unique.len(), 2);
+/// assert_eq!(unique[0].claim.timestamp, 20);
+/// ```
+pub fn dedup_by_content<T: Serialize>(claims: Vec<SignedClaim<T>>) -> Vec<SignedClaim<T>> {
+    dedup_valid(claims, |_, _| false)
+}
+
+/// Like [`dedup_by_content`], but keep the duplicate with the earliest
+/// timestamp (the first seen among equal timestamps)
+///
+/// Each kept claim takes the position where its content first appeared.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, dedup_by_content_earliest, sign_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let claims = vec![
+///     sign_claim(&Claim::new_with_timestamp("door 4 opened".to_string(), 20), &key).unwrap(),
+///     sign_claim(&Claim::new_with_timestamp("door 4 opened".to_string(), 10), &key).unwrap(),
+/// ];
+/// assert_eq!(dedup_by_content_earliest(claims)[0].claim.timestamp, 10);
+/// ```
+pub fn dedup_by_content_earliest<T: Serialize>(claims: Vec<SignedClaim<T>>) -> Vec<SignedClaim<T>> {
+    dedup_valid(claims, |kept, candidate| {
+        candidate.claim.timestamp < kept.claim.timestamp
+    })
+}
+
+/// Keep one valid claim per content ID, replacing the kept one whenever
+/// `replace(kept, candidate)` holds.
+fn dedup_valid<T: Serialize>(
+    claims: Vec<SignedClaim<T>>,
+    replace: impl Fn(&SignedClaim<T>, &SignedClaim<T>) -> bool,
+) -> Vec<SignedClaim<T>> {
+    let mut kept: Vec<SignedClaim<T>> = Vec::new();
+    let mut positions: BTreeMap<String, usize> = BTreeMap::new();
+    for signed in claims {
+        if verify_claim_strict(&signed).is_err() {
+            continue;
+        }
+        let Ok(id) = content_id(&signed.claim) else {
+            continue;
+        };
+        match positions.get(&id) {
+            Some(&position) => {
+                if replace(&kept[position], &signed) {
+                    kept[position] = signed;
+                }
+            }
+            None => {
+                positions.insert(id, kept.len());
+                kept.push(signed);
+            }
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, verify_claim, Claim};
+    use alloc::format;
+    use alloc::string::ToString;
+    use ed25519_dalek::SigningKey;
+
+    /// Identity key with R = identity, s = 0: passes lenient verification
+    fn small_order_forgery(data: &str) -> SignedClaim {
+        let mut forged = sign_claim(
+            &Claim::new_with_timestamp(data.to_string(), 1),
+            &SigningKey::from_bytes(&[0u8; 32]),
+        )
+        .unwrap();
+        forged.public_key = format!("01{}", "00".repeat(31));
+        forged.signature = format!("01{}", "00".repeat(63));
+        assert!(verify_claim(&forged).unwrap());
+        forged
+    }
+
+    #[test]
+    fn test_order_preserved_and_reasons_reported() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claims: Vec<SignedClaim> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|d| sign_claim(&Claim::new_with_timestamp(d.to_string(), 1), &key).unwrap())
+            .enumerate()
+            .map(|(i, mut signed)| {
+                if i % 2 == 1 {
+                    signed.claim.timestamp = 2;
+                }
+                signed
+            })
+            .collect();
+
+        claims.push(small_order_forgery("e"));
+
+        let data = |c: &[&SignedClaim]| {
+            c.iter()
+                .map(|s| s.claim.data.clone())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(data(&filter_valid(&claims)), ["a", "c"]);
+        let retained = retain_valid(claims.clone());
+        assert_eq!(data(&retained.iter().collect::<Vec<_>>()), ["a", "c"]);
+
+        let (valid, invalid) = partition_valid(claims);
+        assert_eq!(valid.len(), 2);
+        assert_eq!(invalid[0].0.claim.data, "b");
+        assert!(matches!(invalid[1].1, SdkError::SignatureError(_)));
+        assert_eq!(invalid[2].0.claim.data, "e");
+    }
+
+    #[test]
+    fn test_group_by_signer() {
+        let alice = SigningKey::from_bytes(&[0u8; 32]);
+        let bob = SigningKey::from_bytes(&[1u8; 32]);
+        let sign = |data: &str, key: &SigningKey| {
+            sign_claim(&Claim::new_with_timestamp(data.to_string(), 1), key).unwrap()
+        };
+        let mut forged = sign("b2", &bob);
+        forged.claim.data = "forged".to_string();
+        let mut upper = sign("a2", &alice);
+        upper.public_key = upper.public_key.to_uppercase();
+        let claims = [
+            sign("a1", &alice),
+            sign("b1", &bob),
+            forged,
+            upper,
+            small_order_forgery("x"),
+        ];
+
+        let alice_hex = hex::encode(alice.verifying_key().as_bytes());
+        let bob_hex = hex::encode(bob.verifying_key().as_bytes());
+        let data = |c: &[&SignedClaim]| {
+            c.iter()
+                .map(|s| s.claim.data.clone())
+                .collect::<Vec<String>>()
+        };
+
+        let verified = group_by_signer(&claims, false);
+        assert_eq!(data(&verified[&alice_hex]), ["a1", "a2"]);
+        assert_eq!(data(&verified[&bob_hex]), ["b1"]);
+        assert_eq!(verified.len(), 2);
+
+        let all = group_by_signer(&claims, true);
+        assert_eq!(data(&all[&bob_hex]), ["b1", "forged"]);
+        assert_eq!(all.len(), 3);
+    }
+
+    #[test]
+    fn test_dedup_by_content() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let sign = |data: &str, timestamp: u64, metadata: Option<&str>| {
+            let mut claim = Claim::new_with_timestamp(data.to_string(), timestamp);
+            claim.metadata = metadata.map(String::from);
+            sign_claim(&claim, &key).unwrap()
+        };
+        let mut forged = sign("c", 1, None);
+        forged.claim.data = "a".to_string();
+        let claims = vec![
+            forged,
+            small_order_forgery("a"),
+            sign("a", 30, None),
+            sign("b", 5, None),
+            sign("a", 10, None),
+            sign("a", 20, Some("m")),
+            sign("a", 10, None),
+        ];
+        let summary = |claims: Vec<SignedClaim>| {
+            claims
+                .iter()
+                .map(|s| (s.claim.data.clone(), s.claim.timestamp))
+                .collect::<Vec<_>>()
+        };
+        let first = [
+            ("a".to_string(), 30),
+            ("b".to_string(), 5),
+            ("a".to_string(), 20),
+        ];
+        assert_eq!(summary(dedup_by_content(claims.clone())), first);
+        let earliest = [
+            ("a".to_string(), 10),
+            ("b".to_string(), 5),
+            ("a".to_string(), 20),
+        ];
+        assert_eq!(summary(dedup_by_content_earliest(claims)), earliest);
+    }
+}
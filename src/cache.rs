@@ -0,0 +1,142 @@
+//! Memoized claim verification.
+//!
+//! A [`VerifyCache`] remembers the [`SignedClaim::signed_id`] of claims that
+//! verified successfully with [`verify_claim_strict`]. The hash covers the
+//! claim, public key and signature, so a tampered copy of a cached claim
+//! hashes differently and is verified from scratch. Failed verifications are
+//! never cached.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+
+use crate::{verify_claim_strict, ClaimId, Result, SignedClaim};
+
+/// A bounded, least-recently-used cache of successful verifications.
+#[derive(Debug, Clone)]
+pub struct VerifyCache {
+    capacity: usize,
+    tick: u64,
+    /// Signed-claim hash to the tick of its last use
+    entries: HashMap<ClaimId, u64>,
+    /// Tick of last use to signed-claim hash, oldest first
+    recency: BTreeMap<u64, ClaimId>,
+}
+
+impl VerifyCache {
+    /// Create a cache holding at most `capacity` verified claims
+    ///
+    /// A capacity of zero disables caching.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    /// Maximum number of cached claims
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of cached claims
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Forget every cached verification
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Verify a signed claim, skipping the signature check if this exact
+    /// claim already verified
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, VerifyCache, sign_claim, generate_keypair};
+    /// let signed = sign_claim(&Claim::new_with_timestamp("retry".to_string(), 1), &generate_keypair()).unwrap();
+    /// let mut cache = VerifyCache::new(128);
+    /// assert!(cache.verify_cached(&signed).unwrap());
+    /// assert!(cache.verify_cached(&signed).unwrap()); // served from the cache
+    /// assert_eq!(cache.len(), 1);
+    /// ```
+    pub fn verify_cached<T: Serialize>(&mut self, signed: &SignedClaim<T>) -> Result<bool> {
+        let hash = signed.signed_id()?;
+        self.tick += 1;
+        if let Some(last_used) = self.entries.get_mut(&hash) {
+            self.recency.remove(last_used);
+            *last_used = self.tick;
+            self.recency.insert(self.tick, hash);
+            return Ok(true);
+        }
+
+        verify_claim_strict(signed)?;
+        if self.capacity == 0 {
+            return Ok(true);
+        }
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(hash, self.tick);
+        self.recency.insert(self.tick, hash);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+    use ed25519_dalek::SigningKey;
+
+    fn signed(data: &str) -> SignedClaim {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        sign_claim(&Claim::new_with_timestamp(data.to_string(), 1), &key).unwrap()
+    }
+
+    #[test]
+    fn test_tampered_claim_misses_cache() {
+        let mut cache = VerifyCache::new(4);
+        let good = signed("payload");
+        assert!(cache.verify_cached(&good).unwrap());
+
+        let mut tampered = good.clone();
+        tampered.claim.data = "forged".to_string();
+        assert!(cache.verify_cached(&tampered).is_err());
+        assert_eq!(cache.len(), 1);
+
+        // A small-order key and signature that lenient verification accepts
+        let mut weak = good;
+        weak.public_key = alloc::format!("01{}", "00".repeat(31));
+        weak.signature = alloc::format!("01{}", "00".repeat(63));
+        assert!(crate::verify_claim(&weak).unwrap());
+        assert!(cache.verify_cached(&weak).is_err());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let mut cache = VerifyCache::new(2);
+        let (a, b, c) = (signed("a"), signed("b"), signed("c"));
+        cache.verify_cached(&a).unwrap();
+        cache.verify_cached(&b).unwrap();
+        cache.verify_cached(&a).unwrap(); // `b` is now the oldest
+        cache.verify_cached(&c).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.entries.contains_key(&a.signed_id().unwrap()));
+        assert!(!cache.entries.contains_key(&b.signed_id().unwrap()));
+        assert!(cache.entries.contains_key(&c.signed_id().unwrap()));
+    }
+}
@@ -0,0 +1,612 @@
+//! JSON Canonicalization Scheme (RFC 8785).
+//!
+//! Values are first converted to a `serde_json::Value` and then written with:
+//!
+//! - object keys sorted by their UTF-16 code units, at every depth, including
+//!   objects nested inside arrays;
+//! - array elements kept in their original order;
+//! - numbers formatted like ECMAScript `Number.prototype.toString`;
+//! - strings escaped minimally, as `JSON.stringify` does;
+//! - no insignificant whitespace.
+//!
+//! Integers outside the JavaScript safe range (`±(2^53 - 1)`) are rejected,
+//! because a JavaScript verifier would round them and produce different bytes.
+//! NaN and infinite numbers have no JSON form and are rejected too
+//! (RFC 8785, section 3.2.2.3), rather than written as `null` the way
+//! serde_json does.
+//!
+//! Numbers are written from their numeric value, never from serde_json's
+//! text for them, so the output does not change when a downstream crate
+//! enables serde_json's `arbitrary_precision` feature.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+use serde::{ser, Serialize};
+use serde_json::{Number, Value};
+
+use crate::trace::{emit, span};
+use crate::{Claim, Result, SdkError, MAX_SAFE_INTEGER};
+
+/// Serialize any value to its RFC 8785 canonical JSON bytes
+///
+/// This is what [`Claim::to_signable_bytes`](crate::Claim::to_signable_bytes)
+/// uses, so payloads with maps or out-of-order struct fields still sign
+/// deterministically.
+///
+/// # Example
+/// ```
+/// use provn_sdk::canonicalize;
+/// let value = serde_json::json!([{"b": 1, "a": 2}, {"z": 0, "y": 0}]);
+/// assert_eq!(
+///     canonicalize(&value).unwrap(),
+///     br#"[{"a":2,"b":1},{"y":0,"z":0}]"#
+/// );
+/// ```
+pub fn canonicalize<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>> {
+    let mut out = String::new();
+    canonicalize_into(value, &mut out)?;
+    Ok(out.into_bytes())
+}
+
+/// Replace the contents of `out` with the canonical JSON of `value`.
+fn canonicalize_into<T: Serialize + ?Sized>(value: &T, out: &mut String) -> Result<()> {
+    span!();
+    let json = serde_json::to_value(value)?;
+    // serde_json turns NaN and infinities into null, so only a value with a
+    // null in it needs the second pass that tells them apart
+    if contains_null(&json) {
+        value.serialize(FiniteCheck)?;
+    }
+    let value = json;
+    out.clear();
+    write_value(out, &value)?;
+    emit!(len = out.len(), "canonicalized");
+    Ok(())
+}
+
+/// Canonicalize many claims, reusing one output buffer
+///
+/// Returns the same bytes as calling
+/// [`Claim::to_signable_bytes`](crate::Claim::to_signable_bytes) on each
+/// claim, in input order, but each claim is written into a buffer that has
+/// already grown to fit, and then copied out at its exact size. Fails on the
+/// first claim that cannot be canonicalized.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, canonicalize_many};
+/// let claims: Vec<Claim> = (0..3)
+///     .map(|i| Claim::new_with_timestamp(format!("reading {}", i), i))
+///     .collect();
+/// let bytes = canonicalize_many(&claims).unwrap();
+/// assert_eq!(bytes[2], claims[2].to_signable_bytes().unwrap());
+/// ```
+pub fn canonicalize_many<T: Serialize>(claims: &[Claim<T>]) -> Result<Vec<Vec<u8>>> {
+    let mut out = String::new();
+    claims
+        .iter()
+        .map(|claim| {
+            claim.check_timestamp()?;
+            canonicalize_into(claim, &mut out)?;
+            Ok(out.as_bytes().to_vec())
+        })
+        .collect()
+}
+
+fn write_value(out: &mut String, value: &Value) -> Result<()> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n)?,
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item)?;
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            emit!(
+                keys = ?entries.iter().map(|(key, _)| key.as_str()).collect::<Vec<_>>(),
+                "sorted object keys"
+            );
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+fn write_string(out: &mut String, s: &str) {
+    // Escape exactly what JCS requires (and `JSON.stringify` does): quote,
+    // backslash and control characters, using the short forms and lowercase
+    // `\u00xx` for the rest. Everything else, including U+2028, is verbatim.
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_number(out: &mut String, n: &Number) -> Result<()> {
+    // Only the as_* accessors are used: with `arbitrary_precision`, Display
+    // would echo the source text ("4.50", "1E2").
+    if let Some(i) = n.as_i64() {
+        if i.unsigned_abs() > MAX_SAFE_INTEGER {
+            return Err(unsafe_integer(n));
+        }
+        // Writing to a String cannot fail
+        let _ = write!(out, "{}", i);
+    } else if n.is_u64() {
+        return Err(unsafe_integer(n));
+    } else {
+        let f = n
+            .as_f64()
+            .ok_or_else(|| SdkError::SerializationError(format!("unsupported number {}", n)))?;
+        // Only reachable with `arbitrary_precision`, which parses "1e400" to infinity
+        if !f.is_finite() {
+            return Err(SdkError::SerializationError(NON_FINITE.into()));
+        }
+        let formatted = format_es_number(f);
+        emit!(input = %n, output = %formatted, "formatted number");
+        out.push_str(&formatted);
+    }
+    Ok(())
+}
+
+const NON_FINITE: &str = "NaN and infinite numbers have no canonical JSON form";
+
+fn contains_null(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.iter().any(contains_null),
+        Value::Object(map) => map.values().any(contains_null),
+        _ => false,
+    }
+}
+
+type Checked = core::result::Result<(), serde_json::Error>;
+
+/// A serializer that writes nothing and fails on the first non-finite float
+struct FiniteCheck;
+
+macro_rules! accept {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(fn $method(self, _: $ty) -> Checked {
+            Ok(())
+        })*
+    };
+}
+
+impl ser::Serializer for FiniteCheck {
+    type Ok = ();
+    type Error = serde_json::Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    accept!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str),
+    );
+
+    fn serialize_f32(self, v: f32) -> Checked {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Checked {
+        if v.is_finite() {
+            Ok(())
+        } else {
+            Err(ser::Error::custom(NON_FINITE))
+        }
+    }
+
+    fn serialize_none(self) -> Checked {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Checked {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Checked {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> Checked {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Checked {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        value: &T,
+    ) -> Checked {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> core::result::Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _: usize) -> core::result::Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> core::result::Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> core::result::Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> core::result::Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> core::result::Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> core::result::Result<Self, serde_json::Error> {
+        Ok(self)
+    }
+
+    fn collect_str<T: core::fmt::Display + ?Sized>(self, _: &T) -> Checked {
+        Ok(())
+    }
+}
+
+macro_rules! check_elements {
+    ($($trait:ident::$method:ident),* $(,)?) => {
+        $(impl ser::$trait for FiniteCheck {
+            type Ok = ();
+            type Error = serde_json::Error;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Checked {
+                value.serialize(FiniteCheck)
+            }
+
+            fn end(self) -> Checked {
+                Ok(())
+            }
+        })*
+    };
+}
+
+check_elements!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field,
+);
+
+impl ser::SerializeMap for FiniteCheck {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Checked {
+        key.serialize(FiniteCheck)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Checked {
+        value.serialize(FiniteCheck)
+    }
+
+    fn end(self) -> Checked {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for FiniteCheck {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _: &'static str, value: &T) -> Checked {
+        value.serialize(FiniteCheck)
+    }
+
+    fn end(self) -> Checked {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStructVariant for FiniteCheck {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, _: &'static str, value: &T) -> Checked {
+        value.serialize(FiniteCheck)
+    }
+
+    fn end(self) -> Checked {
+        Ok(())
+    }
+}
+
+fn unsafe_integer(n: &Number) -> SdkError {
+    SdkError::SerializationError(format!(
+        "integer {} exceeds the JavaScript safe integer range",
+        n
+    ))
+}
+
+/// Format a finite double like ECMAScript `Number.prototype.toString`.
+fn format_es_number(f: f64) -> String {
+    if f == 0.0 {
+        return String::from("0");
+    }
+    // `{:e}` yields the shortest round-tripping digits, e.g. `1.2345e-7`
+    let sci = format!("{:e}", f.abs());
+    let (mantissa, exp) = sci.split_once('e').unwrap_or((&sci, "0"));
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    // The value is 0.<digits> * 10^n
+    let n = exp.parse::<i32>().unwrap_or(0) + 1;
+
+    let mut out = String::new();
+    if f < 0.0 {
+        out.push('-');
+    }
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.extend(core::iter::repeat_n('0', (n - k) as usize));
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = digits.split_at(n as usize);
+        out.push_str(int);
+        out.push('.');
+        out.push_str(frac);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.extend(core::iter::repeat_n('0', (-n) as usize));
+        out.push_str(&digits);
+    } else {
+        let (first, rest) = digits.split_at(1);
+        out.push_str(first);
+        if !rest.is_empty() {
+            out.push('.');
+            out.push_str(rest);
+        }
+        out.push_str(&format!(
+            "e{}{}",
+            if n > 0 { "+" } else { "-" },
+            (n - 1).abs()
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use serde_json::json;
+
+    #[test]
+    fn test_es_number_formatting() {
+        // RFC 8785, Appendix B
+        let vectors: [(u64, &str); 10] = [
+            (0x0000000000000000, "0"),
+            (0x8000000000000000, "0"),
+            (0x0000000000000001, "5e-324"),
+            (0x7fefffffffffffff, "1.7976931348623157e+308"),
+            (0x4340000000000000, "9007199254740992"),
+            (0x444b1ae4d6e2ef50, "1e+21"),
+            (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+            (0x3eb0c6f7a0b5ed8d, "0.000001"),
+            (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+            (0x41b3de4355555555, "333333333.3333333"),
+        ];
+        for (bits, expected) in vectors {
+            assert_eq!(format_es_number(f64::from_bits(bits)), expected);
+        }
+        assert_eq!(format_es_number(-1.5), "-1.5");
+    }
+
+    #[test]
+    fn test_nested_arrays_sort_object_keys() {
+        let value = json!({"list": [{"b": 1, "a": 2}, {"z": 0, "y": 0}], "flag": true});
+        assert_eq!(
+            canonicalize(&value).unwrap(),
+            br#"{"flag":true,"list":[{"a":2,"b":1},{"y":0,"z":0}]}"#
+        );
+    }
+
+    #[test]
+    fn test_string_escaping_matches_serde_json() {
+        let mut all: String = (0u8..0x80).map(char::from).collect();
+        all.push_str("é\u{2028}\u{2029}\u{1f600}\u{fffd}");
+        for s in [all.as_str(), "", "plain"] {
+            let mut out = String::new();
+            write_string(&mut out, s);
+            assert_eq!(out, serde_json::to_string(s).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_canonicalize_many_matches_single() {
+        let mut claims: Vec<Claim<Value>> = vec![
+            Claim::new_with_timestamp(json!({"b": [1.5, "\u{2028}"], "a": null}), 1),
+            Claim::new_with_timestamp(json!("short"), 2),
+        ];
+        claims[1].metadata = Some("m".into());
+        let expected: Vec<_> = claims
+            .iter()
+            .map(|claim| claim.to_signable_bytes().unwrap())
+            .collect();
+        assert_eq!(canonicalize_many(&claims).unwrap(), expected);
+        assert!(canonicalize_many::<Value>(&[]).unwrap().is_empty());
+
+        claims[1].timestamp = u64::MAX;
+        assert!(canonicalize_many(&claims).is_err());
+    }
+
+    #[test]
+    fn test_numbers_independent_of_arbitrary_precision() {
+        // With serde_json's `arbitrary_precision` feature a Number keeps its
+        // source text ("4.50", "1E2"), so the output must come from its value.
+        // CI runs this with and without the feature.
+        let vectors = [
+            ("0", "0"),
+            ("-0", "0"),
+            ("-0.0", "0"),
+            ("1.0", "1"),
+            ("4.50", "4.5"),
+            ("1E2", "100"),
+            ("1e-7", "1e-7"),
+            ("0.000001", "0.000001"),
+            ("1e21", "1e+21"),
+            ("123456789012345678901234567890", "1.2345678901234568e+29"),
+            ("-1.5e-300", "-1.5e-300"),
+            ("9007199254740991", "9007199254740991"),
+            ("-9007199254740991", "-9007199254740991"),
+            ("333333333.33333329", "333333333.3333333"),
+        ];
+        for (input, expected) in vectors {
+            let value: Value = serde_json::from_str(input).unwrap();
+            assert_eq!(
+                canonicalize(&value).unwrap(),
+                expected.as_bytes(),
+                "{}",
+                input
+            );
+        }
+        for f in [0.1, 1e21, -2.5e-8, 1.0] {
+            assert_eq!(
+                canonicalize(&f).unwrap(),
+                format_es_number(f).as_bytes(),
+                "{}",
+                f
+            );
+        }
+        for unsafe_integer in [
+            "9007199254740992",
+            "18446744073709551615",
+            "-9223372036854775808",
+        ] {
+            let value: Value = serde_json::from_str(unsafe_integer).unwrap();
+            assert!(canonicalize(&value).is_err(), "{}", unsafe_integer);
+        }
+    }
+
+    #[test]
+    fn test_non_finite_numbers_are_rejected() {
+        for f in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(
+                canonicalize(&f),
+                Err(SdkError::SerializationError(_))
+            ));
+            let claim = Claim::new_with_timestamp(vec![Some(1.5), None, Some(f)], 1);
+            assert!(claim.to_signable_bytes().is_err());
+        }
+        assert!(canonicalize(&f32::NAN).is_err());
+        // Only parses with `arbitrary_precision`, as a Number that overflows to infinity
+        if let Ok(value) = serde_json::from_str::<Value>("[1e400]") {
+            assert!(canonicalize(&value).is_err());
+        }
+        // A real null next to finite floats is still fine
+        assert_eq!(
+            canonicalize(&(Some(1.5f64), None::<f64>)).unwrap(),
+            b"[1.5,null]"
+        );
+    }
+
+    #[test]
+    fn test_rfc8785_sample() {
+        // RFC 8785, section 3.2.2 and 3.2.3
+        let input = r#"{"numbers":[333333333.33333329,1E30,4.50,2e-3,0.000000000000000000000000001],"string":"\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/","literals":[null,true,false]}"#;
+        let value: Value = serde_json::from_str(input).unwrap();
+        let expected = r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#;
+        assert_eq!(
+            String::from_utf8(canonicalize(&value).unwrap()).unwrap(),
+            expected
+        );
+
+        // Keys are ordered by UTF-16 code units, not UTF-8 bytes
+        let value = json!({"\u{fb33}": 1, "\u{1f600}": 2});
+        assert_eq!(
+            String::from_utf8(canonicalize(&value).unwrap()).unwrap(),
+            "{\"\u{1f600}\":2,\"\u{fb33}\":1}"
+        );
+
+        assert!(canonicalize(&json!(1u64 << 53)).is_err());
+        assert!(canonicalize(&json!(-(1i64 << 53))).is_err());
+    }
+}
@@ -0,0 +1,360 @@
+//! Cross-checking JSON and CBOR canonical forms.
+//!
+//! CBOR output follows the core deterministic encoding requirements of
+//! RFC 8949, section 4.2.1: definite lengths, integers and lengths in their
+//! shortest form, floats in the shortest of half, single or double precision
+//! that preserves the value, and map entries sorted by the bytewise order of
+//! their encoded keys. Only the JSON data model is covered (no byte strings,
+//! tags or undefined).
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+use crate::{canonicalize, Claim, Result, SdkError};
+
+/// Maximum nesting accepted by the decoder.
+const MAX_DEPTH: usize = 128;
+
+fn write_head(out: &mut Vec<u8>, major: u8, n: u64) {
+    let major = major << 5;
+    match n {
+        0..=23 => out.push(major | n as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, n as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(n as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&n.to_be_bytes());
+        }
+    }
+}
+
+/// Half-precision bits of `f`, if the conversion is exact.
+fn to_f16_bits(f: f32) -> Option<u16> {
+    let bits = f.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    if f == 0.0 {
+        return Some(sign);
+    }
+    let exp = ((bits >> 23) & 0xff) as i32 - 127;
+    let mantissa = bits & 0x7f_ffff;
+    match exp {
+        -14..=15 if mantissa & 0x1fff == 0 => {
+            Some(sign | ((exp + 15) as u16) << 10 | (mantissa >> 13) as u16)
+        }
+        -24..=-15 => {
+            let full = mantissa | 0x80_0000;
+            let shift = (-1 - exp) as u32;
+            (full & ((1 << shift) - 1) == 0).then(|| sign | (full >> shift) as u16)
+        }
+        _ => None,
+    }
+}
+
+fn from_f16_bits(h: u16) -> f64 {
+    let sign = if h & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exp = i32::from((h >> 10) & 0x1f);
+    let mantissa = f64::from(h & 0x3ff);
+    match exp {
+        0 => sign * mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => sign * f64::INFINITY,
+        31 => f64::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f64.powi(exp - 15),
+    }
+}
+
+fn write_float(out: &mut Vec<u8>, f: f64) {
+    let single = f as f32;
+    if f64::from(single) != f {
+        out.push(0xfb);
+        out.extend_from_slice(&f.to_be_bytes());
+    } else if let Some(half) = to_f16_bits(single) {
+        out.push(0xf9);
+        out.extend_from_slice(&half.to_be_bytes());
+    } else {
+        out.push(0xfa);
+        out.extend_from_slice(&single.to_be_bytes());
+    }
+}
+
+fn write_number(out: &mut Vec<u8>, n: &Number) -> Result<()> {
+    if let Some(u) = n.as_u64() {
+        write_head(out, 0, u);
+    } else if let Some(i) = n.as_i64() {
+        write_head(out, 1, !(i as u64));
+    } else {
+        let f = n
+            .as_f64()
+            .ok_or_else(|| SdkError::SerializationError(format!("unsupported number {}", n)))?;
+        write_float(out, f);
+    }
+    Ok(())
+}
+
+fn write_value(out: &mut Vec<u8>, value: &Value) -> Result<()> {
+    match value {
+        Value::Null => out.push(0xf6),
+        Value::Bool(b) => out.push(if *b { 0xf5 } else { 0xf4 }),
+        Value::Number(n) => write_number(out, n)?,
+        Value::String(s) => {
+            write_head(out, 3, s.len() as u64);
+            out.extend_from_slice(s.as_bytes());
+        }
+        Value::Array(items) => {
+            write_head(out, 4, items.len() as u64);
+            for item in items {
+                write_value(out, item)?;
+            }
+        }
+        Value::Object(map) => {
+            let mut entries = map
+                .iter()
+                .map(|(key, item)| {
+                    let mut encoded = Vec::new();
+                    write_value(&mut encoded, &Value::String(key.clone()))?;
+                    Ok((encoded, item))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            write_head(out, 5, entries.len() as u64);
+            for (key, item) in entries {
+                out.extend_from_slice(&key);
+                write_value(out, item)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Encode a JSON value as deterministic CBOR.
+fn to_cbor(value: &Value) -> Result<Vec<u8>> {
+    let mut out = Vec::new();
+    write_value(&mut out, value)?;
+    Ok(out)
+}
+
+fn malformed(reason: &str) -> SdkError {
+    SdkError::SerializationError(format!("malformed CBOR: {}", reason))
+}
+
+struct Decoder<'a> {
+    input: &'a [u8],
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8]> {
+        if self.input.len() < n {
+            return Err(malformed("unexpected end of input"));
+        }
+        let (head, rest) = self.input.split_at(n);
+        self.input = rest;
+        Ok(head)
+    }
+
+    fn argument(&mut self, info: u8) -> Result<u64> {
+        Ok(match info {
+            0..=23 => u64::from(info),
+            24 => u64::from(self.take(1)?[0]),
+            25 => u64::from(u16::from_be_bytes(
+                self.take(2)?.try_into().expect("2 bytes"),
+            )),
+            26 => u64::from(u32::from_be_bytes(
+                self.take(4)?.try_into().expect("4 bytes"),
+            )),
+            27 => u64::from_be_bytes(self.take(8)?.try_into().expect("8 bytes")),
+            _ => return Err(malformed("indefinite or reserved length")),
+        })
+    }
+
+    fn len(&mut self, info: u8) -> Result<usize> {
+        usize::try_from(self.argument(info)?)
+            .ok()
+            .filter(|&len| len <= self.input.len())
+            .ok_or_else(|| malformed("length out of bounds"))
+    }
+
+    fn string(&mut self, info: u8) -> Result<String> {
+        let len = self.len(info)?;
+        let bytes = self.take(len)?;
+        core::str::from_utf8(bytes)
+            .map(ToString::to_string)
+            .map_err(|_| malformed("text string is not UTF-8"))
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value> {
+        if depth > MAX_DEPTH {
+            return Err(malformed("nesting too deep"));
+        }
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        Ok(match major {
+            0 => Value::from(self.argument(info)?),
+            1 => {
+                let n = self.argument(info)?;
+                let i = i64::try_from(n).map_err(|_| malformed("negative integer too large"))?;
+                Value::from(-1 - i)
+            }
+            3 => Value::String(self.string(info)?),
+            4 => {
+                let len = self.len(info)?;
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(self.value(depth + 1)?);
+                }
+                Value::Array(items)
+            }
+            5 => {
+                let len = self.len(info)?;
+                let mut map = Map::new();
+                for _ in 0..len {
+                    let key_head = self.take(1)?[0];
+                    if key_head >> 5 != 3 {
+                        return Err(malformed("map key is not a text string"));
+                    }
+                    let key = self.string(key_head & 0x1f)?;
+                    map.insert(key, self.value(depth + 1)?);
+                }
+                Value::Object(map)
+            }
+            7 => match info {
+                20 => Value::Bool(false),
+                21 => Value::Bool(true),
+                22 => Value::Null,
+                25 => {
+                    let bits = u16::from_be_bytes(self.take(2)?.try_into().expect("2 bytes"));
+                    float_value(from_f16_bits(bits))?
+                }
+                26 => {
+                    let bits = u32::from_be_bytes(self.take(4)?.try_into().expect("4 bytes"));
+                    float_value(f64::from(f32::from_bits(bits)))?
+                }
+                27 => float_value(f64::from_bits(self.argument(27)?))?,
+                _ => return Err(malformed("unsupported simple value")),
+            },
+            _ => return Err(malformed("unsupported major type")),
+        })
+    }
+}
+
+fn float_value(f: f64) -> Result<Value> {
+    Number::from_f64(f)
+        .map(Value::Number)
+        .ok_or_else(|| malformed("non-finite float"))
+}
+
+/// Decode a single CBOR data item, rejecting trailing bytes.
+fn from_cbor(input: &[u8]) -> Result<Value> {
+    let mut decoder = Decoder { input };
+    let value = decoder.value(0)?;
+    if !decoder.input.is_empty() {
+        return Err(malformed("trailing bytes"));
+    }
+    Ok(value)
+}
+
+fn drift(format: &str, expected: &[u8], actual: &[u8]) -> SdkError {
+    SdkError::SerializationError(format!(
+        "{} round trip changed the claim: expected {}, got {}",
+        format,
+        String::from_utf8_lossy(expected),
+        String::from_utf8_lossy(actual)
+    ))
+}
+
+/// Check that a claim means the same thing after a JCS JSON round trip and a
+/// deterministic CBOR round trip (requires "cbor")
+///
+/// Both decoded forms must canonicalize to the claim's own canonical bytes,
+/// and the CBOR encoding must be stable when re-encoded. Fails with a
+/// `SerializationError` describing the first difference.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, assert_cross_format_equivalent};
+/// let claim = Claim::new_with_timestamp(serde_json::json!({"temp": 21.5, "ok": true}), 1);
+/// assert_cross_format_equivalent(&claim).unwrap();
+/// ```
+pub fn assert_cross_format_equivalent<T: Serialize>(claim: &Claim<T>) -> Result<()> {
+    let value = claim.to_canonical_value()?;
+    let canonical = canonicalize(&value)?;
+
+    let from_json: Value = serde_json::from_slice(&canonical)?;
+    let json_canonical = canonicalize(&from_json)?;
+    if json_canonical != canonical {
+        return Err(drift("JSON", &canonical, &json_canonical));
+    }
+
+    let cbor = to_cbor(&value)?;
+    let decoded = from_cbor(&cbor)?;
+    let cbor_canonical = canonicalize(&decoded)?;
+    if cbor_canonical != canonical {
+        return Err(drift("CBOR", &canonical, &cbor_canonical));
+    }
+    if to_cbor(&decoded)? != cbor {
+        return Err(SdkError::SerializationError(
+            "CBOR encoding is not deterministic".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rfc8949_appendix_a_vectors() {
+        let vectors = [
+            (json!(0), "00"),
+            (json!(23), "17"),
+            (json!(24), "1818"),
+            (json!(1000), "1903e8"),
+            (json!(-1), "20"),
+            (json!(-1000), "3903e7"),
+            (json!(0.0), "f90000"),
+            (json!(1.5), "f93e00"),
+            (json!(65504.0), "f97bff"),
+            (json!(5.960464477539063e-8), "f90001"),
+            (json!(100000.0), "fa47c35000"),
+            (json!(1.1), "fb3ff199999999999a"),
+            (json!(false), "f4"),
+            (json!(null), "f6"),
+            (json!("\u{00fc}"), "62c3bc"),
+            (json!([1, [2, 3]]), "8201820203"),
+            (json!({"b": [2], "a": 1}), "a2616101616281 02"),
+        ];
+        for (value, expected) in vectors {
+            let expected = expected.replace(' ', "");
+            let cbor = to_cbor(&value).unwrap();
+            assert_eq!(hex::encode(&cbor), expected, "{}", value);
+            assert_eq!(
+                canonicalize(&from_cbor(&cbor).unwrap()).unwrap(),
+                canonicalize(&value).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_cross_format_check() {
+        let mut claim = Claim::new_with_timestamp(
+            json!({"nested": [{"z": -0.5, "a": 1e-7}], "text": "caf\u{e9}"}),
+            1_700_000_000,
+        );
+        claim.metadata = Some("m".into());
+        assert_cross_format_equivalent(&claim).unwrap();
+
+        assert!(from_cbor(&[0x82, 0x01]).is_err());
+        assert!(from_cbor(&[0x01, 0x01]).is_err());
+        assert!(from_cbor(&[0x5f]).is_err());
+    }
+}
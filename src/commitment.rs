@@ -0,0 +1,80 @@
+//! Commit-reveal over canonical claims.
+//!
+//! [`commit_claim`] publishes `SHA-256(salt || canonical claim)` ahead of
+//! time; revealing the claim and salt later lets anyone check it with
+//! [`verify_commitment`]. Useful for sealed bids and fair ordering.
+//!
+//! The salt is what keeps the claim hidden until the reveal: without it a
+//! low-entropy claim ("bid: 100") could be found by hashing every candidate.
+//! Use a fresh salt of at least [`COMMITMENT_SALT_BYTES`] bytes from a
+//! cryptographically secure RNG for every commitment, and never reuse one.
+
+use alloc::string::{String, ToString};
+use serde::Serialize;
+
+use crate::{compute_hash_multi, Claim, HashAlg, Result, SdkError};
+
+/// Recommended salt length for [`commit_claim`], in bytes.
+pub const COMMITMENT_SALT_BYTES: usize = 32;
+
+/// Commit to a claim without revealing it: Hex `SHA-256(salt || canonical bytes)`
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, COMMITMENT_SALT_BYTES, commit_claim, verify_commitment};
+/// use rand::RngCore;
+/// let mut salt = [0u8; COMMITMENT_SALT_BYTES];
+/// rand::rngs::OsRng.fill_bytes(&mut salt);
+///
+/// let bid = Claim::new_with_timestamp("bid: 100".to_string(), 1700000000);
+/// let commitment = commit_claim(&bid, &salt).unwrap();
+/// // ... later, reveal `bid` and `salt`
+/// assert!(verify_commitment(&bid, &salt, &commitment).unwrap());
+/// ```
+pub fn commit_claim<T: Serialize>(claim: &Claim<T>, salt: &[u8]) -> Result<String> {
+    Ok(compute_hash_multi(
+        &[salt, &claim.to_signable_bytes()?],
+        HashAlg::Sha256,
+    ))
+}
+
+/// Check a revealed claim and salt against a commitment from [`commit_claim`]
+///
+/// Fails with [`SdkError::ProofError`] if they do not match.
+pub fn verify_commitment<T: Serialize>(
+    claim: &Claim<T>,
+    salt: &[u8],
+    commitment: &str,
+) -> Result<bool> {
+    if !commit_claim(claim, salt)?.eq_ignore_ascii_case(commitment) {
+        return Err(SdkError::ProofError(
+            "claim does not match the commitment".to_string(),
+        ));
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute_hash;
+
+    #[test]
+    fn test_commitment_binds_claim_and_salt() {
+        let claim = Claim::new_with_timestamp("bid: 100".to_string(), 1);
+        let salt = [7u8; COMMITMENT_SALT_BYTES];
+        let commitment = commit_claim(&claim, &salt).unwrap();
+
+        let mut preimage = salt.to_vec();
+        preimage.extend_from_slice(br#"{"data":"bid: 100","timestamp":1}"#);
+        assert_eq!(commitment, compute_hash(&preimage));
+        assert!(verify_commitment(&claim, &salt, &commitment.to_uppercase()).unwrap());
+
+        let other = Claim::new_with_timestamp("bid: 101".to_string(), 1);
+        assert!(matches!(
+            verify_commitment(&other, &salt, &commitment),
+            Err(SdkError::ProofError(_))
+        ));
+        assert!(verify_commitment(&claim, &[8u8; COMMITMENT_SALT_BYTES], &commitment).is_err());
+    }
+}
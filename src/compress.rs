@@ -0,0 +1,418 @@
+//! Verifying signed claims stored compressed.
+//!
+//! [`verify_compressed`] decompresses gzip (RFC 1952), zlib (RFC 1950), raw
+//! DEFLATE (RFC 1951) or Zstandard (RFC 8878) input to the JSON of a
+//! [`SignedClaim`] and verifies it. DEFLATE is decoded by `miniz_oxide` and
+//! Zstandard by `ruzstd`; only the gzip framing is parsed here. Decompression
+//! stops with [`SdkError::SerializationError`] as soon as the output would
+//! exceed a size limit, so a small hostile input cannot expand into gigabytes
+//! in memory.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use miniz_oxide::inflate::stream::{inflate, InflateState};
+use miniz_oxide::{DataFormat, MZError, MZFlush, MZStatus};
+use ruzstd::decoding::errors::{FrameDecoderError, ReadFrameHeaderError};
+use ruzstd::decoding::{BlockDecodingStrategy, FrameDecoder};
+
+use crate::{parse, verify_claim_strict, Result, SdkError, SignedClaim};
+
+/// Output limit used by [`verify_compressed`], in bytes (1 MiB).
+pub const MAX_DECOMPRESSED_BYTES: usize = 1 << 20;
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// A compressed container format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Raw DEFLATE, with no header or checksum
+    Deflate,
+    /// gzip, as written by `gzip` and most HTTP stacks; concatenated members
+    /// are decompressed in order
+    Gzip,
+    /// zlib, as written by `zlib.compress`
+    Zlib,
+    /// Zstandard, as written by `zstd`; concatenated frames are decompressed
+    /// in order and skippable frames are ignored
+    Zstd,
+}
+
+fn malformed(reason: &str) -> SdkError {
+    SdkError::SerializationError(format!("malformed compressed data: {}", reason))
+}
+
+/// Decompressed output that refuses to grow past a limit.
+struct Output {
+    bytes: Vec<u8>,
+    max: usize,
+}
+
+impl Output {
+    fn reserve(&self, len: usize) -> Result<()> {
+        if self.bytes.len() + len > self.max {
+            return Err(SdkError::SerializationError(format!(
+                "decompressed size exceeds the {} byte limit",
+                self.max
+            )));
+        }
+        Ok(())
+    }
+
+    fn extend(&mut self, bytes: &[u8]) -> Result<()> {
+        self.reserve(bytes.len())?;
+        self.bytes.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Inflate one DEFLATE or zlib stream, returning the number of input bytes
+/// used.
+fn inflate_stream(data: &[u8], format: DataFormat, out: &mut Output) -> Result<usize> {
+    let mut state = InflateState::new_boxed(format);
+    let mut chunk = [0u8; CHUNK_SIZE];
+    let mut pos = 0;
+    loop {
+        let result = inflate(&mut state, &data[pos..], &mut chunk, MZFlush::None);
+        pos += result.bytes_consumed;
+        out.extend(&chunk[..result.bytes_written])?;
+        match result.status {
+            Ok(MZStatus::StreamEnd) => return Ok(pos),
+            Ok(_) => {}
+            Err(MZError::Buf) => return Err(malformed("truncated stream")),
+            Err(_) => return Err(malformed("invalid DEFLATE data or checksum")),
+        }
+    }
+}
+
+/// Skip a zero-terminated header field.
+fn skip_terminated(data: &[u8], pos: usize) -> Result<usize> {
+    data.get(pos..)
+        .and_then(|rest| rest.iter().position(|&byte| byte == 0))
+        .map(|end| pos + end + 1)
+        .ok_or_else(|| malformed("truncated gzip header"))
+}
+
+/// Decompress one gzip member, returning the number of input bytes used.
+fn gunzip_member(data: &[u8], out: &mut Output) -> Result<usize> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+
+    if data.len() < 10 || data[..3] != [0x1f, 0x8b, 8] {
+        return Err(malformed("not a gzip stream"));
+    }
+    let flags = data[3];
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        let len = data
+            .get(pos..pos + 2)
+            .ok_or_else(|| malformed("truncated gzip header"))?;
+        pos += 2 + usize::from(u16::from_le_bytes([len[0], len[1]]));
+    }
+    if flags & FNAME != 0 {
+        pos = skip_terminated(data, pos)?;
+    }
+    if flags & FCOMMENT != 0 {
+        pos = skip_terminated(data, pos)?;
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+
+    let start = out.bytes.len();
+    pos += inflate_stream(data.get(pos..).unwrap_or_default(), DataFormat::Raw, out)?;
+    let trailer = data
+        .get(pos..pos + 8)
+        .ok_or_else(|| malformed("truncated gzip trailer"))?;
+    let member = &out.bytes[start..];
+    if u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]])
+        != crc32fast::hash(member)
+        || u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]])
+            != member.len() as u32
+    {
+        return Err(malformed("gzip checksum mismatch"));
+    }
+    Ok(pos + 8)
+}
+
+/// Window a Zstandard frame header asks the decoder to keep, in bytes
+/// (RFC 8878, section 3.1.1.1.2).
+fn zstd_window(frame: &[u8]) -> Option<u64> {
+    let descriptor = *frame.get(4)?;
+    if descriptor & 0x20 == 0 {
+        let window = *frame.get(5)?;
+        let base = 1u64 << (10 + (window >> 3));
+        return Some(base + base / 8 * u64::from(window & 7));
+    }
+    // A single-segment frame's window is its content size
+    let start = 5 + [0, 1, 2, 4][usize::from(descriptor & 3)];
+    let len = [1, 2, 4, 8][usize::from(descriptor >> 6)];
+    let size = frame
+        .get(start..start + len)?
+        .iter()
+        .rev()
+        .fold(0u64, |size, &byte| size << 8 | u64::from(byte));
+    Some(if len == 2 { size + 256 } else { size })
+}
+
+/// Decompress a sequence of Zstandard frames.
+///
+/// The decoder holds back a full window of output until the frame ends, so
+/// frames whose window exceeds the output limit are rejected up front.
+fn unzstd(mut data: &[u8], out: &mut Output) -> Result<()> {
+    if data.is_empty() {
+        return Err(malformed("not a zstd stream"));
+    }
+    while !data.is_empty() {
+        let frame = data;
+        let mut decoder = FrameDecoder::new();
+        match decoder.init(&mut data) {
+            Ok(()) => {}
+            Err(FrameDecoderError::ReadFrameHeaderError(ReadFrameHeaderError::SkipFrame {
+                length,
+                ..
+            })) => {
+                data = data
+                    .get(length as usize..)
+                    .ok_or_else(|| malformed("truncated zstd skippable frame"))?;
+                continue;
+            }
+            Err(_) => return Err(malformed("not a zstd stream")),
+        }
+        match zstd_window(frame) {
+            Some(window) if window <= out.max as u64 => {}
+            _ => {
+                return Err(SdkError::SerializationError(format!(
+                    "zstd window exceeds the {} byte limit",
+                    out.max
+                )))
+            }
+        }
+        while !decoder.is_finished() {
+            decoder
+                .decode_blocks(&mut data, BlockDecodingStrategy::UptoBytes(CHUNK_SIZE))
+                .map_err(|_| malformed("invalid zstd data"))?;
+            if let Some(bytes) = decoder.collect() {
+                out.extend(&bytes)?;
+            }
+        }
+        if let Some(checksum) = decoder.get_checksum_from_data() {
+            if decoder.get_calculated_checksum() != Some(checksum) {
+                return Err(malformed("zstd checksum mismatch"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Decompress `data`, failing with [`SdkError::SerializationError`] if the
+/// output would exceed `max_output` bytes (requires the "compression" feature)
+///
+/// Checksums are verified and trailing bytes after the stream are rejected.
+pub fn decompress(data: &[u8], codec: Compression, max_output: usize) -> Result<Vec<u8>> {
+    let mut out = Output {
+        bytes: Vec::new(),
+        max: max_output,
+    };
+    match codec {
+        Compression::Deflate | Compression::Zlib => {
+            let format = match codec {
+                Compression::Deflate => DataFormat::Raw,
+                _ => DataFormat::Zlib,
+            };
+            let used = inflate_stream(data, format, &mut out)?;
+            if used != data.len() {
+                return Err(malformed("trailing data after the stream"));
+            }
+        }
+        Compression::Gzip => {
+            let mut pos = 0;
+            while pos < data.len() {
+                pos += gunzip_member(&data[pos..], &mut out)?;
+            }
+            if pos == 0 {
+                return Err(malformed("not a gzip stream"));
+            }
+        }
+        Compression::Zstd => unzstd(data, &mut out)?,
+    }
+    Ok(out.bytes)
+}
+
+/// Decompress the JSON of a signed claim and verify it strictly, with a
+/// [`MAX_DECOMPRESSED_BYTES`] output limit (requires the "compression" feature)
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, Compression, sign_claim, verify_compressed, generate_keypair};
+/// let claim = Claim::new_with_timestamp("Door 4 opened".to_string(), 1700000000);
+/// let json = serde_json::to_vec(&sign_claim(&claim, &generate_keypair()).unwrap()).unwrap();
+///
+/// // A single uncompressed ("stored") DEFLATE block
+/// let len = json.len() as u16;
+/// let mut deflate = vec![0x01];
+/// deflate.extend(len.to_le_bytes());
+/// deflate.extend((!len).to_le_bytes());
+/// deflate.extend(&json);
+/// assert!(verify_compressed(&deflate, Compression::Deflate).unwrap());
+/// ```
+pub fn verify_compressed(data: &[u8], codec: Compression) -> Result<bool> {
+    verify_compressed_with_limit(data, codec, MAX_DECOMPRESSED_BYTES)
+}
+
+/// Like [`verify_compressed`], with an explicit output limit in bytes
+pub fn verify_compressed_with_limit(
+    data: &[u8],
+    codec: Compression,
+    max_output: usize,
+) -> Result<bool> {
+    let json = decompress(data, codec, max_output)?;
+    let signed: SignedClaim<serde_json::Value> = parse::from_slice_unique(&json)?;
+    verify_claim_strict(&signed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `gzip -9n` of a signed claim: one dynamic Huffman block
+    const GZIP: &str = "1f8b08000000000002037d4e3b8e423110bb4a949a22c94c7e6c85b4f758cd2fe8697980e05120c4dd3705f5baf2d8b2c72f2f275a56bf7f79a58dfcde7f5f2e3787ee72b5b3a9e3a763d2a3b918a17eb97f4cbff3ab6df469e1c772d2e57c7487a96fcb6af78dd6abdfc71a3e78effcf5c1a7457e7eed3913c0855265119b04939644404d43a2328256281952ccb546b58440054802368a8d73574a7dfeb92fc7336d8f9bcd3a418e3614a2e9289c53af022223279454532c0a5d0658635452ee420dc2a8268adc6ae332ea68ad69c7c836cc2a869115b3b0640af302ec5832e55473c01108c21c5b7b1a09027260fffe0388d7452e5b010000";
+    /// Raw DEFLATE of the same claim with fixed Huffman codes
+    const FIXED: &str = "ab564ace49cccc55b2aa564a492c4954b25272c9cf2f523051c82f48cd4b4d5148aa54484a4c494f5530343436b756c023a9a4a3949b5a92083525a934332725332f5dc111285e92999b5a5c92985ba06465686e0005b53a4a05a5493999c9f1d9a995401dc649668946e649c9c9a9408689518a9951a271a2458a8151a2599a418ab9b199a9b191a1a9b9b9614aaa918971a2997162b2818945a2a14592a9654aa29125d09ee2ccf4bcc492d2a254a071c9264986a96929c686a929696649a64696e6c9c6c9c969a64626c946e646866629c696c969c6a91649262989294996c98916c60669e6a9c929264916e616496669e6691616162996268649a969a9a9e6260669a62926a6c949c9a68906409eb189a5899969a2a991b9a981499a41a2b101d0b1e696466946c606264906494ab500";
+
+    /// `zstd -19 --check` of the same claim: one single-segment frame
+    const ZSTD: &str = "28b52ffd645b00750700c6d2361b706b1b508f8e84ef6050921421f43f1ed068694972d3fdb9ff5f5e33002e002c00cc368b679606130c2084c3e28685c1db3c830922009608214ff065a4c188ffe1130844110502214005b759478782ccb2864647689b9ede5c9b9ade11630aa5542b999fb7361d6f9b9b9627fd6e666917272ad8f8c2a017033312983c0e430a260227dbb6635f576e9faf5dfa4289a5d6a45e77367349f17afe28fd3d626d54f0158c660f84658109bab4ee14b70fe4c37a32bde498b73db9e7e9512ca9554b9de659d5dc4a5dbda3ecff3d2375de5a7bec2d6eacb17a3f7b0105009f988094058c5fc609cef641a8ca750659cf";
+
+    /// A single stored DEFLATE block holding `bytes`
+    fn stored(bytes: &[u8]) -> Vec<u8> {
+        let len = bytes.len() as u16;
+        let mut block = vec![0x01];
+        block.extend(len.to_le_bytes());
+        block.extend((!len).to_le_bytes());
+        block.extend(bytes);
+        block
+    }
+
+    /// A fixed Huffman block of one zero followed by `copies` 258-byte
+    /// back-references to it
+    fn fixed_zeros(copies: usize) -> Vec<u8> {
+        let mut bits = vec![true, true, false]; // final block, fixed codes
+        let mut code = |value: u32, len: u32| {
+            bits.extend((0..len).rev().map(|i| value >> i & 1 == 1));
+        };
+        code(0b0011_0000, 8); // literal 0
+        for _ in 0..copies {
+            code(0b1100_0101, 8); // length 258
+            code(0, 5); // distance 1
+        }
+        code(0, 7); // end of block
+        let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+        for (i, bit) in bits.into_iter().enumerate() {
+            bytes[i / 8] |= u8::from(bit) << (i % 8);
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_each_codec_verifies() {
+        let gzip = hex::decode(GZIP).unwrap();
+        assert!(verify_compressed(&gzip, Compression::Gzip).unwrap());
+        let fixed = hex::decode(FIXED).unwrap();
+        assert!(verify_compressed(&fixed, Compression::Deflate).unwrap());
+        assert_eq!(
+            decompress(&fixed, Compression::Deflate, usize::MAX).unwrap(),
+            decompress(&gzip, Compression::Gzip, usize::MAX).unwrap()
+        );
+
+        let mut zlib = vec![0x78, 0xda];
+        zlib.extend_from_slice(&gzip[10..gzip.len() - 8]);
+        zlib.extend_from_slice(&hex::decode("1dfb65a5").unwrap());
+        assert!(verify_compressed(&zlib, Compression::Zlib).unwrap());
+
+        let mut twice = gzip.clone();
+        twice.extend_from_slice(&gzip);
+        assert_eq!(
+            decompress(&twice, Compression::Gzip, usize::MAX)
+                .unwrap()
+                .len(),
+            2 * 347
+        );
+    }
+
+    #[test]
+    fn test_zstd_frames_verify() {
+        let zstd = hex::decode(ZSTD).unwrap();
+        assert!(verify_compressed(&zstd, Compression::Zstd).unwrap());
+        let gzip = hex::decode(GZIP).unwrap();
+        assert_eq!(
+            decompress(&zstd, Compression::Zstd, usize::MAX).unwrap(),
+            decompress(&gzip, Compression::Gzip, usize::MAX).unwrap()
+        );
+
+        // A skippable frame, then the claim twice
+        let mut framed = hex::decode("502a4d1803000000ffffff").unwrap();
+        framed.extend_from_slice(&zstd);
+        framed.extend_from_slice(&zstd);
+        assert_eq!(
+            decompress(&framed, Compression::Zstd, usize::MAX)
+                .unwrap()
+                .len(),
+            2 * 347
+        );
+
+        // The frame's window is its 347-byte content size
+        assert!(matches!(
+            decompress(&zstd, Compression::Zstd, 346),
+            Err(SdkError::SerializationError(e)) if e.contains("window")
+        ));
+        let mut corrupt = zstd;
+        let checksum = corrupt.len() - 1;
+        corrupt[checksum] ^= 1;
+        assert!(decompress(&corrupt, Compression::Zstd, usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_claims_are_verified_strictly() {
+        let mut signed: SignedClaim<serde_json::Value> = serde_json::from_slice(
+            &decompress(&hex::decode(GZIP).unwrap(), Compression::Gzip, usize::MAX).unwrap(),
+        )
+        .unwrap();
+        // A small-order key and signature that plain verification accepts
+        signed.public_key = format!("01{}", "00".repeat(31));
+        signed.signature = format!("01{}", "00".repeat(63));
+        assert!(crate::verify_claim(&signed).is_ok());
+        let json = serde_json::to_vec(&signed).unwrap();
+        assert!(verify_compressed(&stored(&json), Compression::Deflate).is_err());
+    }
+
+    #[test]
+    fn test_output_limit_and_corruption() {
+        let gzip = hex::decode(GZIP).unwrap();
+        assert!(verify_compressed_with_limit(&gzip, Compression::Gzip, 347).unwrap());
+        assert!(matches!(
+            verify_compressed_with_limit(&gzip, Compression::Gzip, 346),
+            Err(SdkError::SerializationError(_))
+        ));
+
+        // About 8 KiB of fixed Huffman codes inflating to 1.3 MB of zeros
+        let bomb = fixed_zeros(5000);
+        assert!(bomb.len() < 8200);
+        assert_eq!(
+            decompress(&bomb, Compression::Deflate, usize::MAX)
+                .unwrap()
+                .len(),
+            1 + 5000 * 258
+        );
+        assert!(matches!(
+            decompress(&bomb, Compression::Deflate, MAX_DECOMPRESSED_BYTES),
+            Err(SdkError::SerializationError(e)) if e.contains("limit")
+        ));
+
+        let mut corrupt = gzip;
+        let crc = corrupt.len() - 8;
+        corrupt[crc] ^= 1;
+        assert!(verify_compressed(&corrupt, Compression::Gzip).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! Cosmos / Tendermint Amino public keys.
+//!
+//! Tendermint names a validator key by its Amino encoding: a 4-byte type
+//! prefix, a length byte and the key itself. It appears either as Amino JSON,
+//! `{"type":"tendermint/PubKeyEd25519","value":"<base64>"}` (as in
+//! `priv_validator_key.json`), or as Bech32 of the binary encoding
+//! (`cosmosvalconspub1zcjduepq...`). Only Ed25519 keys can sign claims.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::base64_decode;
+use crate::{decode_public_key, verify_claim_strict, Result, SdkError, SignedClaim};
+
+const ED25519_TYPE: &str = "tendermint/PubKeyEd25519";
+/// Amino type prefix and length byte of an Ed25519 key.
+const ED25519_PREFIX: [u8; 5] = [0x16, 0x24, 0xde, 0x64, 0x20];
+/// Other Amino key types, recognised only to name them in errors.
+const OTHER_TYPES: [(&str, [u8; 4]); 2] = [
+    ("tendermint/PubKeySecp256k1", [0xeb, 0x5a, 0xe9, 0x87]),
+    ("tendermint/PubKeySr25519", [0x0d, 0xfb, 0x10, 0x05]),
+];
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AminoJson {
+    #[serde(rename = "type")]
+    kind: String,
+    value: String,
+}
+
+fn unsupported(kind: &str) -> SdkError {
+    SdkError::KeyError(format!(
+        "unsupported Amino key type {}, expected {}",
+        kind, ED25519_TYPE
+    ))
+}
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    values.fold(1, |chk, value| {
+        let top = chk >> 25;
+        let chk = (chk & 0x01ff_ffff) << 5 ^ u32::from(value);
+        (0..5)
+            .filter(|i| top >> i & 1 == 1)
+            .fold(chk, |chk, i| chk ^ GENERATOR[i])
+    })
+}
+
+/// Decode Bech32 (BIP 173), returning the human-readable part and data.
+fn bech32_decode(input: &str) -> Option<(String, Vec<u8>)> {
+    if input.bytes().any(|b| b.is_ascii_lowercase())
+        && input.bytes().any(|b| b.is_ascii_uppercase())
+    {
+        return None;
+    }
+    let input = input.to_ascii_lowercase();
+    let (hrp, data) = input.rsplit_once('1')?;
+    if hrp.is_empty() || data.len() < 6 || !hrp.bytes().all(|b| (33..=126).contains(&b)) {
+        return None;
+    }
+    let values = data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|&a| a == c).map(|v| v as u8))
+        .collect::<Option<Vec<u8>>>()?;
+    let expanded = hrp
+        .bytes()
+        .map(|b| b >> 5)
+        .chain([0])
+        .chain(hrp.bytes().map(|b| b & 0x1f));
+    if bech32_polymod(expanded.chain(values.iter().copied())) != 1 {
+        return None;
+    }
+
+    // 5-bit groups to bytes; leftover padding must be short and zero
+    let mut bytes = Vec::with_capacity(values.len() * 5 / 8);
+    let (mut acc, mut bits) = (0u32, 0);
+    for &value in &values[..values.len() - 6] {
+        acc = acc << 5 | u32::from(value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    if bits >= 5 || acc != 0 {
+        return None;
+    }
+    Some((String::from(hrp), bytes))
+}
+
+fn ed25519_key(bytes: &[u8]) -> Result<VerifyingKey> {
+    let bytes: &[u8; 32] = bytes.try_into().map_err(|_| {
+        SdkError::KeyError(format!(
+            "Amino Ed25519 key must be 32 bytes, got {}",
+            bytes.len()
+        ))
+    })?;
+    VerifyingKey::from_bytes(bytes)
+        .map_err(|e| SdkError::KeyError(format!("invalid Amino public key: {}", e)))
+}
+
+/// Parse an Amino-encoded Ed25519 public key, given as Amino JSON or as
+/// Bech32 of the binary encoding (requires the "cosmos" feature)
+///
+/// Fails with [`SdkError::KeyError`] if the key is malformed or of another
+/// type, such as secp256k1.
+///
+/// # Example
+/// ```
+/// use provn_sdk::from_amino_pubkey;
+/// let json = r#"{"type":"tendermint/PubKeyEd25519","value":"O2onvM62pC1io6jQKm8Nc2UyFXcd4kOmOsBIoYtZ2ik="}"#;
+/// let bech32 = "cosmosvalconspub1zcjduepq8d4z00xwk6jz6c4r4rgz5mcdwdjny9thrh3y8f36cpy2rz6emg5s2ypa5f";
+/// assert_eq!(from_amino_pubkey(json).unwrap(), from_amino_pubkey(bech32).unwrap());
+/// let secp256k1 = "cosmosvalconspub1addwnpepqgak5faue6m2gttz5w5dq2n0p4ek2vs4wuw7ysax8tqy3gvtt8dzjs72vph";
+/// assert!(from_amino_pubkey(secp256k1).is_err());
+/// ```
+pub fn from_amino_pubkey(amino_pubkey: &str) -> Result<VerifyingKey> {
+    let amino_pubkey = amino_pubkey.trim();
+    if amino_pubkey.starts_with('{') {
+        let json: AminoJson = serde_json::from_str(amino_pubkey)
+            .map_err(|e| SdkError::KeyError(format!("invalid Amino JSON key: {}", e)))?;
+        if json.kind != ED25519_TYPE {
+            return Err(unsupported(&json.kind));
+        }
+        let bytes = base64_decode(&json.value)
+            .ok_or_else(|| SdkError::KeyError("Amino JSON key value is not base64".into()))?;
+        return ed25519_key(&bytes);
+    }
+
+    let (_, bytes) = bech32_decode(amino_pubkey).ok_or_else(|| {
+        SdkError::KeyError("Amino key is neither Amino JSON nor valid Bech32".into())
+    })?;
+    match bytes.strip_prefix(&ED25519_PREFIX[..]) {
+        Some(key) => ed25519_key(key),
+        None => match OTHER_TYPES
+            .iter()
+            .find(|(_, prefix)| bytes.starts_with(prefix))
+        {
+            Some((kind, _)) => Err(unsupported(kind)),
+            None => Err(SdkError::KeyError(format!(
+                "unknown Amino key prefix {}",
+                hex::encode(&bytes[..bytes.len().min(4)])
+            ))),
+        },
+    }
+}
+
+/// Verify a signed claim, requiring it to be signed by the Amino-encoded key
+/// `amino_pubkey` (requires the "cosmos" feature)
+///
+/// The key is parsed with [`from_amino_pubkey`] and the signature checked
+/// with [`verify_claim_strict`] over the usual canonical bytes.
+///
+/// # Example
+/// ```
+/// use ed25519_dalek::SigningKey;
+/// use provn_sdk::{Claim, sign_claim, verify_claim_cosmos};
+/// let key = SigningKey::from_bytes(&[0u8; 32]);
+/// let signed = sign_claim(&Claim::new("Block 1200 signed".to_string()), &key).unwrap();
+/// let validator = "cosmosvalconspub1zcjduepq8d4z00xwk6jz6c4r4rgz5mcdwdjny9thrh3y8f36cpy2rz6emg5s2ypa5f";
+/// assert!(verify_claim_cosmos(&signed, validator).unwrap());
+/// ```
+pub fn verify_claim_cosmos<T: Serialize>(
+    signed: &SignedClaim<T>,
+    amino_pubkey: &str,
+) -> Result<bool> {
+    if decode_public_key(&signed.public_key)? != from_amino_pubkey(amino_pubkey)? {
+        return Err(SdkError::KeyError(
+            "claim is not signed by the given Amino key".into(),
+        ));
+    }
+    verify_claim_strict(signed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+    use alloc::string::ToString;
+    use ed25519_dalek::SigningKey;
+
+    const BECH32: &str =
+        "cosmosvalconspub1zcjduepq8d4z00xwk6jz6c4r4rgz5mcdwdjny9thrh3y8f36cpy2rz6emg5s2ypa5f";
+
+    #[test]
+    fn test_amino_forms_decode_to_the_signer() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let json = format!(
+            r#"{{"type":"{}","value":"O2onvM62pC1io6jQKm8Nc2UyFXcd4kOmOsBIoYtZ2ik="}}"#,
+            ED25519_TYPE
+        );
+        for amino in [json.as_str(), BECH32, &BECH32.to_uppercase()] {
+            assert_eq!(from_amino_pubkey(amino).unwrap(), key.verifying_key());
+        }
+
+        let mut corrupted = String::from(BECH32);
+        corrupted.replace_range(30..31, "q");
+        assert_ne!(corrupted, BECH32);
+        let secp256k1 = json.replace("Ed25519", "Secp256k1");
+        for bad in [
+            corrupted.as_str(),
+            &secp256k1,
+            "O2onvM62pC1io6jQKm8Nc2UyFXcd4kOmOsBIoYtZ2ik=",
+        ] {
+            assert!(
+                matches!(from_amino_pubkey(bad), Err(SdkError::KeyError(_))),
+                "{}",
+                bad
+            );
+        }
+        assert!(from_amino_pubkey(&secp256k1)
+            .unwrap_err()
+            .to_string()
+            .contains("tendermint/PubKeySecp256k1"));
+    }
+
+    #[test]
+    fn test_verify_claim_cosmos_checks_key_and_signature() {
+        let signed = sign_claim(
+            &Claim::new_with_timestamp("x".to_string(), 1),
+            &SigningKey::from_bytes(&[0u8; 32]),
+        )
+        .unwrap();
+        assert!(verify_claim_cosmos(&signed, BECH32).unwrap());
+
+        let other = sign_claim(
+            &Claim::new_with_timestamp("x".to_string(), 1),
+            &SigningKey::from_bytes(&[1u8; 32]),
+        )
+        .unwrap();
+        assert!(matches!(
+            verify_claim_cosmos(&other, BECH32),
+            Err(SdkError::KeyError(_))
+        ));
+
+        let mut tampered = signed;
+        tampered.claim.data = "y".to_string();
+        assert!(matches!(
+            verify_claim_cosmos(&tampered, BECH32),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+}
@@ -0,0 +1,184 @@
+//! `did:key` identifiers for Ed25519 signers.
+//!
+//! A `did:key` is `did:key:z` followed by the Base58btc encoding of the
+//! multicodec-prefixed public key (`0xed 0x01 || 32 key bytes`), as defined by
+//! the [did:key method](https://w3c-ccg.github.io/did-method-key/).
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use ed25519_dalek::VerifyingKey;
+use serde::Serialize;
+
+use crate::encoding::{base58_decode, base58_encode};
+use crate::{decode_public_key, verify_claim, Result, SdkError, SignedClaim};
+
+/// Multicodec prefix for an Ed25519 public key (varint of 0xed).
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+/// Encode an Ed25519 public key as a `did:key`
+///
+/// # Example
+/// ```
+/// use ed25519_dalek::SigningKey;
+/// use provn_sdk::to_did_key;
+/// let did = to_did_key(&SigningKey::from_bytes(&[0u8; 32]).verifying_key());
+/// assert!(did.starts_with("did:key:z6Mk"));
+/// ```
+pub fn to_did_key(pk: &VerifyingKey) -> String {
+    let mut bytes = [0u8; 34];
+    bytes[..2].copy_from_slice(&ED25519_MULTICODEC);
+    bytes[2..].copy_from_slice(pk.as_bytes());
+    format!("did:key:z{}", base58_encode(&bytes))
+}
+
+/// Parse an Ed25519 `did:key` back into its public key
+///
+/// Fails with [`SdkError::KeyError`] if the identifier is malformed or names
+/// a key type other than Ed25519.
+///
+/// # Example
+/// ```
+/// use ed25519_dalek::SigningKey;
+/// use provn_sdk::{from_did_key, to_did_key};
+/// let pk = SigningKey::from_bytes(&[0u8; 32]).verifying_key();
+/// assert_eq!(from_did_key(&to_did_key(&pk)).unwrap(), pk);
+/// assert!(from_did_key("did:web:example.com").is_err());
+/// ```
+pub fn from_did_key(did: &str) -> Result<VerifyingKey> {
+    let encoded = did
+        .strip_prefix("did:key:z")
+        .ok_or_else(|| SdkError::KeyError("not a Base58btc did:key".to_string()))?;
+    let bytes = base58_decode(encoded, ED25519_MULTICODEC.len() + 32)
+        .ok_or_else(|| SdkError::KeyError("did:key is not valid Base58".to_string()))?;
+    let key = bytes
+        .strip_prefix(&ED25519_MULTICODEC)
+        .ok_or_else(|| SdkError::KeyError("did:key is not an Ed25519 key".to_string()))?;
+    let key: &[u8; 32] = key.try_into().map_err(|_| {
+        SdkError::KeyError(format!(
+            "did:key Ed25519 key must be 32 bytes, got {}",
+            key.len()
+        ))
+    })?;
+    VerifyingKey::from_bytes(key)
+        .map_err(|e| SdkError::KeyError(format!("invalid did:key public key: {}", e)))
+}
+
+/// Verify a signed claim, requiring it to be signed by the key of `signer_did`
+///
+/// Fails with [`SdkError::KeyError`] if the `did:key` is malformed, is not an
+/// Ed25519 key, or is not the claim's signer.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, to_did_key, verify_claim_by_did, generate_keypair};
+/// let key = generate_keypair();
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &key).unwrap();
+/// assert!(verify_claim_by_did(&signed, &to_did_key(&key.verifying_key())).unwrap());
+/// let stranger = to_did_key(&generate_keypair().verifying_key());
+/// assert!(verify_claim_by_did(&signed, &stranger).is_err());
+/// ```
+pub fn verify_claim_by_did<T: Serialize>(
+    signed_claim: &SignedClaim<T>,
+    signer_did: &str,
+) -> Result<bool> {
+    let expected = from_did_key(signer_did)?;
+    if decode_public_key(&signed_claim.public_key)? != expected {
+        return Err(SdkError::KeyError(format!(
+            "claim is not signed by {}",
+            signer_did
+        )));
+    }
+    verify_claim(signed_claim)
+}
+
+/// Verify a signed claim and return the signer's `did:key`
+///
+/// Fails with the same errors as [`verify_claim`] if the claim is invalid,
+/// so a returned identity is always a verified one.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, to_did_key, verify_and_identify, generate_keypair};
+/// let key = generate_keypair();
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &key).unwrap();
+/// assert_eq!(verify_and_identify(&signed).unwrap(), to_did_key(&key.verifying_key()));
+/// ```
+pub fn verify_and_identify<T: Serialize>(signed_claim: &SignedClaim<T>) -> Result<String> {
+    verify_claim(signed_claim)?;
+    Ok(to_did_key(&decode_public_key(&signed_claim.public_key)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+    use alloc::string::ToString;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_did_key_format() {
+        // RFC 8032 test vector 1 public key, as listed in the did:key test suite
+        let pk = VerifyingKey::from_bytes(
+            &hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            to_did_key(&pk),
+            "did:key:z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw"
+        );
+    }
+
+    #[test]
+    fn test_did_key_parsing_errors() {
+        let did = "did:key:z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw";
+        assert_eq!(
+            hex::encode(from_did_key(did).unwrap().as_bytes()),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        // secp256k1 (0xe7 0x01) from the did:key test suite
+        let secp = "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme";
+        for bad in [
+            secp,
+            "did:key:z6Mk0OIl",
+            &did[..did.len() - 1],
+            "did:web:example.com",
+        ] {
+            assert!(
+                matches!(from_did_key(bad), Err(SdkError::KeyError(_))),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_by_did_checks_the_signer() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let did = to_did_key(&key.verifying_key());
+        let mut signed = sign_claim(&Claim::new_with_timestamp("a".to_string(), 1), &key).unwrap();
+        assert!(verify_claim_by_did(&signed, &did).unwrap());
+
+        let other = to_did_key(&SigningKey::from_bytes(&[1u8; 32]).verifying_key());
+        assert!(matches!(
+            verify_claim_by_did(&signed, &other),
+            Err(SdkError::KeyError(_))
+        ));
+        signed.claim.data = "b".to_string();
+        assert!(matches!(
+            verify_claim_by_did(&signed, &did),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_and_identify_rejects_tampering() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut signed = sign_claim(&Claim::new_with_timestamp("a".to_string(), 1), &key).unwrap();
+        assert!(verify_and_identify(&signed).is_ok());
+        signed.claim.data = "b".to_string();
+        assert!(verify_and_identify(&signed).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//! Selective disclosure of claim fields.
+//!
+//! Instead of signing the claim directly, the signer commits to a salted hash
+//! of every disclosable field and signs that commitment:
+//!
+//! - The disclosable fields are the top-level keys of the claim's canonical
+//!   JSON object (`data`, `metadata`, ...), except `timestamp`, which stays
+//!   public as the timestamp of the commitment claim.
+//! - Each field gets a fresh random 16-byte salt.
+//! - `digest = SHA-256(salt || field name || 0x00 || canonical JSON of the value)`,
+//!   Hex encoded.
+//! - The commitment is a `Claim<BTreeMap<field, digest>>` with the original
+//!   timestamp, signed like any other claim.
+//!
+//! Revealing a field means handing over its value and salt; the verifier
+//! recomputes the digest and checks it against the signed commitment. Hidden
+//! fields stay protected by their salt even when their values have low entropy.
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use ed25519_dalek::SigningKey;
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    canonicalize, compute_hash_multi, sign_claim, verify_claim_strict, Claim, HashAlg, Result,
+    SdkError, SignedClaim,
+};
+
+/// Length of the random salt applied to each field, in bytes.
+pub const DISCLOSURE_SALT_BYTES: usize = 16;
+
+/// The signed commitment to a claim's field digests.
+pub type FieldCommitment = SignedClaim<BTreeMap<String, String>>;
+
+/// A claim signed for selective disclosure, as kept by its holder.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SelectiveClaim {
+    /// The full original claim
+    pub claim: Claim,
+    /// The signed commitment to the field digests
+    pub commitment: FieldCommitment,
+    /// The salt of every committed field (Hex encoded)
+    pub salts: BTreeMap<String, String>,
+}
+
+/// A revealed field value together with its salt.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DisclosedField {
+    /// The salt committed for this field (Hex encoded)
+    pub salt: String,
+    /// The field value
+    pub value: serde_json::Value,
+}
+
+/// The subset of a [`SelectiveClaim`] shown to a verifier.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DisclosedClaim {
+    /// The signed commitment to all field digests, revealed or not
+    pub commitment: FieldCommitment,
+    /// The revealed fields, by name
+    pub fields: BTreeMap<String, DisclosedField>,
+}
+
+fn field_digest(name: &str, salt: &str, value: &serde_json::Value) -> Result<String> {
+    let salt = crate::decode_hex(salt)
+        .map_err(|e| SdkError::SerializationError(format!("Invalid Hex Salt: {}", e)))?;
+    if salt.len() != DISCLOSURE_SALT_BYTES {
+        return Err(SdkError::SerializationError(format!(
+            "disclosure salt must be {} bytes, got {}",
+            DISCLOSURE_SALT_BYTES,
+            salt.len()
+        )));
+    }
+    let value = canonicalize(value)?;
+    Ok(compute_hash_multi(
+        &[&salt, name.as_bytes(), &[0u8], &value],
+        HashAlg::Sha256,
+    ))
+}
+
+fn disclosable_fields(claim: &Claim) -> Result<BTreeMap<String, serde_json::Value>> {
+    match serde_json::to_value(claim)? {
+        serde_json::Value::Object(map) => Ok(map
+            .into_iter()
+            .filter(|(name, _)| name != "timestamp")
+            .collect()),
+        _ => Err(SdkError::SerializationError(
+            "claim is not a JSON object".into(),
+        )),
+    }
+}
+
+/// Sign a claim so that its fields can later be disclosed selectively
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, disclose, sign_selective, verify_disclosure, generate_keypair};
+/// let mut claim = Claim::new("Patient is over 18".to_string());
+/// claim.metadata = Some("DOB 1990-01-01".to_string());
+/// let selective = sign_selective(&claim, &generate_keypair(), &mut rand::rngs::OsRng).unwrap();
+///
+/// // Reveal the statement but not the metadata
+/// let disclosed = disclose(&selective, &["data"]).unwrap();
+/// assert!(!disclosed.fields.contains_key("metadata"));
+/// assert!(verify_disclosure(&disclosed).unwrap());
+/// ```
+pub fn sign_selective<R: RngCore + CryptoRng>(
+    claim: &Claim,
+    key: &SigningKey,
+    rng: &mut R,
+) -> Result<SelectiveClaim> {
+    let mut salts = BTreeMap::new();
+    let mut digests = BTreeMap::new();
+    for (name, value) in disclosable_fields(claim)? {
+        let mut salt = [0u8; DISCLOSURE_SALT_BYTES];
+        rng.fill_bytes(&mut salt);
+        let salt = hex::encode(salt);
+        digests.insert(name.clone(), field_digest(&name, &salt, &value)?);
+        salts.insert(name, salt);
+    }
+
+    let commitment = sign_claim(&Claim::new_with_timestamp(digests, claim.timestamp), key)?;
+    Ok(SelectiveClaim {
+        claim: claim.clone(),
+        commitment,
+        salts,
+    })
+}
+
+/// Reveal the named fields of a selectively signed claim
+///
+/// Fails if a requested field is not part of the commitment (for example an
+/// absent `metadata`).
+pub fn disclose(selective: &SelectiveClaim, fields: &[&str]) -> Result<DisclosedClaim> {
+    let mut values = disclosable_fields(&selective.claim)?;
+    let mut revealed = BTreeMap::new();
+    for &name in fields {
+        let (value, salt) = values
+            .remove(name)
+            .zip(selective.salts.get(name))
+            .ok_or_else(|| {
+                SdkError::SerializationError(format!("field '{}' is not disclosable", name))
+            })?;
+        revealed.insert(
+            String::from(name),
+            DisclosedField {
+                salt: salt.clone(),
+                value,
+            },
+        );
+    }
+    Ok(DisclosedClaim {
+        commitment: selective.commitment.clone(),
+        fields: revealed,
+    })
+}
+
+/// Verify that every revealed field matches the signed commitment
+///
+/// The commitment's signature is verified strictly (see
+/// [`verify_claim_strict`]).
+pub fn verify_disclosure(disclosed: &DisclosedClaim) -> Result<bool> {
+    verify_claim_strict(&disclosed.commitment)?;
+    let committed = &disclosed.commitment.claim.data;
+    for (name, field) in &disclosed.fields {
+        if committed.get(name) != Some(&field_digest(name, &field.salt, &field.value)?) {
+            return Err(SdkError::ProofError(format!(
+                "disclosed field '{}' does not match the commitment",
+                name
+            )));
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec::Vec;
+    use rand::rngs::OsRng;
+
+    fn selective() -> SelectiveClaim {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claim = Claim::new_with_timestamp("public statement".to_string(), 42);
+        claim.metadata = Some("secret context".to_string());
+        sign_selective(&claim, &key, &mut OsRng).unwrap()
+    }
+
+    #[test]
+    fn test_hidden_fields_stay_hidden() {
+        let selective = selective();
+        let commitment = serde_json::to_string(&selective.commitment).unwrap();
+        assert!(!commitment.contains("secret context"));
+        assert_eq!(
+            selective.commitment.claim.data.keys().collect::<Vec<_>>(),
+            ["data", "metadata"]
+        );
+
+        let disclosed = disclose(&selective, &["metadata"]).unwrap();
+        assert!(verify_disclosure(&disclosed).unwrap());
+        assert!(disclose(&selective, &["timestamp"]).is_err());
+    }
+
+    #[test]
+    fn test_forged_disclosure_fails() {
+        let selective = selective();
+        let mut disclosed = disclose(&selective, &["data", "metadata"]).unwrap();
+        assert!(verify_disclosure(&disclosed).unwrap());
+
+        disclosed.fields.get_mut("data").unwrap().value = "forged statement".into();
+        assert!(matches!(
+            verify_disclosure(&disclosed),
+            Err(SdkError::ProofError(_))
+        ));
+    }
+
+    #[test]
+    fn test_digest_covers_canonical_json() {
+        let salt = [7u8; DISCLOSURE_SALT_BYTES];
+        // JCS writes 1e21 as `1e+21`, where serde_json writes `1e21`
+        let value = serde_json::json!({"b": 1e21, "a": [4.50]});
+        assert_eq!(
+            field_digest("data", &hex::encode(salt), &value).unwrap(),
+            compute_hash_multi(
+                &[&salt, b"data", &[0u8], br#"{"a":[4.5],"b":1e+21}"#],
+                HashAlg::Sha256
+            )
+        );
+    }
+
+    #[test]
+    fn test_commitment_is_verified_strictly() {
+        let selective = selective();
+        let mut disclosed = disclose(&selective, &["data"]).unwrap();
+        disclosed.commitment.public_key = format!("01{}", "00".repeat(31));
+        disclosed.commitment.signature = format!("01{}", "00".repeat(63));
+        assert!(crate::verify_claim(&disclosed.commitment).unwrap());
+        assert!(matches!(
+            verify_disclosure(&disclosed),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_salt_must_have_full_length() {
+        let selective = selective();
+        let mut disclosed = disclose(&selective, &["data"]).unwrap();
+        let field = disclosed.fields.get_mut("data").unwrap();
+        assert_eq!(field.salt.len(), 2 * DISCLOSURE_SALT_BYTES);
+        field.salt.truncate(2 * DISCLOSURE_SALT_BYTES - 2);
+        assert!(matches!(
+            verify_disclosure(&disclosed),
+            Err(SdkError::SerializationError(e)) if e.contains("16 bytes, got 15")
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+//! Domain-separated signing.
+//!
+//! A claim signed with [`sign_claim_with_domain`] is bound to a domain label
+//! such as `"provn-v1"`, so a signature made for one protocol or deployment
+//! cannot be replayed in another. The signed message is
+//! `domain || 0x00 || canonical claim`. Canonical JSON never contains a NUL
+//! byte, so a domain-separated message can never collide with a plain one.
+//!
+//! The domain is recorded in [`SignedClaim::domain`]; verifiers that require
+//! one should use [`verify_claim_with_domain`], which reports a mismatch
+//! explicitly instead of as a failed signature.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use ed25519_dalek::{Signer, SigningKey};
+use serde::Serialize;
+
+use crate::{public_key_hex, verify_claim, Claim, Result, SdkError, SignedClaim};
+
+/// Prefix `message` with `domain` and the NUL separator, if a domain is given.
+pub(crate) fn with_domain(domain: Option<&str>, message: Vec<u8>) -> Result<Vec<u8>> {
+    let Some(domain) = domain else {
+        return Ok(message);
+    };
+    if domain.is_empty() || domain.contains('\0') {
+        return Err(SdkError::SerializationError(
+            "a signing domain must be non-empty and contain no NUL".to_string(),
+        ));
+    }
+    let mut out = Vec::with_capacity(domain.len() + 1 + message.len());
+    out.extend_from_slice(domain.as_bytes());
+    out.push(0);
+    out.extend_from_slice(&message);
+    Ok(out)
+}
+
+/// Sign a claim bound to the signing domain `domain`
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim_with_domain, verify_claim_with_domain, generate_keypair};
+/// let signed = sign_claim_with_domain(&Claim::new("Test Claim".to_string()), &generate_keypair(), "provn-v1").unwrap();
+/// assert!(verify_claim_with_domain(&signed, Some("provn-v1")).unwrap());
+/// assert!(verify_claim_with_domain(&signed, Some("provn-v2")).is_err());
+/// ```
+pub fn sign_claim_with_domain<T: Serialize + Clone>(
+    claim: &Claim<T>,
+    key: &SigningKey,
+    domain: &str,
+) -> Result<SignedClaim<T>> {
+    let message = with_domain(Some(domain), claim.to_signable_bytes()?)?;
+    let signature = key.sign(&message);
+
+    Ok(SignedClaim {
+        claim: claim.clone(),
+        public_key: public_key_hex(key),
+        signature: hex::encode(signature.to_bytes()),
+        signed_fields: None,
+        metadata_salt: None,
+        domain: Some(domain.to_string()),
+    })
+}
+
+/// Verify a signed claim, requiring it to be bound to `expected` (or to no
+/// domain when `None`)
+///
+/// A claim whose domain differs from the expected one fails with a
+/// [`SdkError::SignatureError`] naming both, e.g. `domain mismatch: signed
+/// without domain but verifier expects 'provn-v1'`, before its signature is
+/// checked.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_claim_with_domain, generate_keypair};
+/// let legacy = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// assert!(verify_claim_with_domain(&legacy, None).unwrap());
+/// let err = verify_claim_with_domain(&legacy, Some("provn-v1")).unwrap_err();
+/// assert!(err.to_string().contains("signed without domain but verifier expects 'provn-v1'"));
+/// ```
+pub fn verify_claim_with_domain<T: Serialize>(
+    signed_claim: &SignedClaim<T>,
+    expected: Option<&str>,
+) -> Result<bool> {
+    let signed = signed_claim.domain.as_deref();
+    if signed != expected {
+        let signed_with = match signed {
+            Some(domain) => format!("with domain '{}'", domain),
+            None => String::from("without domain"),
+        };
+        let expects = match expected {
+            Some(domain) => format!("'{}'", domain),
+            None => String::from("none"),
+        };
+        return Err(SdkError::SignatureError(format!(
+            "domain mismatch: signed {} but verifier expects {}",
+            signed_with, expects
+        )));
+    }
+    verify_claim(signed_claim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sign_claim;
+
+    fn mismatch(signed: &SignedClaim, expected: Option<&str>) -> String {
+        match verify_claim_with_domain(signed, expected) {
+            Err(SdkError::SignatureError(message)) => message,
+            other => panic!("expected a domain mismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_all_domain_combinations() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let plain = sign_claim(&claim, &key).unwrap();
+        let bound = sign_claim_with_domain(&claim, &key, "provn-v1").unwrap();
+
+        // no domain / no domain
+        assert!(verify_claim_with_domain(&plain, None).unwrap());
+        // domain / same domain
+        assert!(verify_claim_with_domain(&bound, Some("provn-v1")).unwrap());
+        // no domain / domain expected
+        assert_eq!(
+            mismatch(&plain, Some("provn-v1")),
+            "domain mismatch: signed without domain but verifier expects 'provn-v1'"
+        );
+        // domain / no domain expected
+        assert_eq!(
+            mismatch(&bound, None),
+            "domain mismatch: signed with domain 'provn-v1' but verifier expects none"
+        );
+        assert_eq!(
+            mismatch(&bound, Some("provn-v2")),
+            "domain mismatch: signed with domain 'provn-v1' but verifier expects 'provn-v2'"
+        );
+    }
+
+    #[test]
+    fn test_domain_is_signed() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let bound = sign_claim_with_domain(&claim, &key, "provn-v1").unwrap();
+        let mut message = b"provn-v1\0".to_vec();
+        message.extend_from_slice(&claim.to_signable_bytes().unwrap());
+        assert_eq!(bound.signed_message().unwrap(), message);
+        assert_ne!(bound.signature, sign_claim(&claim, &key).unwrap().signature);
+
+        // Relabelling or stripping the domain breaks the signature
+        let mut relabelled = bound.clone();
+        relabelled.domain = Some("provn-v2".to_string());
+        assert!(verify_claim_with_domain(&relabelled, Some("provn-v2")).is_err());
+        let mut stripped = bound;
+        stripped.domain = None;
+        assert!(verify_claim_with_domain(&stripped, None).is_err());
+
+        assert!(sign_claim_with_domain(&claim, &key, "").is_err());
+        assert!(sign_claim_with_domain(&claim, &key, "a\0b").is_err());
+    }
+}
@@ -0,0 +1,445 @@
+//! Text encodings used by the SDK's interop formats.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::Serialize;
+
+use crate::{decode_hex_array, Result, SdkError, SignedClaim, PUBLIC_KEY_BYTES, SIGNATURE_BYTES};
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Encode bytes as Base58 (Bitcoin alphabet).
+pub(crate) fn base58_encode(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    // Base-58 digits, least significant first
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut out = String::with_capacity(zeros + digits.len());
+    out.extend(core::iter::repeat_n('1', zeros));
+    out.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|&d| BASE58_ALPHABET[d as usize] as char),
+    );
+    out
+}
+
+/// Longest Base58 encoding of `bytes` bytes (each digit carries
+/// log2(58) > 5.857 bits).
+const fn base58_max_len(bytes: usize) -> usize {
+    (bytes * 1366).div_ceil(1000)
+}
+
+/// Decode Base58 (Bitcoin alphabet), rejecting foreign characters and input
+/// too long to encode at most `max_bytes` bytes.
+///
+/// Decoding is quadratic in the input length, so the length is checked first.
+pub(crate) fn base58_decode(input: &str, max_bytes: usize) -> Option<Vec<u8>> {
+    if input.len() > base58_max_len(max_bytes) {
+        return None;
+    }
+    let zeros = input.bytes().take_while(|&c| c == b'1').count();
+    // Base-256 digits, least significant first
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len() * 733 / 1000 + 1);
+    for c in input.bytes().skip(zeros) {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let mut out = Vec::with_capacity(zeros + bytes.len());
+    out.extend(core::iter::repeat_n(0, zeros));
+    out.extend(bytes.iter().rev());
+    Some(out)
+}
+
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Encode bytes as lowercase, unpadded Crockford base32 (most significant bits first).
+pub(crate) fn base32_crockford_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut buffer = 0u16;
+    let mut bits = 0;
+    for &byte in bytes {
+        buffer = buffer << 8 | byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(CROCKFORD_ALPHABET[(buffer >> bits & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(CROCKFORD_ALPHABET[(buffer << (5 - bits) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Write `bytes` as lowercase hex into `out`, returning the number of bytes
+/// written
+///
+/// Never allocates, for `no_std` targets formatting into fixed buffers.
+/// Fails with [`SdkError::SerializationError`], leaving `out` untouched, if
+/// it is shorter than `2 * bytes.len()`.
+///
+/// # Example
+/// ```
+/// use provn_sdk::encode_hex_into;
+/// let mut out = [0u8; 64];
+/// let len = encode_hex_into(&[0xde, 0xad, 0xbe, 0xef], &mut out).unwrap();
+/// assert_eq!(&out[..len], b"deadbeef");
+/// assert!(encode_hex_into(&[0u8; 33], &mut out).is_err());
+/// ```
+pub fn encode_hex_into(bytes: &[u8], out: &mut [u8]) -> Result<usize> {
+    let len = bytes
+        .len()
+        .checked_mul(2)
+        .filter(|&len| len <= out.len())
+        .ok_or_else(|| {
+            SdkError::SerializationError(format!(
+                "hex output buffer too small: {} bytes, need {}",
+                out.len(),
+                bytes.len().saturating_mul(2)
+            ))
+        })?;
+    for (pair, &byte) in out.chunks_exact_mut(2).zip(bytes) {
+        pair[0] = HEX_DIGITS[usize::from(byte >> 4)];
+        pair[1] = HEX_DIGITS[usize::from(byte & 0x0f)];
+    }
+    Ok(len)
+}
+
+/// Lowercase hex of `bytes`, formatted into `out` without allocating.
+pub(crate) fn hex_str<'a>(bytes: &[u8], out: &'a mut [u8]) -> Result<&'a str> {
+    let len = encode_hex_into(bytes, out)?;
+    core::str::from_utf8(&out[..len]).map_err(|e| SdkError::SerializationError(e.to_string()))
+}
+
+/// Text encoding of a signed claim's public key and signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Lowercase hex, the SDK's default
+    Hex,
+    /// Base58 (Bitcoin alphabet), as used by Solana
+    Base58,
+}
+
+impl Encoding {
+    fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Hex => hex::encode(bytes),
+            Encoding::Base58 => base58_encode(bytes),
+        }
+    }
+
+    pub(crate) fn decode_array<const N: usize>(self, input: &str, field: &str) -> Result<[u8; N]> {
+        match self {
+            Encoding::Hex => decode_hex_array(input, field),
+            Encoding::Base58 => {
+                let bytes = base58_decode(input, N).ok_or_else(|| {
+                    SdkError::KeyError(format!("Invalid Base58 {}: not valid Base58", field))
+                })?;
+                <[u8; N]>::try_from(bytes).map_err(|bytes| {
+                    SdkError::KeyError(format!(
+                        "Invalid Base58 {}: length {} bytes, expected {}",
+                        field,
+                        bytes.len(),
+                        N
+                    ))
+                })
+            }
+        }
+    }
+}
+
+/// Re-encode a signed claim's public key and signature without re-signing
+///
+/// The underlying bytes are unchanged, so the claim still verifies. Fails,
+/// leaving `signed` untouched, if either field is not valid `from` encoding.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, Encoding, reencode, sign_claim, verify_claim, verify_claim_with_encoding, generate_keypair};
+/// let mut signed = sign_claim(&Claim::new("Test".to_string()), &generate_keypair()).unwrap();
+/// reencode(&mut signed, Encoding::Hex, Encoding::Base58).unwrap();
+/// assert!(signed.public_key.len() <= 44);
+/// assert!(reencode(&mut signed, Encoding::Hex, Encoding::Base58).is_err());
+///
+/// // Verifiers read hex only, unless told otherwise
+/// assert!(verify_claim(&signed).is_err());
+/// assert!(verify_claim_with_encoding(&signed, Encoding::Base58).unwrap());
+/// ```
+pub fn reencode<T>(signed: &mut SignedClaim<T>, from: Encoding, to: Encoding) -> Result<()> {
+    let public_key = from.decode_array::<32>(&signed.public_key, "Public Key")?;
+    let signature = from.decode_array::<64>(&signed.signature, "Signature")?;
+    signed.public_key = to.encode(&public_key);
+    signed.signature = to.encode(&signature);
+    Ok(())
+}
+
+/// Verify a signed claim whose public key and signature are in `encoding`
+///
+/// [`verify_claim`] and the other verifiers only read hex; claims re-encoded
+/// to Base58 with [`reencode`] verify through this function instead. Uses
+/// strict verification like [`verify_claim_strict`].
+///
+/// [`verify_claim`]: crate::verify_claim
+/// [`verify_claim_strict`]: crate::verify_claim_strict
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, Encoding, reencode, sign_claim, verify_claim_with_encoding, generate_keypair};
+/// let mut signed = sign_claim(&Claim::new("Test".to_string()), &generate_keypair()).unwrap();
+/// assert!(verify_claim_with_encoding(&signed, Encoding::Hex).unwrap());
+/// reencode(&mut signed, Encoding::Hex, Encoding::Base58).unwrap();
+/// assert!(verify_claim_with_encoding(&signed, Encoding::Base58).unwrap());
+/// ```
+pub fn verify_claim_with_encoding<T: Serialize>(
+    signed: &SignedClaim<T>,
+    encoding: Encoding,
+) -> Result<bool> {
+    let message = signed.whole_message()?;
+    let public_key = encoding.decode_array::<PUBLIC_KEY_BYTES>(&signed.public_key, "Public Key")?;
+    let signature = encoding.decode_array::<SIGNATURE_BYTES>(&signed.signature, "Signature")?;
+    VerifyingKey::from_bytes(&public_key)?
+        .verify_strict(&message, &Signature::from_bytes(&signature))?;
+    Ok(true)
+}
+
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode bytes as unpadded base64url (RFC 4648, section 5).
+pub(crate) fn base64url_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(BASE64URL_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+/// Decode unpadded base64url, rejecting padding, foreign characters and
+/// non-zero trailing bits so that every byte string has exactly one encoding.
+pub(crate) fn base64url_decode(input: &str) -> Option<Vec<u8>> {
+    decode_base64_unpadded(input, BASE64URL_ALPHABET)
+}
+
+/// Decode padded base64 (RFC 4648, section 4), as strictly as
+/// [`base64url_decode`].
+#[cfg(feature = "cosmos")]
+pub(crate) fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    const BASE64_ALPHABET: &[u8; 64] =
+        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    if !input.len().is_multiple_of(4) {
+        return None;
+    }
+    let unpadded = input.trim_end_matches('=');
+    if input.len() - unpadded.len() > 2 {
+        return None;
+    }
+    decode_base64_unpadded(unpadded, BASE64_ALPHABET)
+}
+
+fn decode_base64_unpadded(input: &str, alphabet: &[u8; 64]) -> Option<Vec<u8>> {
+    if input.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for b in input.bytes() {
+        let v = alphabet.iter().position(|&c| c == b)? as u32;
+        acc = acc << 6 | v;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    if acc != 0 {
+        return None;
+    }
+    Some(out)
+}
+
+const BASE45_ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Encode bytes as base45 (RFC 9285), the alphanumeric-mode QR alphabet.
+pub(crate) fn base45_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(2) * 3);
+    for chunk in bytes.chunks(2) {
+        let (mut n, digits) = match *chunk {
+            [a, b] => ((a as u32) << 8 | b as u32, 3),
+            [a] => (a as u32, 2),
+            _ => unreachable!(),
+        };
+        for _ in 0..digits {
+            out.push(BASE45_ALPHABET[(n % 45) as usize] as char);
+            n /= 45;
+        }
+    }
+    out
+}
+
+/// Decode base45, rejecting foreign characters, a dangling final character
+/// and groups whose value overflows their byte width.
+pub(crate) fn base45_decode(input: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len() / 3 * 2 + 1);
+    for group in input.as_bytes().chunks(3) {
+        if group.len() < 2 {
+            return None;
+        }
+        let mut n = 0u32;
+        for &c in group.iter().rev() {
+            n = n * 45 + BASE45_ALPHABET.iter().position(|&a| a == c)? as u32;
+        }
+        if group.len() == 3 {
+            if n > 0xffff {
+                return None;
+            }
+            out.extend_from_slice(&(n as u16).to_be_bytes());
+        } else {
+            out.push(u8::try_from(n).ok()?);
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_hex_into_matches_hex_crate() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let mut out = [0u8; 600];
+        let len = encode_hex_into(&bytes, &mut out).unwrap();
+        assert_eq!(&out[..len], hex::encode(&bytes).as_bytes());
+        assert_eq!(out[len], 0);
+        assert_eq!(encode_hex_into(&[], &mut []).unwrap(), 0);
+
+        let mut short = [0u8; 7];
+        assert!(matches!(
+            encode_hex_into(&[1, 2, 3, 4], &mut short),
+            Err(SdkError::SerializationError(_))
+        ));
+        assert_eq!(short, [0u8; 7]);
+    }
+
+    #[test]
+    fn test_base58_known_values() {
+        assert_eq!(base58_encode(b""), "");
+        assert_eq!(base58_encode(&[0, 0, 1]), "112");
+        assert_eq!(base58_encode(b"hello world"), "StV1DL6CwTryKyV");
+        assert_eq!(
+            base58_decode("StV1DL6CwTryKyV", 11).unwrap(),
+            b"hello world"
+        );
+        assert_eq!(base58_decode("112", 3).unwrap(), [0, 0, 1]);
+        assert_eq!(base58_decode("", 0).unwrap(), b"");
+        assert!(base58_decode("0OIl", 4).is_none());
+
+        // Lengths are checked before the quadratic decode
+        assert_eq!(base58_max_len(32), 44);
+        assert_eq!(base58_max_len(64), 88);
+        assert_eq!(base58_encode(&[0xff; 32]).len(), 44);
+        assert_eq!(base58_encode(&[0xff; 64]).len(), 88);
+        assert!(base58_decode(&"2".repeat(45), 32).is_none());
+    }
+
+    #[test]
+    fn test_base32_crockford_known_values() {
+        assert_eq!(base32_crockford_encode(b""), "");
+        assert_eq!(base32_crockford_encode(b"f"), "cr");
+        assert_eq!(base32_crockford_encode(b"foobar"), "csqpyrk1e8");
+    }
+
+    #[test]
+    fn test_reencode_roundtrip() {
+        use crate::{sign_claim, verify_claim, Claim};
+        use alloc::string::ToString;
+        let key = ed25519_dalek::SigningKey::from_bytes(&[0u8; 32]);
+        let original = sign_claim(&Claim::new_with_timestamp("r".to_string(), 1), &key).unwrap();
+        let mut signed = original.clone();
+        reencode(&mut signed, Encoding::Hex, Encoding::Base58).unwrap();
+        assert_eq!(
+            signed.public_key,
+            "4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS"
+        );
+        assert!(verify_claim(&signed).is_err());
+        assert!(verify_claim_with_encoding(&signed, Encoding::Base58).unwrap());
+        assert!(verify_claim_with_encoding(&signed, Encoding::Hex).is_err());
+        reencode(&mut signed, Encoding::Base58, Encoding::Hex).unwrap();
+        assert_eq!(signed, original);
+    }
+
+    #[test]
+    fn test_base64url_roundtrip() {
+        // RFC 4648 test vectors, unpadded
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", ""),
+            (b"f", "Zg"),
+            (b"fo", "Zm8"),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg"),
+            (b"fooba", "Zm9vYmE"),
+            (b"foobar", "Zm9vYmFy"),
+        ];
+        for (raw, encoded) in vectors {
+            assert_eq!(base64url_encode(raw), encoded);
+            assert_eq!(base64url_decode(encoded).unwrap(), raw);
+        }
+        assert_eq!(base64url_encode(&[0xfb, 0xff]), "-_8");
+        assert!(base64url_decode("Zg==").is_none());
+        assert!(base64url_decode("Zh").is_none());
+        assert!(base64url_decode("Z").is_none());
+    }
+
+    #[test]
+    fn test_base45_roundtrip() {
+        // RFC 9285 section 4.3 examples
+        let vectors: [(&[u8], &str); 4] = [
+            (b"AB", "BB8"),
+            (b"Hello!!", "%69 VD92EX0"),
+            (b"base-45", "UJCLQE7W581"),
+            (b"ietf!", "QED8WEX0"),
+        ];
+        for (raw, encoded) in vectors {
+            assert_eq!(base45_encode(raw), encoded);
+            assert_eq!(base45_decode(encoded).unwrap(), raw);
+        }
+        assert!(base45_decode("GGW").is_none()); // 65536 overflows two bytes
+        assert!(base45_decode("BB8A").is_none());
+        assert!(base45_decode("bb8").is_none());
+    }
+}
@@ -0,0 +1,473 @@
+//! FlatBuffers encoding of signed claims for zero-copy verification.
+//!
+//! The buffer is the FlatBuffers binary form of this schema, so any
+//! generated client can build or read it:
+//!
+//! ```fbs
+//! namespace provn.v1;
+//!
+//! table SignedClaim {
+//!   claim: [ubyte];           // canonical (RFC 8785) JSON of the claim
+//!   public_key: [ubyte];      // raw Ed25519 public key, 32 bytes
+//!   signature: [ubyte];       // raw Ed25519 signature, 64 bytes
+//!   signed_fields: [string];  // see SignedClaim::signed_fields
+//!   metadata_salt: string;
+//!   domain: string;
+//! }
+//!
+//! root_type SignedClaim;
+//! ```
+//!
+//! The claim travels as its canonical bytes, which are exactly what the
+//! signature covers. [`verify_flatbuffer`] verifies the signature over the
+//! borrowed bytes without copying them, after parsing them once to check
+//! that they are the canonical form of a claim (and not some other message
+//! signed by the same key). Claims signed over a subset of their fields are
+//! rejected, as by [`verify_claim`](crate::verify_claim).
+
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{
+    claim_message, domain, parse, require_whole_claim, Claim, Result, SdkError, SignedClaim,
+    PUBLIC_KEY_BYTES, SIGNATURE_BYTES,
+};
+
+/// Number of fields in the `SignedClaim` table.
+const FIELD_COUNT: usize = 6;
+
+fn malformed(reason: &str) -> SdkError {
+    SdkError::SerializationError(format!("malformed flatbuffer: {}", reason))
+}
+
+/// A borrowed view of a FlatBuffers `provn.v1.SignedClaim`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlatSignedClaim<'a> {
+    /// Canonical JSON of the claim
+    pub claim: &'a [u8],
+    /// Raw public key of the signer
+    pub public_key: &'a [u8],
+    /// Raw signature
+    pub signature: &'a [u8],
+    /// Claim fields covered by the signature, if not all of them
+    pub signed_fields: Option<Vec<&'a str>>,
+    /// Salt of the metadata commitment (Hex encoded)
+    pub metadata_salt: Option<&'a str>,
+    /// Signing domain
+    pub domain: Option<&'a str>,
+}
+
+/// Bounds-checked little-endian reads from a buffer.
+struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&self, pos: usize, len: usize) -> Result<&'a [u8]> {
+        pos.checked_add(len)
+            .and_then(|end| self.buf.get(pos..end))
+            .ok_or_else(|| malformed("offset out of bounds"))
+    }
+
+    fn u16(&self, pos: usize) -> Result<u16> {
+        let bytes = self.bytes(pos, 2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&self, pos: usize) -> Result<u32> {
+        let bytes = self.bytes(pos, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Follow the unsigned offset stored at `pos`.
+    fn indirect(&self, pos: usize) -> Result<usize> {
+        pos.checked_add(self.u32(pos)? as usize)
+            .ok_or_else(|| malformed("offset out of bounds"))
+    }
+
+    fn vector(&self, pos: usize) -> Result<&'a [u8]> {
+        let start = self.indirect(pos)?;
+        let len = self.u32(start)? as usize;
+        self.bytes(start + 4, len)
+    }
+
+    fn string(&self, pos: usize) -> Result<&'a str> {
+        core::str::from_utf8(self.vector(pos)?).map_err(|_| malformed("string is not UTF-8"))
+    }
+}
+
+/// Locates the fields of a table through its vtable.
+struct Table<'a> {
+    reader: Reader<'a>,
+    pos: usize,
+    vtable: usize,
+    vtable_len: usize,
+}
+
+impl<'a> Table<'a> {
+    fn root(buf: &'a [u8]) -> Result<Self> {
+        let reader = Reader { buf };
+        let pos = reader.u32(0)? as usize;
+        let soffset = reader.u32(pos)? as i32;
+        let vtable = (pos as i64)
+            .checked_sub(i64::from(soffset))
+            .and_then(|vtable| usize::try_from(vtable).ok())
+            .ok_or_else(|| malformed("vtable out of bounds"))?;
+        let vtable_len = reader.u16(vtable)? as usize;
+        Ok(Self {
+            reader,
+            pos,
+            vtable,
+            vtable_len,
+        })
+    }
+
+    /// Position of field `index`, if present.
+    fn field(&self, index: usize) -> Result<Option<usize>> {
+        let entry = 4 + 2 * index;
+        if entry + 2 > self.vtable_len {
+            return Ok(None);
+        }
+        match self.reader.u16(self.vtable + entry)? {
+            0 => Ok(None),
+            offset => Ok(Some(self.pos + offset as usize)),
+        }
+    }
+
+    fn vector(&self, index: usize) -> Result<Option<&'a [u8]>> {
+        self.field(index)?
+            .map(|pos| self.reader.vector(pos))
+            .transpose()
+    }
+
+    fn string(&self, index: usize) -> Result<Option<&'a str>> {
+        self.field(index)?
+            .map(|pos| self.reader.string(pos))
+            .transpose()
+    }
+
+    fn strings(&self, index: usize) -> Result<Option<Vec<&'a str>>> {
+        let Some(pos) = self.field(index)? else {
+            return Ok(None);
+        };
+        let start = self.reader.indirect(pos)?;
+        let len = self.reader.u32(start)? as usize;
+        // Reject lengths the buffer cannot hold before allocating
+        self.reader.bytes(start + 4, len.saturating_mul(4))?;
+        (0..len)
+            .map(|i| self.reader.string(start + 4 + 4 * i))
+            .collect::<Result<Vec<_>>>()
+            .map(Some)
+    }
+}
+
+impl<'a> FlatSignedClaim<'a> {
+    /// Read a buffer without copying. Unknown fields are ignored.
+    pub fn from_slice(buf: &'a [u8]) -> Result<Self> {
+        let table = Table::root(buf)?;
+        Ok(Self {
+            claim: table.vector(0)?.unwrap_or_default(),
+            public_key: table.vector(1)?.unwrap_or_default(),
+            signature: table.vector(2)?.unwrap_or_default(),
+            signed_fields: table.strings(3)?,
+            metadata_salt: table.string(4)?,
+            domain: table.string(5)?,
+        })
+    }
+
+    /// Verify the signature over the claim bytes
+    ///
+    /// The claim bytes are parsed and must be the canonical JSON of a
+    /// [`Claim`], so a signature the key made over anything else is not
+    /// accepted as a claim. That parse, and re-canonicalizing to compare,
+    /// is the cost of this check on top of Ed25519.
+    pub fn verify(&self) -> Result<bool> {
+        let public_key: &[u8; PUBLIC_KEY_BYTES] = self.public_key.try_into().map_err(|_| {
+            SdkError::KeyError(format!(
+                "Invalid Flatbuffer Public Key: length {} bytes, expected {}",
+                self.public_key.len(),
+                PUBLIC_KEY_BYTES
+            ))
+        })?;
+        let signature: &[u8; SIGNATURE_BYTES] = self.signature.try_into().map_err(|_| {
+            SdkError::KeyError(format!(
+                "Invalid Flatbuffer Signature: length {} bytes, expected {}",
+                self.signature.len(),
+                SIGNATURE_BYTES
+            ))
+        })?;
+
+        require_whole_claim(self.signed_fields.is_some())?;
+
+        // The key may have signed other things (roots, bundles, raw
+        // messages); only the canonical bytes of a claim count as one
+        let claim: Claim<serde_json::Value> = parse::from_slice_unique(self.claim)?;
+        let message = match self.metadata_salt {
+            None => {
+                if claim.to_signable_bytes()? != self.claim {
+                    return Err(malformed("claim bytes are not a canonical claim"));
+                }
+                Cow::Borrowed(self.claim)
+            }
+            Some(salt) => Cow::Owned(claim_message(&claim, None, Some(salt))?),
+        };
+        let message = match self.domain {
+            None => message,
+            Some(name) => Cow::Owned(domain::with_domain(Some(name), message.into_owned())?),
+        };
+
+        VerifyingKey::from_bytes(public_key)?
+            .verify(&message, &Signature::from_bytes(signature))?;
+        Ok(true)
+    }
+}
+
+/// Verify a FlatBuffers encoded signed claim (requires the "flatbuffers" feature)
+///
+/// Reads the key, signature and canonical claim bytes in place, then checks
+/// them as [`FlatSignedClaim::verify`] describes. Like
+/// [`verify_claim`](crate::verify_claim), it rejects claims signed over a
+/// subset of their fields.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_flatbuffer, generate_keypair};
+/// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &generate_keypair()).unwrap();
+/// let buf = signed.to_flatbuffer().unwrap();
+/// assert!(verify_flatbuffer(&buf).unwrap());
+/// ```
+pub fn verify_flatbuffer(buf: &[u8]) -> Result<bool> {
+    FlatSignedClaim::from_slice(buf)?.verify()
+}
+
+/// Append a vector (or, with `nul`, a string) aligned to 4 bytes and return
+/// its position.
+fn write_vector(out: &mut Vec<u8>, bytes: &[u8], nul: bool) -> usize {
+    let pos = out.len();
+    out.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    out.extend_from_slice(bytes);
+    if nul {
+        out.push(0);
+    }
+    while !out.len().is_multiple_of(4) {
+        out.push(0);
+    }
+    pos
+}
+
+fn patch_offset(out: &mut [u8], slot: usize, target: usize) {
+    out[slot..slot + 4].copy_from_slice(&((target - slot) as u32).to_le_bytes());
+}
+
+impl<T: Serialize> SignedClaim<T> {
+    /// Encode as a FlatBuffers `provn.v1.SignedClaim` (requires the "flatbuffers" feature)
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, SignedClaim, sign_claim, generate_keypair};
+    /// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &generate_keypair()).unwrap();
+    /// let decoded: SignedClaim = SignedClaim::from_flatbuffer(&signed.to_flatbuffer().unwrap()).unwrap();
+    /// assert_eq!(decoded, signed);
+    /// ```
+    pub fn to_flatbuffer(&self) -> Result<Vec<u8>> {
+        let claim = self.claim.to_signable_bytes()?;
+        let public_key = self.public_key_bytes()?;
+        let signature = self.signature_bytes()?;
+        let present = [
+            true,
+            true,
+            true,
+            self.signed_fields.is_some(),
+            self.metadata_salt.is_some(),
+            self.domain.is_some(),
+        ];
+
+        // Root offset, then the vtable, then the table, then the field data
+        let vtable = 4;
+        let table = vtable + 4 + 2 * FIELD_COUNT;
+        let mut slots = [0usize; FIELD_COUNT];
+        let mut table_len = 4;
+        for (slot, _) in slots
+            .iter_mut()
+            .zip(present)
+            .filter(|(_, present)| *present)
+        {
+            *slot = table_len;
+            table_len += 4;
+        }
+
+        let mut out = Vec::with_capacity(table + table_len + claim.len() + 128);
+        out.extend_from_slice(&(table as u32).to_le_bytes());
+        out.extend_from_slice(&((4 + 2 * FIELD_COUNT) as u16).to_le_bytes());
+        out.extend_from_slice(&(table_len as u16).to_le_bytes());
+        for slot in slots {
+            out.extend_from_slice(&(slot as u16).to_le_bytes());
+        }
+        out.extend_from_slice(&((table - vtable) as i32).to_le_bytes());
+        out.resize(table + table_len, 0);
+
+        let write = |out: &mut Vec<u8>, field: usize, bytes: &[u8], nul: bool| {
+            let pos = write_vector(out, bytes, nul);
+            patch_offset(out, table + slots[field], pos);
+        };
+        write(&mut out, 0, &claim, false);
+        write(&mut out, 1, &public_key, false);
+        write(&mut out, 2, &signature, false);
+        if let Some(salt) = &self.metadata_salt {
+            write(&mut out, 4, salt.as_bytes(), true);
+        }
+        if let Some(name) = &self.domain {
+            write(&mut out, 5, name.as_bytes(), true);
+        }
+        if let Some(fields) = &self.signed_fields {
+            // A vector of offsets: its length counts elements, not bytes
+            let list = out.len();
+            out.extend_from_slice(&(fields.len() as u32).to_le_bytes());
+            out.resize(list + 4 + 4 * fields.len(), 0);
+            patch_offset(&mut out, table + slots[3], list);
+            for (i, field) in fields.iter().enumerate() {
+                let pos = write_vector(&mut out, field.as_bytes(), true);
+                patch_offset(&mut out, list + 4 + 4 * i, pos);
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl<T: DeserializeOwned> SignedClaim<T> {
+    /// Decode a FlatBuffers `provn.v1.SignedClaim` (requires the "flatbuffers" feature)
+    ///
+    /// The signature itself is not checked.
+    pub fn from_flatbuffer(buf: &[u8]) -> Result<Self> {
+        let flat = FlatSignedClaim::from_slice(buf)?;
+        if flat.public_key.len() != PUBLIC_KEY_BYTES || flat.signature.len() != SIGNATURE_BYTES {
+            return Err(SdkError::KeyError(format!(
+                "flatbuffer key and signature must be {} and {} bytes, got {} and {}",
+                PUBLIC_KEY_BYTES,
+                SIGNATURE_BYTES,
+                flat.public_key.len(),
+                flat.signature.len()
+            )));
+        }
+        Ok(SignedClaim {
+            claim: parse::from_slice_unique(flat.claim)?,
+            public_key: hex::encode(flat.public_key),
+            signature: hex::encode(flat.signature),
+            signed_fields: flat
+                .signed_fields
+                .map(|fields| fields.into_iter().map(String::from).collect()),
+            metadata_salt: flat.metadata_salt.map(String::from),
+            domain: flat.domain.map(String::from),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, sign_claim_fields, sign_claim_with_domain, verify_claim};
+    use alloc::string::ToString;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    #[test]
+    fn test_roundtrip_matches_verify_claim() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claim = Claim::new_with_timestamp("x".to_string(), 1);
+        claim.metadata = Some("note".to_string());
+        for signed in [
+            sign_claim(&claim, &key).unwrap(),
+            sign_claim_with_domain(&claim, &key, "provn-v1").unwrap(),
+        ] {
+            let buf = signed.to_flatbuffer().unwrap();
+            assert_eq!(buf.len() % 4, 0);
+            assert!(verify_flatbuffer(&buf).unwrap());
+            let decoded: SignedClaim = SignedClaim::from_flatbuffer(&buf).unwrap();
+            assert_eq!(decoded, signed);
+
+            let mut tampered = signed.clone();
+            tampered.claim.data = "y".to_string();
+            assert_eq!(
+                verify_flatbuffer(&tampered.to_flatbuffer().unwrap()).is_ok(),
+                verify_claim(&tampered).is_ok()
+            );
+        }
+
+        // A signature the key made over something other than a claim
+        let buf = sign_claim(&claim, &key).unwrap().to_flatbuffer().unwrap();
+        let mut flat = FlatSignedClaim::from_slice(&buf).unwrap();
+        let raw = b"not a claim at all";
+        let signature = key.sign(raw).to_bytes();
+        flat.claim = raw;
+        flat.signature = &signature;
+        assert!(matches!(
+            flat.verify(),
+            Err(SdkError::SerializationError(_))
+        ));
+        // nor a claim in non-canonical form, even if that is what was signed
+        let loose = br#"{"timestamp":1,"data":"x"}"#;
+        let signature = key.sign(loose).to_bytes();
+        flat.claim = loose;
+        flat.signature = &signature;
+        assert!(matches!(
+            flat.verify(),
+            Err(SdkError::SerializationError(_))
+        ));
+
+        // Partial signatures round-trip but, as with verify_claim, do not verify
+        let partial = sign_claim_fields(&claim, &key, &["data", "timestamp"]).unwrap();
+        let buf = partial.to_flatbuffer().unwrap();
+        assert_eq!(
+            SignedClaim::<String>::from_flatbuffer(&buf).unwrap(),
+            partial
+        );
+        assert!(matches!(
+            verify_flatbuffer(&buf),
+            Err(SdkError::PolicyError(_))
+        ));
+
+        // The claim bytes are borrowed from the buffer
+        let signed = sign_claim(&claim, &key).unwrap();
+        let buf = signed.to_flatbuffer().unwrap();
+        let flat = FlatSignedClaim::from_slice(&buf).unwrap();
+        let range = buf.as_ptr_range();
+        assert!(range.contains(&flat.claim.as_ptr()));
+        assert_eq!(flat.claim, &claim.to_signable_bytes().unwrap()[..]);
+        assert_eq!(flat.signed_fields, None);
+    }
+
+    #[test]
+    fn test_reads_buffers_laid_out_by_other_builders() {
+        // flatc-style layout: vtable after the table (negative soffset) and
+        // shorter than the schema, with the key and signature left out
+        let mut buf = alloc::vec![0u8; 4];
+        let table = buf.len();
+        buf.extend_from_slice(&[0; 8]); // soffset, claim offset
+        let vtable = buf.len();
+        buf.extend_from_slice(&[10, 0, 8, 0, 4, 0, 0, 0, 0, 0]);
+        buf.extend_from_slice(&[0, 0]);
+        let claim = buf.len();
+        buf.extend_from_slice(&[2, 0, 0, 0, b'{', b'}', 0, 0]);
+        buf[..4].copy_from_slice(&(table as u32).to_le_bytes());
+        buf[table..table + 4].copy_from_slice(&(table as i32 - vtable as i32).to_le_bytes());
+        buf[table + 4..table + 8].copy_from_slice(&((claim - table - 4) as u32).to_le_bytes());
+
+        let flat = FlatSignedClaim::from_slice(&buf).unwrap();
+        assert_eq!(flat.claim, b"{}");
+        assert!(flat.public_key.is_empty() && flat.domain.is_none());
+        assert!(matches!(flat.verify(), Err(SdkError::KeyError(_))));
+
+        // Offsets pointing outside the buffer are rejected, not followed
+        let mut broken = buf.clone();
+        broken[table + 4..table + 8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            FlatSignedClaim::from_slice(&broken),
+            Err(SdkError::SerializationError(_))
+        ));
+        assert!(verify_flatbuffer(&buf[..3]).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Typed claim identifiers.
+
+use alloc::string::String;
+use core::fmt;
+use core::str::FromStr;
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+use crate::encoding::hex_str;
+use crate::{canonicalize, compute_hash, Claim, Result, SdkError, SignedClaim};
+
+/// The SHA-256 hash of a claim's canonical bytes (or, from
+/// [`SignedClaim::signed_id`], of a signed claim's; in Merkle proofs and
+/// roots, of a tree node over claims).
+///
+/// Displays, parses and serializes as 64 hex characters, but cannot be mixed
+/// up with a hex public key or signature at the type level.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, ClaimId, claim_hash};
+/// let claim = Claim::new_with_timestamp("Test".to_string(), 1);
+/// let id = claim.id().unwrap();
+/// assert_eq!(id.to_string(), claim_hash(&claim).unwrap());
+/// assert_eq!(id.to_string().parse::<ClaimId>().unwrap(), id);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ClaimId(pub [u8; 32]);
+
+impl ClaimId {
+    /// The raw hash bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// The ID as a 52-character slug (see [`claim_slug`])
+    pub fn to_slug(&self) -> String {
+        crate::encoding::base32_crockford_encode(&self.0)
+    }
+}
+
+/// A fixed-width, URL- and filename-safe ID for a claim
+///
+/// The slug is the claim hash ([`claim_hash`](crate::claim_hash)) encoded as
+/// Crockford base32: alphabet `0123456789abcdefghjkmnpqrstvwxyz`, lowercase,
+/// most significant bits first, no padding, the final 4 bits zero-filled.
+/// It is always 52 characters long.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, claim_slug};
+/// let slug = claim_slug(&Claim::new_with_timestamp("Test".to_string(), 1)).unwrap();
+/// assert_eq!(slug, "0kszgtc5wzznwzfgzq10ksvstqpkv0zy4rj4jar7jcwtf1473y3g");
+/// ```
+pub fn claim_slug<T: Serialize>(claim: &Claim<T>) -> Result<String> {
+    Ok(claim.id()?.to_slug())
+}
+
+/// The parts of a claim addressed by [`content_id`].
+#[derive(Serialize)]
+struct Content<'a, T> {
+    data: &'a T,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: &'a Option<String>,
+}
+
+/// A timestamp-independent content address for a claim (Hex SHA-256)
+///
+/// Hashes the canonical JSON object `{"data":<data>,"metadata":<metadata>}`
+/// (`metadata` omitted when `None`). No other field is included: not
+/// `timestamp`, `expires_at`, `kind`, `attachments`, `metadata_hash`, `pow`
+/// or `salt`. The same statement made at different times therefore has the same
+/// content ID, while [`claim_hash`](crate::claim_hash) and the signature
+/// still cover every field.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, claim_hash, content_id};
+/// let monday = Claim::new_with_timestamp("Test".to_string(), 1);
+/// let tuesday = Claim::new_with_timestamp("Test".to_string(), 86_401);
+/// assert_eq!(content_id(&monday).unwrap(), content_id(&tuesday).unwrap());
+/// assert_ne!(claim_hash(&monday).unwrap(), claim_hash(&tuesday).unwrap());
+/// ```
+pub fn content_id<T: Serialize>(claim: &Claim<T>) -> Result<String> {
+    let content = Content {
+        data: &claim.data,
+        metadata: &claim.metadata,
+    };
+    Ok(compute_hash(&canonicalize(&content)?))
+}
+
+impl<T: Serialize> Claim<T> {
+    /// Identifier of this claim: the SHA-256 hash of its canonical bytes
+    pub fn id(&self) -> Result<ClaimId> {
+        Ok(ClaimId(Sha256::digest(self.to_signable_bytes()?).into()))
+    }
+}
+
+impl<T: Serialize> SignedClaim<T> {
+    /// Identifier of this signed claim, envelope included: the SHA-256 hash
+    /// of its canonical bytes, as [`signed_claim_hash`](crate::signed_claim_hash)
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, sign_claim, signed_claim_hash, generate_keypair};
+    /// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+    /// assert_eq!(signed.signed_id().unwrap().to_string(), signed_claim_hash(&signed).unwrap());
+    /// ```
+    pub fn signed_id(&self) -> Result<ClaimId> {
+        Ok(ClaimId(Sha256::digest(self.to_canonical_bytes()?).into()))
+    }
+}
+
+impl From<[u8; 32]> for ClaimId {
+    fn from(bytes: [u8; 32]) -> Self {
+        ClaimId(bytes)
+    }
+}
+
+impl fmt::Display for ClaimId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut hex = [0u8; 64];
+        f.write_str(hex_str(&self.0, &mut hex).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl FromStr for ClaimId {
+    type Err = SdkError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        crate::decode_hex_array(s, "Claim Id")
+            .map(ClaimId)
+            .map_err(|e| match e {
+                SdkError::KeyError(msg) => SdkError::SerializationError(msg),
+                other => other,
+            })
+    }
+}
+
+impl Serialize for ClaimId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut hex = [0u8; 64];
+        serializer.serialize_str(hex_str(&self.0, &mut hex).map_err(ser::Error::custom)?)
+    }
+}
+
+impl<'de> Deserialize<'de> for ClaimId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        hex.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_content_id_covers_data_and_metadata_only() {
+        let mut claim = Claim::new_with_timestamp("Test".to_string(), 1);
+        assert_eq!(
+            content_id(&claim).unwrap(),
+            compute_hash(br#"{"data":"Test"}"#)
+        );
+        claim.metadata = Some("m".to_string());
+        let with_metadata = content_id(&claim).unwrap();
+        assert_eq!(
+            with_metadata,
+            compute_hash(br#"{"data":"Test","metadata":"m"}"#)
+        );
+        claim.timestamp = 2;
+        claim.expires_at = Some(3);
+        claim.kind = Some("access".to_string());
+        assert_eq!(content_id(&claim).unwrap(), with_metadata);
+    }
+
+    #[test]
+    fn test_claim_id_round_trips() {
+        let id = Claim::new_with_timestamp("Test".to_string(), 1)
+            .id()
+            .unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, alloc::format!("\"{}\"", id));
+        assert_eq!(serde_json::from_str::<ClaimId>(&json).unwrap(), id);
+    }
+
+    #[test]
+    fn test_claim_id_rejects_wrong_length() {
+        let short = "ab".repeat(31);
+        match short.parse::<ClaimId>() {
+            Err(SdkError::SerializationError(msg)) => {
+                assert_eq!(msg, "Invalid Hex Claim Id: length 31 bytes, expected 32")
+            }
+            other => panic!("expected a length error, got {:?}", other),
+        }
+        assert!(serde_json::from_str::<ClaimId>("\"abcd\"").is_err());
+    }
+
+    #[test]
+    fn test_slug_is_pinned_and_fixed_width() {
+        let claim = Claim::new_with_timestamp("Test".to_string(), 1);
+        // Claim hash 04f3f869...871f87
+        assert_eq!(
+            claim_slug(&claim).unwrap(),
+            "0kszgtc5wzznwzfgzq10ksvstqpkv0zy4rj4jar7jcwtf1473y3g"
+        );
+        assert_eq!(
+            ClaimId([0xff; 32]).to_slug(),
+            alloc::format!("{}g", "z".repeat(51))
+        );
+        assert_eq!(ClaimId([0; 32]).to_slug(), "0".repeat(52));
+    }
+}
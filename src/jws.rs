@@ -0,0 +1,200 @@
+//! Compact JWS (RFC 7515) interop with `alg: EdDSA` (RFC 8037).
+//!
+//! A JWS signs `BASE64URL(header) || "." || BASE64URL(payload)`, not the raw
+//! JCS bytes signed by [`crate::sign_claim`]. The payload here *is* the
+//! canonical claim JSON, but the signature covers its base64url form plus the
+//! header, so a JWS signature cannot be moved into a `SignedClaim` (or vice
+//! versa). The signer's key travels in the header as an OKP `jwk`, but since
+//! anyone can put their own key there, [`verify_jws`] requires the key the
+//! verifier expects.
+
+use alloc::format;
+use alloc::string::String;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::encoding::{base64url_decode, base64url_encode};
+use crate::{Claim, Result, SdkError};
+
+/// Public key in JWK form (RFC 8037). Fields are ordered alphabetically.
+#[derive(Serialize, Deserialize)]
+struct OkpJwk {
+    crv: String,
+    kty: String,
+    x: String,
+}
+
+/// Protected JWS header. Fields are ordered alphabetically.
+#[derive(Serialize, Deserialize)]
+struct JwsHeader {
+    alg: String,
+    /// Critical extensions (RFC 7515, 4.1.11); none are understood
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    crit: Option<serde_json::Value>,
+    jwk: OkpJwk,
+}
+
+fn jws_error(msg: &str) -> SdkError {
+    SdkError::SerializationError(format!("Invalid JWS: {}", msg))
+}
+
+/// Decode exactly `N` bytes of base64url, reporting the actual length.
+fn decode_fixed<const N: usize>(input: &str, field: &str) -> Result<[u8; N]> {
+    let bytes = base64url_decode(input)
+        .ok_or_else(|| SdkError::KeyError(format!("Invalid {}: not valid base64url", field)))?;
+    <[u8; N]>::try_from(bytes.as_slice()).map_err(|_| {
+        SdkError::KeyError(format!(
+            "Invalid {}: length {} bytes, expected {}",
+            field,
+            bytes.len(),
+            N
+        ))
+    })
+}
+
+/// Sign a claim as a compact JWS (`header.payload.signature`)
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, to_jws, verify_jws, generate_keypair};
+/// let claim = Claim::new("Test Claim".to_string());
+/// let key = generate_keypair();
+/// let jws = to_jws(&claim, &key).unwrap();
+/// assert_eq!(jws.split('.').count(), 3);
+/// let verified: Claim = verify_jws(&jws, &key.verifying_key()).unwrap();
+/// assert_eq!(verified, claim);
+/// ```
+pub fn to_jws<T: Serialize>(claim: &Claim<T>, key: &SigningKey) -> Result<String> {
+    let header = JwsHeader {
+        alg: "EdDSA".into(),
+        crit: None,
+        jwk: OkpJwk {
+            crv: "Ed25519".into(),
+            kty: "OKP".into(),
+            x: base64url_encode(key.verifying_key().as_bytes()),
+        },
+    };
+    let signing_input = format!(
+        "{}.{}",
+        base64url_encode(serde_json::to_string(&header)?.as_bytes()),
+        base64url_encode(&claim.to_signable_bytes()?)
+    );
+    let signature = key.sign(signing_input.as_bytes());
+    Ok(format!(
+        "{}.{}",
+        signing_input,
+        base64url_encode(&signature.to_bytes())
+    ))
+}
+
+/// Verify a compact JWS produced by [`to_jws`], signed by `expected`, and
+/// return its claim
+///
+/// The header must declare `alg: EdDSA` with an Ed25519 OKP `jwk` equal to
+/// `expected`, and must not carry `crit`. The signature is checked with
+/// strict verification.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, to_jws, verify_jws, generate_keypair};
+/// let jws = to_jws(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// // Valid, but not signed by the key we trust
+/// let trusted = generate_keypair().verifying_key();
+/// assert!(verify_jws::<String>(&jws, &trusted).is_err());
+/// ```
+pub fn verify_jws<T: DeserializeOwned>(jws: &str, expected: &VerifyingKey) -> Result<Claim<T>> {
+    let mut parts = jws.split('.');
+    let (header_b64, payload_b64, signature_b64) =
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(h), Some(p), Some(s), None) => (h, p, s),
+            _ => return Err(jws_error("expected three dot-separated segments")),
+        };
+
+    let header_bytes =
+        base64url_decode(header_b64).ok_or_else(|| jws_error("bad header encoding"))?;
+    let header: JwsHeader = crate::parse::from_slice_unique(&header_bytes)?;
+    if header.alg != "EdDSA" || header.jwk.kty != "OKP" || header.jwk.crv != "Ed25519" {
+        return Err(jws_error("unsupported algorithm or key type"));
+    }
+    if header.crit.is_some() {
+        return Err(jws_error("critical header extensions are not supported"));
+    }
+
+    let pk_bytes = decode_fixed::<32>(&header.jwk.x, "JWK Public Key")?;
+    if pk_bytes != expected.to_bytes() {
+        return Err(SdkError::KeyError(
+            "JWS is not signed by the expected key".into(),
+        ));
+    }
+
+    let sig_bytes = decode_fixed::<64>(signature_b64, "JWS Signature")?;
+    let signing_input = &jws[..header_b64.len() + 1 + payload_b64.len()];
+    expected.verify_strict(signing_input.as_bytes(), &Signature::from_bytes(&sig_bytes))?;
+
+    let payload = base64url_decode(payload_b64).ok_or_else(|| jws_error("bad payload encoding"))?;
+    crate::parse::from_slice_unique(&payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_jws_structure_and_tamper() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("Hello World".to_string(), 123456789);
+        let jws = to_jws(&claim, &key).unwrap();
+
+        let segments: alloc::vec::Vec<&str> = jws.split('.').collect();
+        let header = base64url_decode(segments[0]).unwrap();
+        assert!(header.starts_with(br#"{"alg":"EdDSA","jwk":{"crv":"Ed25519","kty":"OKP""#));
+        assert_eq!(
+            base64url_decode(segments[1]).unwrap(),
+            claim.to_signable_bytes().unwrap()
+        );
+
+        let pk = key.verifying_key();
+        assert_eq!(verify_jws::<String>(&jws, &pk).unwrap(), claim);
+        let forged_payload = base64url_encode(br#"{"data":"Forged","timestamp":123456789}"#);
+        let forged = format!("{}.{}.{}", segments[0], forged_payload, segments[2]);
+        assert!(verify_jws::<String>(&forged, &pk).is_err());
+        assert!(verify_jws::<String>("a.b", &pk).is_err());
+
+        // A self-consistent JWS minted with another key is not trusted
+        let minted = to_jws(&claim, &SigningKey::from_bytes(&[1u8; 32])).unwrap();
+        assert!(matches!(
+            verify_jws::<String>(&minted, &pk),
+            Err(SdkError::KeyError(_))
+        ));
+
+        // Headers with critical extensions are refused
+        let mut header: serde_json::Value = serde_json::from_slice(&header).unwrap();
+        header["crit"] = serde_json::json!(["exp"]);
+        let input = format!(
+            "{}.{}",
+            base64url_encode(header.to_string().as_bytes()),
+            segments[1]
+        );
+        let crit = format!(
+            "{}.{}",
+            input,
+            base64url_encode(&key.sign(input.as_bytes()).to_bytes())
+        );
+        assert!(verify_jws::<String>(&crit, &pk)
+            .unwrap_err()
+            .to_string()
+            .contains("critical"));
+
+        let truncated = format!("{}.{}.{}", segments[0], segments[1], &segments[2][..84]);
+        let err = verify_jws::<String>(&truncated, &pk)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.ends_with("JWS Signature: length 63 bytes, expected 64"),
+            "{}",
+            err
+        );
+    }
+}
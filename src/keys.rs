@@ -0,0 +1,97 @@
+//! Loading signing keys from the environment or from files.
+//!
+//! Keys are stored as the hex encoded 32-byte Ed25519 seed (an optional `0x`
+//! prefix and surrounding whitespace are accepted). Every intermediate copy
+//! of the secret is zeroized once the key has been parsed.
+
+use std::path::Path;
+
+use ed25519_dalek::SigningKey;
+use zeroize::Zeroizing;
+
+use crate::{decode_hex_array, Result, SdkError};
+
+/// Conventional environment variable holding a service's signing key.
+pub const SIGNING_KEY_ENV: &str = "PROVN_SIGNING_KEY";
+
+fn parse_signing_key(secret: &str, source: &str) -> Result<SigningKey> {
+    let secret = secret.trim();
+    if secret.is_empty() {
+        return Err(SdkError::KeyError(format!("{} is empty", source)));
+    }
+    if secret.starts_with('{') {
+        return Err(SdkError::KeyError(format!(
+            "{} looks like an encrypted keystore, which is not supported; \
+             provide the hex encoded seed instead",
+            source
+        )));
+    }
+    let seed = Zeroizing::new(
+        decode_hex_array::<32>(secret, "Signing Key")
+            .map_err(|e| SdkError::KeyError(format!("{}: {}", source, e)))?,
+    );
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Load a signing key from a hex encoded seed in an environment variable
+///
+/// # Example
+/// ```
+/// use provn_sdk::load_key_from_env;
+/// std::env::set_var("DOC_SIGNING_KEY", "00".repeat(32));
+/// let key = load_key_from_env("DOC_SIGNING_KEY").unwrap();
+/// assert_eq!(key.to_bytes(), [0u8; 32]);
+/// assert!(load_key_from_env("DOC_MISSING_KEY").is_err());
+/// ```
+pub fn load_key_from_env(var: &str) -> Result<SigningKey> {
+    let secret = Zeroizing::new(
+        std::env::var(var)
+            .map_err(|e| SdkError::KeyError(format!("environment variable {}: {}", var, e)))?,
+    );
+    parse_signing_key(&secret, &format!("environment variable {}", var))
+}
+
+/// Load a signing key from a file containing the hex encoded seed
+pub fn load_key_from_file(path: &Path) -> Result<SigningKey> {
+    let source = format!("key file {}", path.display());
+    let secret = Zeroizing::new(
+        std::fs::read_to_string(path)
+            .map_err(|e| SdkError::KeyError(format!("{}: {}", source, e)))?,
+    );
+    parse_signing_key(&secret, &source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_key_from_file() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("provn-key-{}.hex", std::process::id()));
+        let seed = [0x11u8; 32];
+        std::fs::write(&path, format!("0x{}\n", hex::encode(seed))).unwrap();
+        assert_eq!(load_key_from_file(&path).unwrap().to_bytes(), seed);
+
+        std::fs::write(&path, r#"{"crypto":{}}"#).unwrap();
+        let err = load_key_from_file(&path).unwrap_err().to_string();
+        assert!(err.contains("keystore"), "{}", err);
+
+        std::fs::write(&path, "zz").unwrap();
+        assert!(matches!(
+            load_key_from_file(&path),
+            Err(SdkError::KeyError(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(load_key_from_file(&dir.join("provn-no-such-key")).is_err());
+    }
+
+    #[test]
+    fn test_errors_do_not_echo_the_secret() {
+        let secret = "ab".repeat(31);
+        let err = parse_signing_key(&secret, "test").unwrap_err().to_string();
+        assert!(!err.contains(&secret), "{}", err);
+        assert!(err.contains("length 31 bytes, expected 32"), "{}", err);
+    }
+}
@@ -0,0 +1,119 @@
+//! Claim kinds for routing.
+//!
+//! The signed `kind` field of a [`Claim`](crate::Claim) is a plain string so
+//! that new kinds never invalidate old verifiers; [`ClaimKind`] is the typed
+//! view of it.
+
+use alloc::string::{String, ToString};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// What a claim records, as stored in [`Claim::kind`](crate::Claim::kind).
+///
+/// Serializes as its lowercase name (`"access"`, `"change"`, `"approval"`),
+/// or the string itself for [`ClaimKind::Other`].
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, ClaimKind};
+/// let mut claim = Claim::new_with_timestamp("door 4 opened".to_string(), 1);
+/// claim.kind = Some(ClaimKind::Access.into());
+/// match claim.claim_kind() {
+///     Some(ClaimKind::Access) => {}
+///     other => panic!("unexpected kind {:?}", other),
+/// }
+/// assert_eq!(ClaimKind::from("deploy"), ClaimKind::Other("deploy".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ClaimKind {
+    /// Someone accessed a resource
+    Access,
+    /// Something was modified
+    Change,
+    /// Someone approved something
+    Approval,
+    /// Any other kind
+    Other(String),
+}
+
+impl ClaimKind {
+    /// The string stored in the claim
+    pub fn as_str(&self) -> &str {
+        match self {
+            ClaimKind::Access => "access",
+            ClaimKind::Change => "change",
+            ClaimKind::Approval => "approval",
+            ClaimKind::Other(kind) => kind,
+        }
+    }
+}
+
+impl From<&str> for ClaimKind {
+    fn from(kind: &str) -> Self {
+        match kind {
+            "access" => ClaimKind::Access,
+            "change" => ClaimKind::Change,
+            "approval" => ClaimKind::Approval,
+            other => ClaimKind::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for ClaimKind {
+    fn from(kind: String) -> Self {
+        match ClaimKind::from(kind.as_str()) {
+            ClaimKind::Other(_) => ClaimKind::Other(kind),
+            known => known,
+        }
+    }
+}
+
+impl From<ClaimKind> for String {
+    fn from(kind: ClaimKind) -> Self {
+        match kind {
+            ClaimKind::Other(kind) => kind,
+            known => known.as_str().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ClaimKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, verify_claim, Claim};
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_kind_is_signed_and_optional() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let plain = claim.to_signable_bytes().unwrap();
+        assert!(!String::from_utf8(plain).unwrap().contains("kind"));
+
+        claim.kind = Some(ClaimKind::Approval.into());
+        assert_eq!(
+            claim.to_signable_bytes().unwrap(),
+            br#"{"data":"x","kind":"approval","timestamp":1}"#
+        );
+        let mut signed = sign_claim(&claim, &key).unwrap();
+        assert!(verify_claim(&signed).unwrap());
+        signed.claim.kind = Some("change".into());
+        assert!(verify_claim(&signed).is_err());
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        for kind in [ClaimKind::Change, ClaimKind::Other("audit/export".into())] {
+            let json = serde_json::to_string(&kind).unwrap();
+            assert_eq!(json, alloc::format!("\"{}\"", kind));
+            assert_eq!(serde_json::from_str::<ClaimKind>(&json).unwrap(), kind);
+        }
+    }
+}
@@ -20,6 +20,151 @@ use core::fmt;
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 
+mod anchor;
+mod anomaly;
+#[cfg(feature = "arweave")]
+mod arweave;
+mod attachment;
+mod batch;
+#[cfg(feature = "std")]
+mod cache;
+mod canonical;
+#[cfg(feature = "cbor")]
+mod cbor;
+mod commitment;
+#[cfg(feature = "compression")]
+mod compress;
+#[cfg(feature = "cosmos")]
+mod cosmos;
+mod did;
+mod disclosure;
+mod domain;
+mod encoding;
+#[cfg(feature = "flatbuffers")]
+mod flatbuf;
+mod id;
+mod jws;
+#[cfg(feature = "std")]
+mod keys;
+mod kind;
+mod log;
+mod mac;
+mod merkle;
+mod metrics;
+mod multisig;
+mod nested;
+mod parse;
+mod policy;
+#[cfg(feature = "prost")]
+mod proto;
+mod redact;
+mod registry;
+mod report;
+mod rotation;
+#[cfg(feature = "solana")]
+mod solana;
+#[cfg(feature = "std")]
+mod stream;
+mod time;
+mod trace;
+mod trust;
+#[cfg(feature = "rfc3161")]
+mod tsa;
+#[cfg(feature = "std")]
+mod unique;
+mod verified;
+
+pub use anchor::{AnchorEntry, MultiAnchor};
+pub use anomaly::{detect_signature_anomaly, AnomalyReport};
+#[cfg(feature = "arweave")]
+pub use arweave::{to_ans104_dataitem, verify_ans104_dataitem, ANS104_CONTENT_TYPE};
+pub use attachment::{verify_attachment, Attachment};
+pub use batch::{
+    dedup_by_content, dedup_by_content_earliest, filter_valid, group_by_signer, partition_valid,
+    retain_valid, verify_batch, InvalidClaim,
+};
+#[cfg(feature = "std")]
+pub use cache::VerifyCache;
+pub use canonical::{canonicalize, canonicalize_many};
+#[cfg(feature = "cbor")]
+pub use cbor::assert_cross_format_equivalent;
+pub use commitment::{commit_claim, verify_commitment, COMMITMENT_SALT_BYTES};
+#[cfg(feature = "compression")]
+pub use compress::{
+    decompress, verify_compressed, verify_compressed_with_limit, Compression,
+    MAX_DECOMPRESSED_BYTES,
+};
+#[cfg(feature = "cosmos")]
+pub use cosmos::{from_amino_pubkey, verify_claim_cosmos};
+pub use did::{from_did_key, to_did_key, verify_and_identify, verify_claim_by_did};
+pub use disclosure::{
+    disclose, sign_selective, verify_disclosure, DisclosedClaim, DisclosedField, FieldCommitment,
+    SelectiveClaim, DISCLOSURE_SALT_BYTES,
+};
+pub use domain::{sign_claim_with_domain, verify_claim_with_domain};
+pub use encoding::{encode_hex_into, reencode, verify_claim_with_encoding, Encoding};
+#[cfg(feature = "flatbuffers")]
+pub use flatbuf::{verify_flatbuffer, FlatSignedClaim};
+pub use id::{claim_slug, content_id, ClaimId};
+pub use jws::{to_jws, verify_jws};
+#[cfg(feature = "std")]
+pub use keys::{load_key_from_env, load_key_from_file, SIGNING_KEY_ENV};
+pub use kind::ClaimKind;
+#[cfg(feature = "std")]
+pub use log::checkpoint;
+pub use log::{
+    checkpoint_at, merge_logs, verify_checkpoint, CheckpointMetadata, ClaimLog, LogEntry,
+    MergeOutcome, CHECKPOINT_KIND, GENESIS_HASH,
+};
+pub use mac::{mac_claim, verify_mac};
+pub use merkle::{
+    anchor_batch, merkle_leaf_hash, sign_root, verify_anchored, verify_root, AnchoredBatch,
+    BatchReceipt, MerkleAccumulator, MerkleProof, MerkleTree, SignedRoot,
+};
+#[cfg(feature = "metrics")]
+pub use metrics::{verify_metrics, VerifyCounts, VerifyMetric, VERIFY_COUNTER};
+pub use multisig::{ClaimSignature, MultiSignedClaim};
+pub use nested::{
+    verify_nested, verify_nested_with_depth, NestedVerification, DEFAULT_NESTING_DEPTH,
+};
+pub use parse::{reject_duplicate_keys, ParseLimits};
+pub use policy::{
+    verify_and_remaining, verify_claim_max_age, verify_with_policy, VerifyOutcome, VerifyPolicy,
+    NO_EXPIRY,
+};
+#[cfg(feature = "prost")]
+pub use proto::{ProtoFieldList, ProtoSignedClaim};
+pub use redact::{redact_metadata, sign_claim_redactable, METADATA_SALT_BYTES};
+pub use registry::{verify_and_attribute, KeyRegistry, MemoryRegistry};
+#[cfg(feature = "std")]
+pub use report::verification_report;
+pub use report::{
+    debug_canonical, preview_sign, verification_report_at, SignPreview, VerificationReport,
+};
+pub use rotation::{verify_with_key_history, KeyHistory, KeyWindow, ROTATION_METADATA};
+#[cfg(feature = "solana")]
+pub use solana::{
+    claims_from_ed25519_instruction, parse_ed25519_instruction, Ed25519PrecompileEntry,
+};
+#[cfg(feature = "std")]
+pub use stream::{
+    sign_claim_streaming, sign_file_claim, verify_claim_streaming, verify_file_claim,
+    verify_ndjson, verify_ndjson_file, VerifySummary, MAX_NDJSON_LINE_BYTES,
+};
+#[cfg(feature = "std")]
+pub use time::SystemClock;
+pub use time::{sign_claim_trusted_time, TrustedTime};
+#[cfg(feature = "trace")]
+pub use trace::TRACE_TARGET;
+pub use trust::{
+    trusted_key_from_hex, verify_claim_anchored, verify_claim_trusted, TRUSTED_PUBKEY,
+};
+#[cfg(feature = "rfc3161")]
+pub use tsa::{verify_claim_with_tsa, verify_claim_with_tsa_within, TsaToken, TSA_TOLERANCE_SECS};
+#[cfg(feature = "std")]
+pub use unique::{sign_claim_unique, CLAIM_SALT_BYTES};
+pub use verified::{verify_and_extract, VerifiedClaim};
+
 /// Errors encountered during SDK operations.
 #[derive(Debug)]
 pub enum SdkError {
@@ -29,6 +174,12 @@ pub enum SdkError {
     SignatureError(String),
     /// Error occurred due to invalid key format or length.
     KeyError(String),
+    /// Error occurred because a Merkle inclusion proof did not check out.
+    ProofError(String),
+    /// A valid signature was rejected by a verification policy (expiry, key allow-list, ...).
+    PolicyError(String),
+    /// The current time could not be established from a time source.
+    TimeError(String),
 }
 
 impl fmt::Display for SdkError {
@@ -37,6 +188,9 @@ impl fmt::Display for SdkError {
             SdkError::SerializationError(e) => write!(f, "Serialization failed: {}", e),
             SdkError::SignatureError(e) => write!(f, "Invalid signature: {}", e),
             SdkError::KeyError(e) => write!(f, "Key format error: {}", e),
+            SdkError::ProofError(e) => write!(f, "Invalid proof: {}", e),
+            SdkError::PolicyError(e) => write!(f, "Policy violation: {}", e),
+            SdkError::TimeError(e) => write!(f, "Time source error: {}", e),
         }
     }
 }
@@ -61,60 +215,737 @@ pub type Result<T> = core::result::Result<T, SdkError>;
 /// A Claim representing a statement of truth to be anchored.
 /// Fields are ordered alphabetically to ensure "Canonical JSON" (JCS - RFC 8785)
 /// compliance when using deterministic serialization.
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct Claim {
+///
+/// The `data` payload is generic so products can sign typed claims (audit
+/// records, model cards, ...) while keeping the canonical signing guarantee.
+/// `Claim` without a type parameter is `Claim<String>`, so existing code is
+/// unaffected. A custom `T` is serialized in place of the string and
+/// canonicalized with the rest of the claim, so its fields and map keys may be
+/// declared in any order.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_claim, generate_keypair};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, Clone)]
+/// struct ModelCard {
+///     accuracy: u32,
+///     model: String,
+/// }
+///
+/// let card = ModelCard { accuracy: 98, model: "v1.0".to_string() };
+/// let claim = Claim::new_with_timestamp(card, 1700000000);
+/// let signed = sign_claim(&claim, &generate_keypair()).unwrap();
+/// assert!(verify_claim(&signed).unwrap());
+/// ```
+///
+/// # Equality, ordering and hashing
+///
+/// `Eq`, `Ord` and `Hash` are implemented over the canonical bytes of the
+/// claim, so two claims are equal exactly when they would sign the same
+/// message, and ordering is a plain byte-wise comparison of those bytes. This
+/// makes `HashSet<Claim>` and `BTreeMap<Claim, _>` suitable for deduplication
+/// even when `T` contains floats.
+///
+/// A claim with a timestamp beyond [`MAX_SAFE_TIMESTAMP`] has no canonical
+/// bytes; it is compared by its serde_json bytes instead and sorts after
+/// every claim that can be canonicalized, so the two encodings are never
+/// compared with each other. Claims whose payload cannot be serialized at all
+/// are equal to one another and sort last. Neither kind can be signed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Claim<T = String> {
+    /// Optional external artifacts bound by hash (see [`verify_attachment`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<Attachment>>,
     /// The actual data being claimed (e.g., "AI Model v1.0 Accuracy: 98%")
-    pub data: String,
+    pub data: T,
+    /// Optional expiry of the claim (UTC seconds), checked by [`verify_with_policy`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    /// Optional kind of claim for routing, e.g. `"access"` (see [`ClaimKind`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
     /// Optional metadata or context
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<String>,
+    /// Salted hash standing in for redacted `metadata` (see [`redact_metadata`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata_hash: Option<String>,
+    /// Optional proof-of-work nonce (see [`mine_claim`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pow: Option<String>,
+    /// Optional random salt making otherwise identical claims distinct (see
+    /// [`sign_claim_unique`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
     /// Timestamp of the claim (UTC seconds)
     pub timestamp: u64,
 }
 
 /// A SignedClaim wraps the claim with its signature and public key.
 #[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SignedClaim {
+pub struct SignedClaim<T = String> {
+    /// The original claim
+    pub claim: Claim<T>,
+    /// Signing domain the signature is bound to (see [`sign_claim_with_domain`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    /// Salt of the metadata commitment (Hex encoded), for claims signed with
+    /// [`sign_claim_redactable`] whose metadata has not been redacted yet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_salt: Option<String>,
+    /// The public key of the signer (Hex encoded, or Base58 after [`reencode`];
+    /// see [`verify_claim_with_encoding`])
+    pub public_key: String,
+    /// The signature of the serialized claim (Hex encoded, or Base58 after
+    /// [`reencode`]; see [`verify_claim_with_encoding`])
+    pub signature: String,
+    /// If set, only these claim fields are covered by the signature (see [`sign_claim_fields`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_fields: Option<Vec<String>>,
+}
+
+impl<T> SignedClaim<T> {
+    /// Decode the raw 64-byte Ed25519 signature
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, sign_claim, generate_keypair};
+    /// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+    /// let sig: [u8; 64] = signed.signature_bytes().unwrap();
+    /// assert_eq!(hex::encode(sig), signed.signature);
+    /// ```
+    pub fn signature_bytes(&self) -> Result<[u8; SIGNATURE_BYTES]> {
+        decode_signature_bytes(&self.signature)
+    }
+
+    /// Decode the raw 32-byte Ed25519 public key of the signer
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, sign_claim, generate_keypair};
+    /// let key = generate_keypair();
+    /// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &key).unwrap();
+    /// assert_eq!(&signed.public_key_bytes().unwrap(), key.verifying_key().as_bytes());
+    /// ```
+    pub fn public_key_bytes(&self) -> Result<[u8; PUBLIC_KEY_BYTES]> {
+        decode_public_key_bytes(&self.public_key)
+    }
+}
+
+impl<T: Serialize> SignedClaim<T> {
+    /// The exact bytes covered by the signature
+    ///
+    /// This is the canonical claim, or the signed field list and canonical
+    /// subset it names for claims produced by [`sign_claim_fields`], with
+    /// `metadata` replaced by its salted hash for claims produced by
+    /// [`sign_claim_redactable`], and prefixed by the signing domain for
+    /// claims produced by [`sign_claim_with_domain`].
+    pub fn signed_message(&self) -> Result<Vec<u8>> {
+        let message = claim_message(
+            &self.claim,
+            self.signed_fields.as_deref(),
+            self.metadata_salt.as_deref(),
+        )?;
+        domain::with_domain(self.domain.as_deref(), message)
+    }
+
+    /// [`signed_message`](Self::signed_message), refusing claims whose
+    /// signature covers only some fields.
+    pub(crate) fn whole_message(&self) -> Result<Vec<u8>> {
+        require_whole_claim(self.signed_fields.is_some())?;
+        self.signed_message()
+    }
+
+    /// Canonical (RFC 8785) bytes of the whole envelope: claim, public key and signature
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, sign_claim, generate_keypair};
+    /// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &generate_keypair()).unwrap();
+    /// let bytes = signed.to_canonical_bytes().unwrap();
+    /// assert!(bytes.starts_with(br#"{"claim":{"data":"Test","timestamp":1},"public_key":""#));
+    /// ```
+    pub fn to_canonical_bytes(&self) -> Result<Vec<u8>> {
+        self.claim.check_timestamp()?;
+        canonicalize(self)
+    }
+
+    /// Encode the whole signed claim as unpadded base64url over its JSON
+    ///
+    /// The result needs no URL escaping, so it can be passed as a query
+    /// parameter or embedded in a link.
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, SignedClaim, sign_claim, verify_claim, generate_keypair};
+    /// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &generate_keypair()).unwrap();
+    /// let blob = signed.to_base64url().unwrap();
+    /// let decoded: SignedClaim = SignedClaim::from_base64url(&blob).unwrap();
+    /// assert!(verify_claim(&decoded).unwrap());
+    /// ```
+    pub fn to_base64url(&self) -> Result<String> {
+        Ok(encoding::base64url_encode(&self.to_canonical_bytes()?))
+    }
+
+    /// Compact binary form: `public key (32) || signature (64) || canonical claim`
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, SignedClaim, sign_claim, generate_keypair};
+    /// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &generate_keypair()).unwrap();
+    /// let bytes = signed.to_bytes().unwrap();
+    /// assert_eq!(&bytes[96..], br#"{"data":"Test","timestamp":1}"#);
+    /// let decoded: SignedClaim = SignedClaim::from_bytes(&bytes).unwrap();
+    /// assert_eq!(decoded.signature, signed.signature);
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        if self.signed_fields.is_some() || self.metadata_salt.is_some() || self.domain.is_some() {
+            return Err(SdkError::SerializationError(
+                "the binary form cannot carry signed_fields, a metadata salt or a domain"
+                    .to_string(),
+            ));
+        }
+        let claim = self.claim.to_signable_bytes()?;
+        let mut out = Vec::with_capacity(SIGNED_BYTES_HEADER_LEN + claim.len());
+        out.extend_from_slice(&self.public_key_bytes()?);
+        out.extend_from_slice(&self.signature_bytes()?);
+        out.extend_from_slice(&claim);
+        Ok(out)
+    }
+
+    /// Encode [`SignedClaim::to_bytes`] as base45 for alphanumeric-mode QR codes
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, SignedClaim, sign_claim, verify_claim, generate_keypair};
+    /// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &generate_keypair()).unwrap();
+    /// let payload = signed.to_qr_payload().unwrap();
+    /// let decoded: SignedClaim = SignedClaim::from_qr_payload(&payload).unwrap();
+    /// assert!(verify_claim(&decoded).unwrap());
+    /// ```
+    pub fn to_qr_payload(&self) -> Result<String> {
+        Ok(encoding::base45_encode(&self.to_bytes()?))
+    }
+}
+
+/// Length of the key and signature prefix of [`SignedClaim::to_bytes`].
+const SIGNED_BYTES_HEADER_LEN: usize = 32 + 64;
+
+impl<T: serde::de::DeserializeOwned> SignedClaim<T> {
+    /// Decode a signed claim produced by [`SignedClaim::to_base64url`]
+    ///
+    /// Fails unless the input is strict unpadded base64url whose payload is a
+    /// well-formed `SignedClaim` JSON object. The signature is not checked.
+    pub fn from_base64url(s: &str) -> Result<Self> {
+        let json = encoding::base64url_decode(s).ok_or_else(|| {
+            SdkError::SerializationError("invalid base64url signed claim".to_string())
+        })?;
+        parse::from_slice_unique(&json)
+    }
+
+    /// Decode the binary form produced by [`SignedClaim::to_bytes`]
+    ///
+    /// The signature is not checked.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < SIGNED_BYTES_HEADER_LEN {
+            return Err(SdkError::SerializationError(format!(
+                "signed claim bytes must be at least {} bytes, got {}",
+                SIGNED_BYTES_HEADER_LEN,
+                bytes.len()
+            )));
+        }
+        let (public_key, rest) = bytes.split_at(32);
+        let (signature, claim) = rest.split_at(64);
+        Ok(SignedClaim {
+            claim: parse::from_slice_unique(claim)?,
+            public_key: hex::encode(public_key),
+            signature: hex::encode(signature),
+            signed_fields: None,
+            metadata_salt: None,
+            domain: None,
+        })
+    }
+
+    /// Decode a payload produced by [`SignedClaim::to_qr_payload`]
+    pub fn from_qr_payload(payload: &str) -> Result<Self> {
+        let bytes = encoding::base45_decode(payload).ok_or_else(|| {
+            SdkError::SerializationError("invalid base45 signed claim".to_string())
+        })?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Largest integer magnitude that can be canonicalized: `2^53 - 1`
+/// (`Number.MAX_SAFE_INTEGER`).
+///
+/// JCS verifiers in JavaScript parse numbers as IEEE-754 doubles, so larger
+/// integers would be rounded and re-serialized differently, breaking the
+/// signature across platforms. Values beyond this bound are rejected at
+/// canonicalization time instead.
+pub const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+/// Largest timestamp that can be canonicalized, equal to [`MAX_SAFE_INTEGER`].
+pub const MAX_SAFE_TIMESTAMP: u64 = MAX_SAFE_INTEGER;
+
+/// Length of a raw Ed25519 public key, in bytes.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{PUBLIC_KEY_BYTES, SECRET_KEY_BYTES, SIGNATURE_BYTES, keypair_from_seed};
+/// let key = keypair_from_seed(&[0u8; SECRET_KEY_BYTES]);
+/// let pk: [u8; PUBLIC_KEY_BYTES] = key.verifying_key().to_bytes();
+/// assert_eq!(hex::encode(pk).len(), 2 * PUBLIC_KEY_BYTES);
+/// assert_eq!(SIGNATURE_BYTES, 64);
+/// ```
+pub const PUBLIC_KEY_BYTES: usize = ed25519_dalek::PUBLIC_KEY_LENGTH;
+
+/// Length of a raw Ed25519 signature, in bytes.
+pub const SIGNATURE_BYTES: usize = ed25519_dalek::SIGNATURE_LENGTH;
+
+/// Length of an Ed25519 secret key (seed), in bytes.
+pub const SECRET_KEY_BYTES: usize = ed25519_dalek::SECRET_KEY_LENGTH;
+
+/// A bandwidth-friendly encoding of [`SignedClaim`] using one-letter field names.
+///
+/// Serializes as `{"c":<claim>,"p":"<public key>","s":"<signature>"}`. The
+/// claim itself is unchanged, so verification reconstructs exactly the same
+/// canonical bytes regardless of which representation was transmitted.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, CompactSignedClaim, SignedClaim, sign_claim, generate_keypair};
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// let compact = CompactSignedClaim::from(signed.clone());
+/// let json = serde_json::to_string(&compact).unwrap();
+/// assert!(json.starts_with(r#"{"c":"#));
+/// let restored: SignedClaim = serde_json::from_str::<CompactSignedClaim>(&json).unwrap().into();
+/// assert_eq!(restored.signature, signed.signature);
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CompactSignedClaim<T = String> {
     /// The original claim
-    pub claim: Claim,
+    #[serde(rename = "c")]
+    pub claim: Claim<T>,
     /// The public key of the signer (Hex encoded)
+    #[serde(rename = "p")]
     pub public_key: String,
     /// The signature of the serialized claim (Hex encoded)
+    #[serde(rename = "s")]
     pub signature: String,
+    /// Claim fields covered by the signature, if not all of them
+    #[serde(rename = "f", default, skip_serializing_if = "Option::is_none")]
+    pub signed_fields: Option<Vec<String>>,
+    /// Salt of the metadata commitment, for unredacted redactable claims
+    #[serde(rename = "m", default, skip_serializing_if = "Option::is_none")]
+    pub metadata_salt: Option<String>,
+    /// Signing domain the signature is bound to
+    #[serde(rename = "d", default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+}
+
+impl<T> From<SignedClaim<T>> for CompactSignedClaim<T> {
+    fn from(signed: SignedClaim<T>) -> Self {
+        Self {
+            claim: signed.claim,
+            public_key: signed.public_key,
+            signature: signed.signature,
+            signed_fields: signed.signed_fields,
+            metadata_salt: signed.metadata_salt,
+            domain: signed.domain,
+        }
+    }
+}
+
+impl<T> From<CompactSignedClaim<T>> for SignedClaim<T> {
+    fn from(compact: CompactSignedClaim<T>) -> Self {
+        Self {
+            claim: compact.claim,
+            public_key: compact.public_key,
+            signature: compact.signature,
+            signed_fields: compact.signed_fields,
+            metadata_salt: compact.metadata_salt,
+            domain: compact.domain,
+        }
+    }
 }
 
-impl Claim {
+impl<T> Claim<T> {
     /// Create a new claim with the current system time (requires "std")
     #[cfg(feature = "std")]
-    pub fn new(data: String) -> Self {
+    pub fn new(data: T) -> Self {
         Self {
             data,
             timestamp: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
                 .as_secs(),
+            attachments: None,
+            expires_at: None,
+            kind: None,
             metadata: None,
+            metadata_hash: None,
+            pow: None,
+            salt: None,
         }
     }
 
     /// Create a new claim with a provided timestamp (useful for no-std)
-    pub fn new_with_timestamp(data: String, timestamp: u64) -> Self {
+    pub fn new_with_timestamp(data: T, timestamp: u64) -> Self {
         Self {
             data,
             timestamp,
+            attachments: None,
+            expires_at: None,
+            kind: None,
             metadata: None,
+            metadata_hash: None,
+            pow: None,
+            salt: None,
+        }
+    }
+
+    /// The claim's `kind` as a [`ClaimKind`], if set
+    pub fn claim_kind(&self) -> Option<ClaimKind> {
+        self.kind.as_deref().map(ClaimKind::from)
+    }
+
+    /// Set `metadata` to the canonical JSON text of `value`
+    ///
+    /// The stored string is always valid JSON and depends only on the value,
+    /// not on key order or formatting, so consumers can parse it back.
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::Claim;
+    /// let claim = Claim::new_with_timestamp("Deploy".to_string(), 1)
+    ///     .metadata_json(serde_json::json!({ "region": "eu", "build": 42 }))
+    ///     .unwrap();
+    /// assert_eq!(claim.metadata.as_deref(), Some(r#"{"build":42,"region":"eu"}"#));
+    /// ```
+    pub fn metadata_json(mut self, value: serde_json::Value) -> Result<Self> {
+        let text = String::from_utf8(canonicalize(&value)?)
+            .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+        self.metadata = Some(text);
+        Ok(self)
+    }
+
+    /// Create a new claim from a timestamp in the given unit
+    ///
+    /// The value is converted to whole seconds (truncating), which is what
+    /// `timestamp` always holds on the wire.
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, TimeUnit};
+    /// let claim = Claim::new_with_unit("Test".to_string(), 1700000000123, TimeUnit::Millis);
+    /// assert_eq!(claim.timestamp, 1700000000);
+    /// ```
+    pub fn new_with_unit(data: T, value: u64, unit: TimeUnit) -> Self {
+        Self::new_with_timestamp(data, unit.to_seconds(value))
+    }
+
+    /// Create a new claim from a millisecond epoch, stored as seconds
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::Claim;
+    /// let claim = Claim::from_millis("Test".to_string(), 1700000000999);
+    /// assert_eq!(claim.timestamp, 1700000000);
+    /// assert_eq!(claim.timestamp_millis(), 1700000000000);
+    /// ```
+    pub fn from_millis(data: T, millis: u64) -> Self {
+        Self::new_with_unit(data, millis, TimeUnit::Millis)
+    }
+
+    /// The timestamp in milliseconds (saturating)
+    pub fn timestamp_millis(&self) -> u64 {
+        self.timestamp.saturating_mul(1000)
+    }
+}
+
+/// Unit of an epoch timestamp supplied to [`Claim::new_with_unit`].
+///
+/// Claims always store seconds; this only describes the input, so that
+/// millisecond sources are converted explicitly instead of being stored as
+/// a timestamp 1000 times too large.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    /// Seconds since the Unix epoch
+    Seconds,
+    /// Milliseconds since the Unix epoch
+    Millis,
+}
+
+impl TimeUnit {
+    /// Convert a value in this unit to whole seconds (truncating)
+    pub fn to_seconds(self, value: u64) -> u64 {
+        match self {
+            TimeUnit::Seconds => value,
+            TimeUnit::Millis => value / 1000,
+        }
+    }
+}
+
+impl<T: Serialize> Claim<T> {
+    fn check_timestamp(&self) -> Result<()> {
+        for (field, value) in [
+            ("timestamp", Some(self.timestamp)),
+            ("expires_at", self.expires_at),
+        ] {
+            if let Some(value) = value.filter(|&v| v > MAX_SAFE_TIMESTAMP) {
+                return Err(SdkError::SerializationError(format!(
+                    "{} {} exceeds the JavaScript safe integer range",
+                    field, value
+                )));
+            }
         }
+        Ok(())
+    }
+
+    /// The canonical structure of the claim as a `serde_json::Value`
+    ///
+    /// Passing the returned value to [`canonicalize`] reproduces
+    /// [`Claim::to_signable_bytes`] byte-for-byte.
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{canonicalize, Claim};
+    /// let claim = Claim::new_with_timestamp("Test".to_string(), 1);
+    /// let value = claim.to_canonical_value().unwrap();
+    /// assert_eq!(value["data"], "Test");
+    /// assert_eq!(canonicalize(&value).unwrap(), claim.to_signable_bytes().unwrap());
+    /// ```
+    pub fn to_canonical_value(&self) -> Result<serde_json::Value> {
+        self.check_timestamp()?;
+        Ok(serde_json::to_value(self)?)
     }
 
     /// Canonical serialization for signing (Sorted keys, no whitespace)
-    /// This follows JCS (RFC 8785) logic by relying on struct field ordering.
+    /// This follows JCS (RFC 8785) via [`canonicalize`]: object keys are
+    /// sorted at every depth, including inside arrays, regardless of struct
+    /// field order.
+    ///
+    /// Fails if the timestamp exceeds [`MAX_SAFE_TIMESTAMP`], because such a
+    /// value cannot be represented exactly by JavaScript verifiers.
     pub fn to_signable_bytes(&self) -> Result<Vec<u8>> {
-        // Enforce canonical JSON (no whitespace, sorted keys via struct order)
-        let json = serde_json::to_string(self)?;
-        Ok(json.into_bytes())
+        self.check_timestamp()?;
+        canonicalize(self)
+    }
+}
+
+/// Bytes a claim is compared by. Variants are ordered first, so canonical
+/// bytes are never compared with plain serde_json bytes.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum OrderingBytes {
+    /// RFC 8785 canonical bytes
+    Canonical(Vec<u8>),
+    /// serde_json bytes of a claim that cannot be canonicalized
+    Serialized(Vec<u8>),
+    /// The claim cannot be serialized at all
+    Unserializable,
+}
+
+impl<T: Serialize> Claim<T> {
+    /// Canonical bytes used for comparison, falling back to serde_json so
+    /// that claims beyond the timestamp limit remain comparable.
+    fn ordering_bytes(&self) -> OrderingBytes {
+        match canonicalize(self) {
+            Ok(bytes) => OrderingBytes::Canonical(bytes),
+            Err(_) => match serde_json::to_vec(self) {
+                Ok(bytes) => OrderingBytes::Serialized(bytes),
+                Err(_) => OrderingBytes::Unserializable,
+            },
+        }
+    }
+}
+
+impl<T: Serialize> PartialEq for Claim<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ordering_bytes() == other.ordering_bytes()
+    }
+}
+
+impl<T: Serialize> Eq for Claim<T> {}
+
+impl<T: Serialize> PartialOrd for Claim<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Serialize> Ord for Claim<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.ordering_bytes().cmp(&other.ordering_bytes())
+    }
+}
+
+impl<T: Serialize> core::hash::Hash for Claim<T> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.ordering_bytes().hash(state);
+    }
+}
+
+/// Sort key of a [`SignedClaim`]: timestamp, claim hash, then the envelope
+/// (see [`SignedClaim::ordering_key`]).
+pub type SignedClaimKey = (
+    u64,
+    String,
+    String,
+    String,
+    Option<Vec<String>>,
+    Option<String>,
+    Option<String>,
+);
+
+impl<T: Serialize> SignedClaim<T> {
+    /// The key signed claims are ordered by
+    ///
+    /// Computing it canonicalizes and hashes the claim, and `sort()` does so
+    /// for both sides of every comparison. To sort a large log, compute the
+    /// key once per claim with `sort_by_cached_key`, which gives the same
+    /// order.
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, SignedClaim, sign_claim, generate_keypair};
+    /// let key = generate_keypair();
+    /// let mut log: Vec<_> = [30, 10, 20]
+    ///     .iter()
+    ///     .map(|&ts| sign_claim(&Claim::new_with_timestamp("entry".to_string(), ts), &key).unwrap())
+    ///     .collect();
+    /// let mut sorted = log.clone();
+    /// sorted.sort();
+    /// log.sort_by_cached_key(SignedClaim::ordering_key);
+    /// assert_eq!(log, sorted);
+    /// ```
+    pub fn ordering_key(&self) -> SignedClaimKey {
+        (
+            self.claim.timestamp,
+            match self.claim.ordering_bytes() {
+                OrderingBytes::Canonical(bytes) | OrderingBytes::Serialized(bytes) => {
+                    compute_hash(&bytes)
+                }
+                OrderingBytes::Unserializable => String::new(),
+            },
+            self.public_key.clone(),
+            self.signature.clone(),
+            self.signed_fields.clone(),
+            self.metadata_salt.clone(),
+            self.domain.clone(),
+        )
+    }
+}
+
+/// Signed claims are ordered chronologically.
+///
+/// The ordering key is `(claim.timestamp, claim_hash(claim))`, so claims
+/// made in the same second still sort deterministically. Envelopes over the
+/// same claim are then ordered by public key, signature, signed fields,
+/// metadata salt and domain, and two signed claims are equal only if all of
+/// these match.
+///
+/// Every comparison canonicalizes and hashes both claims; use
+/// `sort_by_cached_key(SignedClaim::ordering_key)` to sort large logs.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let mut log: Vec<_> = [30, 10, 20]
+///     .iter()
+///     .map(|&ts| sign_claim(&Claim::new_with_timestamp("entry".to_string(), ts), &key).unwrap())
+///     .collect();
+/// log.sort();
+/// let order: Vec<u64> = log.iter().map(|s| s.claim.timestamp).collect();
+/// assert_eq!(order, [10, 20, 30]);
+/// ```
+impl<T: Serialize> PartialEq for SignedClaim<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.claim == other.claim && self.ordering_key() == other.ordering_key()
+    }
+}
+
+impl<T: Serialize> Eq for SignedClaim<T> {}
+
+impl<T: Serialize> PartialOrd for SignedClaim<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Serialize> Ord for SignedClaim<T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.ordering_key().cmp(&other.ordering_key())
     }
 }
 
+/// Compare two signed claims, checking key and signature bytes in constant time
+///
+/// The public keys and signatures are decoded and compared with
+/// [`subtle::ConstantTimeEq`], so the time taken does not reveal how many
+/// leading bytes of a signature or key match; this matters when an attacker
+/// can submit candidates against a stored claim and time the answer. The
+/// claims themselves (canonical bytes), `signed_fields`, `metadata_salt` and
+/// `domain` are public and are compared normally. Unlike `==`, upper- and
+/// lower-case hex of the same bytes compare equal, and claims whose key or
+/// signature does not decode as hex are never equal.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, signed_claims_equal_ct, generate_keypair};
+/// let signed = sign_claim(&Claim::new("Test".to_string()), &generate_keypair()).unwrap();
+/// let mut upper = signed.clone();
+/// upper.signature = upper.signature.to_uppercase();
+/// assert!(signed_claims_equal_ct(&signed, &upper));
+/// ```
+pub fn signed_claims_equal_ct<T: Serialize>(a: &SignedClaim<T>, b: &SignedClaim<T>) -> bool {
+    use subtle::ConstantTimeEq;
+    let (Ok(a_key), Ok(b_key), Ok(a_sig), Ok(b_sig)) = (
+        decode_public_key_bytes(&a.public_key),
+        decode_public_key_bytes(&b.public_key),
+        decode_signature_bytes(&a.signature),
+        decode_signature_bytes(&b.signature),
+    ) else {
+        return false;
+    };
+    let secret_adjacent_equal = bool::from(a_key.ct_eq(&b_key) & a_sig.ct_eq(&b_sig));
+    secret_adjacent_equal
+        && a.signed_fields == b.signed_fields
+        && a.metadata_salt == b.metadata_salt
+        && a.domain == b.domain
+        && a.claim == b.claim
+}
+
+/// Encode a claim timestamp for binary wire formats.
+///
+/// The binary layout of a timestamp is always **8 bytes, little-endian,
+/// unsigned** (UTC seconds), independent of the host's native endianness.
+/// The JSON canonical form is unaffected: there the timestamp is a plain
+/// decimal integer.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{timestamp_from_le_bytes, timestamp_to_le_bytes};
+/// let bytes = timestamp_to_le_bytes(0x0102030405060708);
+/// assert_eq!(bytes, [0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01]);
+/// assert_eq!(timestamp_from_le_bytes(&bytes).unwrap(), 0x0102030405060708);
+/// ```
+pub fn timestamp_to_le_bytes(timestamp: u64) -> [u8; 8] {
+    timestamp.to_le_bytes()
+}
+
+/// Decode a timestamp written by [`timestamp_to_le_bytes`].
+///
+/// Fails unless `bytes` is exactly 8 bytes long.
+pub fn timestamp_from_le_bytes(bytes: &[u8]) -> Result<u64> {
+    let bytes: [u8; 8] = bytes.try_into().map_err(|_| {
+        SdkError::SerializationError(format!("timestamp must be 8 bytes, got {}", bytes.len()))
+    })?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
 /// Compute a SHA-256 hash of a byte slice for "Hash-Only" forensics.
 pub fn compute_hash(data: &[u8]) -> String {
     use sha2::{Digest, Sha256};
@@ -123,82 +954,1118 @@ pub fn compute_hash(data: &[u8]) -> String {
     hex::encode(hasher.finalize())
 }
 
-/// Generate a new random keypair (requires "std" for entropy)
+/// Hash algorithms supported by the hashing helpers.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlg {
+    /// SHA-256 (the default used by claim hashes and Merkle trees)
+    #[default]
+    Sha256,
+    /// SHA-384
+    Sha384,
+    /// SHA-512
+    Sha512,
+}
+
+fn hash_segments<D: sha2::Digest>(segments: &[&[u8]]) -> String {
+    let mut hasher = D::new();
+    for segment in segments {
+        hasher.update(segment);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Hash several byte segments in order, as if they were concatenated.
+///
+/// Segments are fed to the hasher one by one, so domain-separated hashes
+/// (`prefix || data`) need no temporary buffer.
 ///
 /// # Example
 /// ```
-/// use provn_sdk::generate_keypair;
-/// let key = generate_keypair();
+/// use provn_sdk::{compute_hash, compute_hash_multi, HashAlg};
+/// let joined = compute_hash_multi(&[b"provn:", b"payload"], HashAlg::Sha256);
+/// assert_eq!(joined, compute_hash(b"provn:payload"));
 /// ```
-#[cfg(feature = "std")]
-pub fn generate_keypair() -> SigningKey {
-    use rand::rngs::OsRng;
-    SigningKey::generate(&mut OsRng)
+pub fn compute_hash_multi(segments: &[&[u8]], alg: HashAlg) -> String {
+    match alg {
+        HashAlg::Sha256 => hash_segments::<sha2::Sha256>(segments),
+        HashAlg::Sha384 => hash_segments::<sha2::Sha384>(segments),
+        HashAlg::Sha512 => hash_segments::<sha2::Sha512>(segments),
+    }
 }
 
-/// Sign a claim with a private key
+/// Compute the SHA-256 hash of a claim's canonical bytes (Hex encoded).
 ///
 /// # Example
 /// ```
-/// use provn_sdk::{Claim, sign_claim, generate_keypair};
-/// let key = generate_keypair();
-/// let claim = Claim::new("Test Claim".to_string());
-/// let signed = sign_claim(&claim, &key).unwrap();
+/// use provn_sdk::{Claim, claim_hash, compute_hash};
+/// let claim = Claim::new_with_timestamp("Test".to_string(), 1);
+/// let expected = compute_hash(&claim.to_signable_bytes().unwrap());
+/// assert_eq!(claim_hash(&claim).unwrap(), expected);
 /// ```
-pub fn sign_claim(claim: &Claim, key: &SigningKey) -> Result<SignedClaim> {
-    let bytes = claim.to_signable_bytes()?;
-    let signature = key.sign(&bytes);
+pub fn claim_hash<T: Serialize>(claim: &Claim<T>) -> Result<String> {
+    Ok(compute_hash(&claim.to_signable_bytes()?))
+}
 
-    Ok(SignedClaim {
-        claim: claim.clone(),
-        public_key: hex::encode(key.verifying_key().as_bytes()),
-        signature: hex::encode(signature.to_bytes()),
-    })
+/// Compute the digest to submit to an OpenTimestamps calendar.
+///
+/// OpenTimestamps commits to the SHA-256 of a file, so this is the SHA-256 of
+/// the claim's canonical bytes: stamping a file holding exactly
+/// [`Claim::to_signable_bytes`] with `ots stamp` commits to the same digest.
+/// To submit it directly, `POST` the 32 raw bytes to a calendar's `/digest`
+/// endpoint (e.g. `https://a.pool.opentimestamps.org/digest`); the response
+/// is the pending timestamp, to be upgraded once the calendar has anchored it
+/// in Bitcoin.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, claim_hash, to_ots_digest};
+/// let claim = Claim::new_with_timestamp("Test".to_string(), 1);
+/// let digest = to_ots_digest(&claim).unwrap();
+/// assert_eq!(hex::encode(digest), claim_hash(&claim).unwrap());
+/// ```
+pub fn to_ots_digest<T: Serialize>(claim: &Claim<T>) -> Result<[u8; 32]> {
+    use sha2::{Digest, Sha256};
+    Ok(Sha256::digest(claim.to_signable_bytes()?).into())
 }
 
-/// Verify a signed claim
+/// Serialize a claim once and return its canonical bytes with their hash (Hex encoded).
+///
+/// Equivalent to calling [`Claim::to_signable_bytes`] and hashing the result
+/// with [`compute_hash_multi`], without serializing twice.
 ///
 /// # Example
 /// ```
-/// use provn_sdk::{Claim, sign_claim, verify_claim, generate_keypair};
-/// let key = generate_keypair();
-/// let claim = Claim::new("Test Claim".to_string());
-/// let signed = sign_claim(&claim, &key).unwrap();
-/// assert!(verify_claim(&signed).unwrap());
+/// use provn_sdk::{Claim, canonicalize_and_hash, claim_hash, HashAlg};
+/// let claim = Claim::new_with_timestamp("Test".to_string(), 1);
+/// let (bytes, hash) = canonicalize_and_hash(&claim, HashAlg::Sha256).unwrap();
+/// assert_eq!(bytes, claim.to_signable_bytes().unwrap());
+/// assert_eq!(hash, claim_hash(&claim).unwrap());
 /// ```
-pub fn verify_claim(signed_claim: &SignedClaim) -> Result<bool> {
-    // 1. Decode Public Key
-    let pk_bytes = hex::decode(&signed_claim.public_key)
-        .map_err(|e| SdkError::KeyError(format!("Invalid Hex Public Key: {}", e)))?;
-    let pk = VerifyingKey::from_bytes(
-        pk_bytes
-            .as_slice()
-            .try_into()
-            .map_err(|_| SdkError::KeyError("Invalid Key Length".into()))?,
-    )?;
+pub fn canonicalize_and_hash<T: Serialize>(
+    claim: &Claim<T>,
+    alg: HashAlg,
+) -> Result<(Vec<u8>, String)> {
+    let bytes = claim.to_signable_bytes()?;
+    let hash = compute_hash_multi(&[&bytes], alg);
+    Ok((bytes, hash))
+}
 
-    // 2. Decode Signature
-    let sig_bytes = hex::decode(&signed_claim.signature)
-        .map_err(|e| SdkError::KeyError(format!("Invalid Hex Signature: {}", e)))?;
-    let sig = Signature::from_bytes(
-        sig_bytes
-            .as_slice()
-            .try_into()
-            .map_err(|_| SdkError::KeyError("Invalid Signature Length".into()))?,
-    );
+/// Compute the SHA-256 hash of a `SignedClaim` envelope (Hex encoded).
+///
+/// Unlike [`claim_hash`], this also covers the public key and signature, so
+/// it identifies one specific signature over a claim (used for anchoring and
+/// caching). The envelope is hashed in its canonical form
+/// ([`SignedClaim::to_canonical_bytes`]), so the hash does not depend on how
+/// the struct happens to be serialized.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, signed_claim_hash, generate_keypair};
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// assert_eq!(signed_claim_hash(&signed).unwrap().len(), 64);
+/// ```
+pub fn signed_claim_hash<T: Serialize>(signed_claim: &SignedClaim<T>) -> Result<String> {
+    Ok(compute_hash(&signed_claim.to_canonical_bytes()?))
+}
 
-    // 3. Reconstruct Signable Bytes
-    let msg_bytes = signed_claim.claim.to_signable_bytes()?;
+/// Number of leading zero bits of a claim's canonical SHA-256 hash.
+fn pow_zero_bits<T: Serialize>(claim: &Claim<T>) -> Result<u32> {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(claim.to_signable_bytes()?);
+    let mut bits = 0;
+    for byte in digest {
+        bits += byte.leading_zeros();
+        if byte != 0 {
+            break;
+        }
+    }
+    Ok(bits)
+}
 
-    // 4. Verify
-    pk.verify(&msg_bytes, &sig)?;
+/// Find a proof-of-work nonce for a claim.
+///
+/// The algorithm is SHA-256 over the claim's canonical bytes, which include
+/// the `pow` field. Nonces are decimal strings tried from `"0"` upwards until
+/// the hash has at least `difficulty` leading zero bits or `max_attempts`
+/// nonces have been tried, so the expected cost is `2^difficulty` hashes
+/// while verification costs a single hash. The difficulty must be at most
+/// 256.
+///
+/// Mine *before* signing: the nonce is covered by the signature.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, mine_claim, sign_claim, verify_pow, generate_keypair};
+/// let claim = mine_claim(Claim::new("Spam-resistant claim".to_string()), 8, 1 << 16).unwrap();
+/// let signed = sign_claim(&claim, &generate_keypair()).unwrap();
+/// assert!(verify_pow(&signed, 8));
+/// assert!(mine_claim(Claim::new("Too hard".to_string()), 64, 10).is_err());
+/// ```
+pub fn mine_claim<T: Serialize>(
+    mut claim: Claim<T>,
+    difficulty: u32,
+    max_attempts: u64,
+) -> Result<Claim<T>> {
+    if difficulty > 256 {
+        return Err(SdkError::SerializationError(format!(
+            "proof-of-work difficulty {} exceeds 256 bits",
+            difficulty
+        )));
+    }
+    for nonce in 0..max_attempts {
+        claim.pow = Some(nonce.to_string());
+        if pow_zero_bits(&claim)? >= difficulty {
+            return Ok(claim);
+        }
+    }
+    Err(SdkError::SerializationError(format!(
+        "no {}-bit proof-of-work found in {} attempts",
+        difficulty, max_attempts
+    )))
+}
 
-    Ok(true)
+/// Check that a signed claim carries a proof-of-work of at least `difficulty` bits.
+///
+/// This only checks the work; use [`verify_claim`] to check the signature.
+pub fn verify_pow<T: Serialize>(signed_claim: &SignedClaim<T>, difficulty: u32) -> bool {
+    signed_claim.claim.pow.is_some()
+        && pow_zero_bits(&signed_claim.claim).is_ok_and(|bits| bits >= difficulty)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Generate a new random keypair (requires "std" for entropy)
+///
+/// # Example
+/// ```
+/// use provn_sdk::generate_keypair;
+/// let key = generate_keypair();
+/// ```
+#[cfg(feature = "std")]
+pub fn generate_keypair() -> SigningKey {
+    use rand::rngs::OsRng;
+    SigningKey::generate(&mut OsRng)
+}
+
+/// Derive a keypair from a 32-byte Ed25519 seed
+///
+/// The same seed always yields the same key, independent of any RNG, which
+/// makes it suitable for reproducible tests and for keys stored as raw seeds.
+///
+/// # Example
+/// ```
+/// use provn_sdk::keypair_from_seed;
+/// let key = keypair_from_seed(&[0u8; 32]);
+/// assert_eq!(
+///     hex::encode(key.verifying_key().as_bytes()),
+///     "3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29"
+/// );
+/// ```
+pub fn keypair_from_seed(seed: &[u8; SECRET_KEY_BYTES]) -> SigningKey {
+    SigningKey::from_bytes(seed)
+}
+
+/// The public key of `key` (Hex encoded), as stored in [`SignedClaim::public_key`]
+///
+/// # Example
+/// ```
+/// use provn_sdk::{keypair_from_seed, public_key_hex};
+/// let key = keypair_from_seed(&[0u8; 32]);
+/// assert_eq!(
+///     public_key_hex(&key),
+///     "3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29"
+/// );
+/// ```
+pub fn public_key_hex(key: &SigningKey) -> String {
+    hex::encode(key.verifying_key().as_bytes())
+}
+
+/// The public key of `key` (Base58 encoded, see [`Encoding::Base58`])
+///
+/// # Example
+/// ```
+/// use provn_sdk::{keypair_from_seed, public_key_base58};
+/// let key = keypair_from_seed(&[0u8; 32]);
+/// assert_eq!(public_key_base58(&key), "4zvwRjXUKGfvwnParsHAS3HuSVzV5cA4McphgmoCtajS");
+/// ```
+pub fn public_key_base58(key: &SigningKey) -> String {
+    encoding::base58_encode(key.verifying_key().as_bytes())
+}
+
+/// Generate a keypair whose hex public key starts with `prefix_hex` (requires "std")
+///
+/// Keys are drawn from the OS RNG until one matches or `max_attempts` keys
+/// have been tried. The cost is exponential in the prefix length: each hex
+/// nibble multiplies the expected number of attempts by 16, so a prefix of
+/// `n` characters needs about `16^n` attempts on average (4 characters is
+/// instant, 8 takes minutes, 10+ is impractical). The prefix is
+/// case-insensitive.
+///
+/// # Example
+/// ```
+/// use provn_sdk::generate_vanity_keypair;
+/// let key = generate_vanity_keypair("a", 10_000).unwrap();
+/// assert!(hex::encode(key.verifying_key().as_bytes()).starts_with('a'));
+/// assert!(generate_vanity_keypair("xyz", 10).is_err());
+/// ```
+#[cfg(feature = "std")]
+pub fn generate_vanity_keypair(prefix_hex: &str, max_attempts: u64) -> Result<SigningKey> {
+    let prefix = prefix_hex.to_ascii_lowercase();
+    if prefix.len() > PUBLIC_KEY_HEX_LEN || !prefix.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(SdkError::KeyError(format!(
+            "vanity prefix '{}' is not a hex string of at most {} characters",
+            prefix_hex, PUBLIC_KEY_HEX_LEN
+        )));
+    }
+
+    let mut hex = [0u8; PUBLIC_KEY_HEX_LEN];
+    for _ in 0..max_attempts {
+        let key = generate_keypair();
+        encode_hex_into(key.verifying_key().as_bytes(), &mut hex)?;
+        if hex.starts_with(prefix.as_bytes()) {
+            return Ok(key);
+        }
+    }
+    Err(SdkError::KeyError(format!(
+        "no public key with prefix '{}' found in {} attempts",
+        prefix, max_attempts
+    )))
+}
+
+/// Sign a claim with a private key
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let claim = Claim::new("Test Claim".to_string());
+/// let signed = sign_claim(&claim, &key).unwrap();
+/// ```
+pub fn sign_claim<T: Serialize + Clone>(
+    claim: &Claim<T>,
+    key: &SigningKey,
+) -> Result<SignedClaim<T>> {
+    let bytes = claim.to_signable_bytes()?;
+    let signature = key.sign(&bytes);
+
+    Ok(SignedClaim {
+        claim: claim.clone(),
+        public_key: public_key_hex(key),
+        signature: hex::encode(signature.to_bytes()),
+        signed_fields: None,
+        metadata_salt: None,
+        domain: None,
+    })
+}
+
+/// Sign a claim and check that the result verifies before returning it
+///
+/// Runs [`verify_claim_strict`] on the freshly signed claim, so a claim whose
+/// signature does not check out never leaves the signing path. This costs an
+/// extra verification per claim; use [`sign_claim`] where that matters.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_and_verify, generate_keypair};
+/// let signed = sign_and_verify(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// assert_eq!(signed.claim.data, "Test Claim");
+/// ```
+pub fn sign_and_verify<T: Serialize + Clone>(
+    claim: &Claim<T>,
+    key: &SigningKey,
+) -> Result<SignedClaim<T>> {
+    let signed = sign_claim(claim, key)?;
+    verify_claim_strict(&signed).map_err(|e| {
+        SdkError::SignatureError(format!("signed claim failed self-verification: {}", e))
+    })?;
+    Ok(signed)
+}
+
+/// Canonical bytes of `claim`, with `metadata` committed to through
+/// `metadata_salt` when given. When restricted to `fields`, the message is
+/// the canonical JSON array of the field names followed by the canonical
+/// object of just those fields, each of which must be present.
+fn claim_message<T: Serialize>(
+    claim: &Claim<T>,
+    fields: Option<&[String]>,
+    metadata_salt: Option<&str>,
+) -> Result<Vec<u8>> {
+    let value = match metadata_salt {
+        Some(salt) => redact::committed_value(claim, salt)?,
+        None if fields.is_none() => return claim.to_signable_bytes(),
+        None => claim.to_canonical_value()?,
+    };
+    let Some(fields) = fields else {
+        return canonicalize(&value);
+    };
+    let object = value
+        .as_object()
+        .ok_or_else(|| SdkError::SerializationError("claim is not a JSON object".to_string()))?;
+    let subset = fields
+        .iter()
+        .map(|field| {
+            let value = object.get(field).ok_or_else(|| {
+                SdkError::SerializationError(format!(
+                    "signed field '{}' is not present in the claim",
+                    field
+                ))
+            })?;
+            Ok((field.clone(), value.clone()))
+        })
+        .collect::<Result<serde_json::Map<String, serde_json::Value>>>()?;
+    // The field list is signed too, so fields cannot be added to it later
+    let mut message = canonicalize(&fields)?;
+    message.extend_from_slice(&canonicalize(&subset)?);
+    Ok(message)
+}
+
+/// Refuse a signature that covers only some of the claim's fields.
+pub(crate) fn require_whole_claim(partial: bool) -> Result<()> {
+    if partial {
+        return Err(SdkError::PolicyError(
+            "signature covers only some fields of the claim; use verify_claim_fields to accept it"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Sign only the named fields of a claim
+///
+/// The signature covers the (sorted) field names followed by the canonical
+/// JSON object made of just those fields. The names are recorded in
+/// [`SignedClaim::signed_fields`] so that verification rebuilds exactly that
+/// message. Every named field must be present in the claim.
+///
+/// **Security tradeoff:** fields left out are *not* authenticated. Anyone can
+/// change, add or remove them without invalidating the signature, so they
+/// must be treated as untrusted, advisory data. For that reason
+/// [`verify_claim`] and the other whole-claim verifiers reject these claims;
+/// use [`verify_claim_fields`].
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim_fields, verify_claim, verify_claim_fields, generate_keypair};
+/// let mut claim = Claim::new_with_timestamp("audit result".to_string(), 1);
+/// claim.metadata = Some("advisory note".to_string());
+/// let mut signed = sign_claim_fields(&claim, &generate_keypair(), &["data", "timestamp"]).unwrap();
+///
+/// signed.claim.metadata = Some("edited note".to_string());
+/// assert!(verify_claim_fields(&signed).unwrap());
+/// assert!(verify_claim(&signed).is_err());
+/// signed.claim.data = "forged".to_string();
+/// assert!(verify_claim_fields(&signed).is_err());
+/// ```
+pub fn sign_claim_fields<T: Serialize + Clone>(
+    claim: &Claim<T>,
+    key: &SigningKey,
+    signed_fields: &[&str],
+) -> Result<SignedClaim<T>> {
+    let value = claim.to_canonical_value()?;
+    let mut fields: Vec<String> = Vec::with_capacity(signed_fields.len());
+    for &field in signed_fields {
+        if value.get(field).is_none() {
+            return Err(SdkError::SerializationError(format!(
+                "signed field '{}' is not present in the claim",
+                field
+            )));
+        }
+        fields.push(field.to_string());
+    }
+    if fields.is_empty() {
+        return Err(SdkError::SerializationError(
+            "at least one field must be signed".to_string(),
+        ));
+    }
+    fields.sort();
+    fields.dedup();
+
+    let message = claim_message(claim, Some(&fields), None)?;
+    let signature = key.sign(&message);
+    Ok(SignedClaim {
+        claim: claim.clone(),
+        public_key: public_key_hex(key),
+        signature: hex::encode(signature.to_bytes()),
+        signed_fields: Some(fields),
+        metadata_salt: None,
+        domain: None,
+    })
+}
+
+/// Verify a signed claim whose signature may cover only some of its fields
+///
+/// Uses strict verification like [`verify_claim_strict`]. For a claim signed
+/// with [`sign_claim_fields`], only the fields named in
+/// [`SignedClaim::signed_fields`] are authenticated; the rest of the claim is
+/// untrusted. Claims signed in full verify as with [`verify_claim_strict`].
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim_fields, verify_claim_fields, generate_keypair};
+/// let mut signed = sign_claim_fields(&Claim::new_with_timestamp("x".to_string(), 1), &generate_keypair(), &["data"]).unwrap();
+/// signed.claim.metadata = Some("added later, not authenticated".to_string());
+/// assert!(verify_claim_fields(&signed).unwrap());
+///
+/// // The field list is signed: naming a field that was not signed fails
+/// signed.signed_fields = Some(vec!["data".to_string(), "metadata".to_string()]);
+/// assert!(verify_claim_fields(&signed).is_err());
+/// ```
+pub fn verify_claim_fields<T: Serialize>(signed_claim: &SignedClaim<T>) -> Result<bool> {
+    let msg = signed_claim.signed_message()?;
+    let pk = decode_public_key(&signed_claim.public_key)?;
+    let sig = decode_signature(&signed_claim.signature)?;
+    pk.verify_strict(&msg, &sig)?;
+    Ok(true)
+}
+
+/// A policy check run on a claim before it is signed.
+///
+/// Implement this to codify claim invariants (e.g. "data must be a SHA-256
+/// hash") at the signing boundary. Closures of the form
+/// `Fn(&Claim<T>) -> Result<()>` implement it automatically.
+pub trait ClaimValidator<T = String> {
+    /// Return an error if the claim must not be signed.
+    fn validate(&self, claim: &Claim<T>) -> Result<()>;
+}
+
+impl<T, F> ClaimValidator<T> for F
+where
+    F: Fn(&Claim<T>) -> Result<()>,
+{
+    fn validate(&self, claim: &Claim<T>) -> Result<()> {
+        self(claim)
+    }
+}
+
+/// Validator rejecting claims whose `data` is empty
+///
+/// Empty strings, `null`, and empty arrays or objects are all treated as
+/// empty. Use it with [`sign_claim_validated`] to catch pipelines that
+/// failed to populate the data; plain [`sign_claim`] still signs empty claims.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, reject_empty_data, sign_claim_validated, generate_keypair};
+/// let key = generate_keypair();
+/// let empty = Claim::new_with_timestamp(String::new(), 1);
+/// let err = sign_claim_validated(&empty, &key, &reject_empty_data).unwrap_err();
+/// assert_eq!(err.to_string(), "Serialization failed: empty claim data");
+/// ```
+pub fn reject_empty_data<T: Serialize>(claim: &Claim<T>) -> Result<()> {
+    let empty = match serde_json::to_value(&claim.data)? {
+        serde_json::Value::Null => true,
+        serde_json::Value::String(s) => s.is_empty(),
+        serde_json::Value::Array(items) => items.is_empty(),
+        serde_json::Value::Object(map) => map.is_empty(),
+        _ => false,
+    };
+    if empty {
+        return Err(SdkError::SerializationError("empty claim data".to_string()));
+    }
+    Ok(())
+}
+
+/// Sign a claim after it passes a validator
+///
+/// If validation fails the error is returned and nothing is signed.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, SdkError, sign_claim_validated, generate_keypair};
+/// let key = generate_keypair();
+/// let is_hash = |claim: &Claim| {
+///     if claim.data.len() == 64 && claim.data.bytes().all(|b| b.is_ascii_hexdigit()) {
+///         Ok(())
+///     } else {
+///         Err(SdkError::SerializationError("data must be a SHA-256 hex digest".into()))
+///     }
+/// };
+/// let claim = Claim::new("not a hash".to_string());
+/// assert!(sign_claim_validated(&claim, &key, &is_hash).is_err());
+/// ```
+pub fn sign_claim_validated<T, V>(
+    claim: &Claim<T>,
+    key: &SigningKey,
+    validator: &V,
+) -> Result<SignedClaim<T>>
+where
+    T: Serialize + Clone,
+    V: ClaimValidator<T> + ?Sized,
+{
+    validator.validate(claim)?;
+    sign_claim(claim, key)
+}
+
+/// Sign claim bytes that were canonicalized by another implementation
+///
+/// The bytes are parsed back into a [`Claim`] and re-canonicalized; signing
+/// only proceeds if that reproduces `canonical` exactly. Bytes with extra
+/// whitespace, unsorted keys or unknown fields are rejected, because they
+/// would not verify on other platforms.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{sign_precanonicalized, verify_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let signed = sign_precanonicalized(br#"{"data":"Test","timestamp":1}"#, &key).unwrap();
+/// assert!(verify_claim(&signed).unwrap());
+/// assert!(sign_precanonicalized(br#"{"timestamp":1,"data":"Test"}"#, &key).is_err());
+/// ```
+pub fn sign_precanonicalized(canonical: &[u8], key: &SigningKey) -> Result<SignedClaim> {
+    let claim: Claim = parse::from_slice_unique(canonical)?;
+    if claim.to_signable_bytes()? != canonical {
+        return Err(SdkError::SerializationError(
+            "bytes are not the canonical form of a claim".to_string(),
+        ));
+    }
+    let signature = key.sign(canonical);
+
+    Ok(SignedClaim {
+        claim,
+        public_key: public_key_hex(key),
+        signature: hex::encode(signature.to_bytes()),
+        signed_fields: None,
+        metadata_salt: None,
+        domain: None,
+    })
+}
+
+/// Build a signed claim from a signature produced elsewhere, such as a
+/// hardware wallet that signs bytes it is given but never exposes its key
+///
+/// The signer must have signed exactly [`Claim::to_signable_bytes`]. The
+/// result is checked with [`verify_claim_strict`] before it is returned, so
+/// a signature over anything else is an error rather than a claim that
+/// fails later.
+///
+/// # Example
+/// ```
+/// use ed25519_dalek::Signer;
+/// use provn_sdk::{Claim, assemble_signed_claim, generate_keypair};
+/// let device = generate_keypair(); // stands in for the wallet
+/// let claim = Claim::new_with_timestamp("Approve payout 17".to_string(), 1);
+///
+/// // Send the canonical bytes to the device and read back its signature
+/// let signature = device.sign(&claim.to_signable_bytes().unwrap()).to_bytes();
+/// let public_key = device.verifying_key().to_bytes();
+///
+/// let signed = assemble_signed_claim(&claim, &public_key, &signature).unwrap();
+/// assert!(assemble_signed_claim(&claim, &public_key, &[0u8; 64]).is_err());
+/// ```
+pub fn assemble_signed_claim<T: Serialize + Clone>(
+    claim: &Claim<T>,
+    public_key: &[u8; PUBLIC_KEY_BYTES],
+    signature: &[u8; SIGNATURE_BYTES],
+) -> Result<SignedClaim<T>> {
+    let signed = SignedClaim {
+        claim: claim.clone(),
+        public_key: hex::encode(public_key),
+        signature: hex::encode(signature),
+        signed_fields: None,
+        metadata_salt: None,
+        domain: None,
+    };
+    verify_claim_strict(&signed)?;
+    Ok(signed)
+}
+
+/// Verify a signed claim
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let claim = Claim::new("Test Claim".to_string());
+/// let signed = sign_claim(&claim, &key).unwrap();
+/// assert!(verify_claim(&signed).unwrap());
+/// ```
+pub fn verify_claim<T: Serialize>(signed_claim: &SignedClaim<T>) -> Result<bool> {
+    verify_signature_recorded(
+        signed_claim.whole_message(),
+        &signed_claim.public_key,
+        &signed_claim.signature,
+        false,
+    )?;
+    Ok(true)
+}
+
+/// Verify a signed claim with Ed25519 strict verification
+///
+/// Unlike [`verify_claim`], this rejects malleable (non-canonical) signatures
+/// and small-order public keys.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_claim_strict, generate_keypair};
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// assert!(verify_claim_strict(&signed).unwrap());
+/// ```
+pub fn verify_claim_strict<T: Serialize>(signed_claim: &SignedClaim<T>) -> Result<bool> {
+    verify_signature_recorded(
+        signed_claim.whole_message(),
+        &signed_claim.public_key,
+        &signed_claim.signature,
+        true,
+    )?;
+    Ok(true)
+}
+
+/// Verify a raw Ed25519 signature over `msg`, with no encoding or JSON involved
+///
+/// Uses strict verification like [`verify_claim_strict`], rejecting
+/// malleable signatures and small-order public keys. `msg` is used as is:
+/// to check a claim, pass its canonical bytes (see
+/// [`SignedClaim::signed_message`]).
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_raw, generate_keypair};
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// let msg = signed.signed_message().unwrap();
+/// let (pk, sig) = (signed.public_key_bytes().unwrap(), signed.signature_bytes().unwrap());
+/// assert!(verify_raw(&msg, &pk, &sig).unwrap());
+/// assert!(verify_raw(b"other message", &pk, &sig).is_err());
+/// ```
+pub fn verify_raw(
+    msg: &[u8],
+    pk: &[u8; PUBLIC_KEY_BYTES],
+    sig: &[u8; SIGNATURE_BYTES],
+) -> Result<bool> {
+    let pk = VerifyingKey::from_bytes(pk)?;
+    pk.verify_strict(msg, &Signature::from_bytes(sig))?;
+    Ok(true)
+}
+
+/// Verify a signed claim, ignoring whitespace around its key and signature
+///
+/// Hex copied out of a UI or terminal often picks up a trailing newline or
+/// leading spaces. This variant trims surrounding ASCII whitespace from
+/// `public_key` and `signature` before decoding; whitespace inside either
+/// value is still an error, and the claim itself is verified unchanged.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_claim, verify_claim_lenient, generate_keypair};
+/// let mut signed = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// signed.signature = format!("  {}\n", signed.signature);
+/// assert!(verify_claim(&signed).is_err());
+/// assert!(verify_claim_lenient(&signed).unwrap());
+/// ```
+pub fn verify_claim_lenient<T: Serialize>(signed_claim: &SignedClaim<T>) -> Result<bool> {
+    verify_signature_recorded(
+        signed_claim.whole_message(),
+        signed_claim.public_key.trim_ascii(),
+        signed_claim.signature.trim_ascii(),
+        false,
+    )?;
+    Ok(true)
+}
+
+/// Length of a hex encoded Ed25519 public key.
+const PUBLIC_KEY_HEX_LEN: usize = 64;
+/// Length of a hex encoded Ed25519 signature.
+const SIGNATURE_HEX_LEN: usize = 128;
+
+/// Compute the byte size of the JSON `SignedClaim` that signing `claim` would produce.
+///
+/// The public key and signature are always hex encoded at a fixed width
+/// (64 and 128 characters), so the result is exact and can be used to budget
+/// Arweave/Solana storage before any key is involved.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, estimated_serialized_size, sign_claim, generate_keypair};
+/// let claim = Claim::new("Test Claim".to_string());
+/// let size = estimated_serialized_size(&claim).unwrap();
+/// let signed = sign_claim(&claim, &generate_keypair()).unwrap();
+/// assert_eq!(size, serde_json::to_string(&signed).unwrap().len());
+/// ```
+pub fn estimated_serialized_size<T: Serialize>(claim: &Claim<T>) -> Result<usize> {
+    // {"claim":<claim>,"public_key":"<hex>","signature":"<hex>"}
+    const ENVELOPE: usize = r#"{"claim":,"public_key":"","signature":""}"#.len();
+    let claim_len = claim.to_signable_bytes()?.len();
+    Ok(ENVELOPE + claim_len + PUBLIC_KEY_HEX_LEN + SIGNATURE_HEX_LEN)
+}
+
+/// Verify a signed claim received in its compact representation
+///
+/// The signature is verified strictly, as [`verify_claim_strict`] does.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, CompactSignedClaim, sign_claim, verify_compact_claim, generate_keypair};
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &generate_keypair()).unwrap();
+/// assert!(verify_compact_claim(&CompactSignedClaim::from(signed)).unwrap());
+/// ```
+pub fn verify_compact_claim<T: Serialize>(compact: &CompactSignedClaim<T>) -> Result<bool> {
+    require_whole_claim(compact.signed_fields.is_some())?;
+    let msg_bytes = claim_message(
+        &compact.claim,
+        compact.signed_fields.as_deref(),
+        compact.metadata_salt.as_deref(),
+    )
+    .and_then(|msg_bytes| domain::with_domain(compact.domain.as_deref(), msg_bytes));
+    verify_signature_recorded(msg_bytes, &compact.public_key, &compact.signature, true)?;
+    Ok(true)
+}
+
+/// Sign a claim together with additional associated data (AAD).
+///
+/// The signed message is `canonical claim bytes || aad`: the AAD is appended
+/// after the closing `}` of the canonical JSON, with no separator. Because a
+/// canonical JSON object can never be a prefix of another one, the boundary
+/// is unambiguous. The AAD is *not* stored in the returned `SignedClaim`;
+/// verifiers must obtain it out-of-band and pass it to [`verify_claim_aad`].
+/// An empty AAD produces the same signature as [`sign_claim`].
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim_aad, verify_claim_aad, generate_keypair};
+/// let key = generate_keypair();
+/// let signed = sign_claim_aad(&Claim::new("Test Claim".to_string()), &key, b"tenant:acme").unwrap();
+/// assert!(verify_claim_aad(&signed, b"tenant:acme").unwrap());
+/// assert!(verify_claim_aad(&signed, b"tenant:other").is_err());
+/// ```
+pub fn sign_claim_aad<T: Serialize + Clone>(
+    claim: &Claim<T>,
+    key: &SigningKey,
+    aad: &[u8],
+) -> Result<SignedClaim<T>> {
+    let mut bytes = claim.to_signable_bytes()?;
+    bytes.extend_from_slice(aad);
+    let signature = key.sign(&bytes);
+
+    Ok(SignedClaim {
+        claim: claim.clone(),
+        public_key: public_key_hex(key),
+        signature: hex::encode(signature.to_bytes()),
+        signed_fields: None,
+        metadata_salt: None,
+        domain: None,
+    })
+}
+
+/// Verify a claim signed with [`sign_claim_aad`] using the same AAD
+///
+/// The signature is verified strictly, as [`verify_claim_strict`] does.
+pub fn verify_claim_aad<T: Serialize>(signed_claim: &SignedClaim<T>, aad: &[u8]) -> Result<bool> {
+    let msg_bytes = signed_claim.whole_message().map(|mut msg_bytes| {
+        msg_bytes.extend_from_slice(aad);
+        msg_bytes
+    });
+    verify_signature_recorded(
+        msg_bytes,
+        &signed_claim.public_key,
+        &signed_claim.signature,
+        true,
+    )?;
+    Ok(true)
+}
+
+/// Countersign another party's signed claim (notarization).
+///
+/// The inner `SignedClaim` is serialized as canonical JSON (RFC 8785) into
+/// the `data` of a new outer claim made at `timestamp`, which is then signed by the notary.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, sign_notarization, verify_notarization, generate_keypair};
+/// let author = generate_keypair();
+/// let notary = generate_keypair();
+/// let inner = sign_claim(&Claim::new("Original statement".to_string()), &author).unwrap();
+/// let notarized = sign_notarization(&inner, &notary, 1700000000).unwrap();
+/// assert!(verify_notarization(&notarized).unwrap());
+/// ```
+pub fn sign_notarization<T: Serialize>(
+    inner: &SignedClaim<T>,
+    key: &SigningKey,
+    timestamp: u64,
+) -> Result<SignedClaim> {
+    let data = String::from_utf8(canonicalize(inner)?)
+        .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+    sign_claim(&Claim::new_with_timestamp(data, timestamp), key)
+}
+
+/// Verify a notarization produced by [`sign_notarization`]
+///
+/// Both the notary's outer signature and the original inner signature must
+/// be valid; tampering with either layer fails.
+pub fn verify_notarization(outer: &SignedClaim) -> Result<bool> {
+    verify_claim(outer)?;
+    let inner: SignedClaim<serde_json::Value> =
+        parse::from_slice_unique(outer.claim.data.as_bytes())?;
+    verify_claim(&inner)
+}
+
+/// An ordered list of claims that is signed as a single canonical unit.
+///
+/// Unlike Merkle batching, the signature covers the exact set *and order*
+/// of the claims: reordering, adding or removing any claim invalidates it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClaimBundle {
+    /// The claims, in signing order
+    pub claims: Vec<Claim>,
+}
+
+/// A SignedBundle wraps a bundle with its signature and public key.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SignedBundle {
+    /// The original bundle
+    pub bundle: ClaimBundle,
+    /// The public key of the signer (Hex encoded)
+    pub public_key: String,
+    /// The signature of the serialized bundle (Hex encoded)
+    pub signature: String,
+}
+
+impl ClaimBundle {
+    /// Create a bundle from an ordered list of claims
+    pub fn new(claims: Vec<Claim>) -> Self {
+        Self { claims }
+    }
+
+    /// Canonical serialization for signing: `{"claims":[...]}` with every
+    /// claim canonicalized in place and the array order preserved.
+    ///
+    /// Fails if any claim's timestamp exceeds [`MAX_SAFE_TIMESTAMP`], as
+    /// [`Claim::to_signable_bytes`] does.
+    pub fn to_signable_bytes(&self) -> Result<Vec<u8>> {
+        for claim in &self.claims {
+            claim.check_timestamp()?;
+        }
+        canonicalize(self)
+    }
+}
+
+/// Sign an ordered bundle of claims with a single signature
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, ClaimBundle, sign_bundle, verify_bundle, generate_keypair};
+/// let key = generate_keypair();
+/// let bundle = ClaimBundle::new(vec![
+///     Claim::new_with_timestamp("first".to_string(), 1),
+///     Claim::new_with_timestamp("second".to_string(), 2),
+/// ]);
+/// let signed = sign_bundle(&bundle, &key).unwrap();
+/// assert!(verify_bundle(&signed).unwrap());
+/// ```
+pub fn sign_bundle(bundle: &ClaimBundle, key: &SigningKey) -> Result<SignedBundle> {
+    let bytes = bundle.to_signable_bytes()?;
+    let signature = key.sign(&bytes);
+
+    Ok(SignedBundle {
+        bundle: bundle.clone(),
+        public_key: public_key_hex(key),
+        signature: hex::encode(signature.to_bytes()),
+    })
+}
+
+/// Verify a signed bundle
+///
+/// Fails if any claim was altered or if the claims were reordered. The
+/// signature is verified strictly, as [`verify_claim_strict`] does.
+pub fn verify_bundle(signed_bundle: &SignedBundle) -> Result<bool> {
+    verify_signature_strict(
+        &signed_bundle.bundle.to_signable_bytes()?,
+        &signed_bundle.public_key,
+        &signed_bundle.signature,
+    )?;
+    Ok(true)
+}
+
+/// Decode hex as emitted by a wide range of tools.
+///
+/// An optional `0x`/`0X` prefix (as produced by EVM tooling) is stripped and
+/// upper, lower or mixed case digits are accepted. Any other non-hex character
+/// is still rejected.
+pub(crate) fn decode_hex(input: &str) -> core::result::Result<Vec<u8>, hex::FromHexError> {
+    hex::decode(split_hex_prefix(input).0)
+}
+
+/// Strip an optional `0x`/`0X` prefix, returning the digits and the prefix length.
+fn split_hex_prefix(input: &str) -> (&str, usize) {
+    match input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        Some(digits) => (digits, 2),
+        None => (input, 0),
+    }
+}
+
+/// Decode exactly `N` bytes of hex with precise diagnostics.
+///
+/// Errors name the field, the position (in characters, counted from the
+/// start of `input` including any `0x` prefix) of the first invalid
+/// character, and the expected versus actual number of hex digits, e.g.
+/// `Invalid Hex Public Key: invalid hex at position 41; expected 64 chars, got 63`.
+/// Well-formed hex of the wrong length reports the decoded length in bytes,
+/// e.g. `Invalid Hex Signature: length 63 bytes, expected 64`.
+pub(crate) fn decode_hex_array<const N: usize>(input: &str, field: &str) -> Result<[u8; N]> {
+    let (digits, offset) = split_hex_prefix(input);
+    let expected = N * 2;
+    let actual = digits.chars().count();
+
+    if let Some(position) = digits.chars().position(|c| !c.is_ascii_hexdigit()) {
+        return Err(SdkError::KeyError(format!(
+            "Invalid Hex {}: invalid hex at position {}; expected {} chars, got {}",
+            field,
+            position + offset,
+            expected,
+            actual
+        )));
+    }
+    if actual % 2 == 1 {
+        return Err(SdkError::KeyError(format!(
+            "Invalid Hex {}: odd number of hex digits ({}), expected {}",
+            field, actual, expected
+        )));
+    }
+    if actual != expected {
+        return Err(SdkError::KeyError(format!(
+            "Invalid Hex {}: length {} bytes, expected {}",
+            field,
+            actual / 2,
+            N
+        )));
+    }
+
+    let mut out = [0u8; N];
+    hex::decode_to_slice(digits, &mut out)
+        .map_err(|e| SdkError::KeyError(format!("Invalid Hex {}: {}", field, e)))?;
+    Ok(out)
+}
+
+/// Decode a hex encoded Ed25519 public key into its raw bytes.
+pub(crate) fn decode_public_key_bytes(public_key: &str) -> Result<[u8; PUBLIC_KEY_BYTES]> {
+    decode_hex_array(public_key, "Public Key")
+}
+
+/// Decode a hex encoded Ed25519 signature into its raw bytes.
+pub(crate) fn decode_signature_bytes(signature: &str) -> Result<[u8; SIGNATURE_BYTES]> {
+    decode_hex_array(signature, "Signature")
+}
+
+/// Decode a hex encoded Ed25519 public key.
+pub(crate) fn decode_public_key(public_key: &str) -> Result<VerifyingKey> {
+    Ok(VerifyingKey::from_bytes(&decode_public_key_bytes(
+        public_key,
+    )?)?)
+}
+
+/// Decode a hex encoded Ed25519 signature.
+fn decode_signature(signature: &str) -> Result<Signature> {
+    Ok(Signature::from_bytes(&decode_signature_bytes(signature)?))
+}
+
+/// Decode a hex public key and signature and verify them over `msg`.
+pub(crate) fn verify_signature(msg: &[u8], public_key: &str, signature: &str) -> Result<()> {
+    // 1. Decode Public Key
+    let pk = decode_public_key(public_key)?;
+
+    // 2. Decode Signature
+    let sig = decode_signature(signature)?;
+
+    // 3. Verify
+    pk.verify(msg, &sig)?;
+
+    Ok(())
+}
+
+/// Like [`verify_signature`], rejecting small-order keys and non-canonical
+/// signatures as [`verify_claim_strict`] does.
+pub(crate) fn verify_signature_strict(msg: &[u8], public_key: &str, signature: &str) -> Result<()> {
+    let pk = decode_public_key(public_key)?;
+    let sig = decode_signature(signature)?;
+    pk.verify_strict(msg, &sig)?;
+    Ok(())
+}
+
+/// Like [`verify_signature`], optionally with strict verification, and
+/// counting the outcome for the `metrics` feature.
+fn verify_signature_recorded(
+    msg: Result<Vec<u8>>,
+    public_key: &str,
+    signature: &str,
+    strict: bool,
+) -> Result<()> {
+    let decoded = msg.and_then(|msg| {
+        Ok((
+            msg,
+            decode_public_key(public_key)?,
+            decode_signature(signature)?,
+        ))
+    });
+    let (msg, pk, sig) =
+        decoded.inspect_err(|_| metrics::record(metrics::VerifyMetric::DecodeError))?;
+    let verified = if strict {
+        pk.verify_strict(&msg, &sig)
+    } else {
+        pk.verify(&msg, &sig)
+    };
+    verified.inspect_err(|_| metrics::record(metrics::VerifyMetric::Tampered))?;
+    metrics::record(metrics::VerifyMetric::Valid);
+    Ok(())
+}
+
+/// Public key derived from the all-zeros seed `[0u8; 32]`, a well-known test key.
+const ZERO_SEED_PUBLIC_KEY: [u8; 32] = [
+    0x3b, 0x6a, 0x27, 0xbc, 0xce, 0xb6, 0xa4, 0x2d, 0x62, 0xa3, 0xa8, 0xd0, 0x2a, 0x6f, 0x0d, 0x73,
+    0x65, 0x32, 0x15, 0x77, 0x1d, 0xe2, 0x43, 0xa6, 0x3a, 0xc0, 0x48, 0xa1, 0x8b, 0x59, 0xda, 0x29,
+];
+
+/// Check whether a public key is a known weak or test key.
+///
+/// The following keys are classified as weak:
+/// - Small-order points (including the all-zeros and identity encodings),
+///   for which a forged signature can verify against many messages.
+/// - The public key of the all-zeros seed `[0u8; 32]`, which is used in test
+///   suites (including this crate's) and must never sign production claims.
+///
+/// # Example
+/// ```
+/// use ed25519_dalek::SigningKey;
+/// use provn_sdk::{generate_keypair, is_weak_key};
+/// assert!(is_weak_key(&SigningKey::from_bytes(&[0u8; 32]).verifying_key()));
+/// assert!(!is_weak_key(&generate_keypair().verifying_key()));
+/// ```
+pub fn is_weak_key(pk: &VerifyingKey) -> bool {
+    pk.is_weak() || pk.as_bytes() == &ZERO_SEED_PUBLIC_KEY
+}
+
+/// Options controlling the checks performed by [`verify_claim_with_options`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VerifyOptions {
+    /// Reject claims signed by a key classified as weak by [`is_weak_key`]
+    pub reject_weak_keys: bool,
+}
+
+/// Verify a signed claim with additional checks
+///
+/// With default options this is equivalent to [`verify_claim`].
+///
+/// # Example
+/// ```
+/// use ed25519_dalek::SigningKey;
+/// use provn_sdk::{Claim, sign_claim, verify_claim_with_options, VerifyOptions};
+/// let test_key = SigningKey::from_bytes(&[0u8; 32]);
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &test_key).unwrap();
+/// let options = VerifyOptions { reject_weak_keys: true };
+/// assert!(verify_claim_with_options(&signed, &options).is_err());
+/// ```
+pub fn verify_claim_with_options<T: Serialize>(
+    signed_claim: &SignedClaim<T>,
+    options: &VerifyOptions,
+) -> Result<bool> {
+    if options.reject_weak_keys && is_weak_key(&decode_public_key(&signed_claim.public_key)?) {
+        return Err(SdkError::KeyError("Weak Public Key Rejected".into()));
+    }
+    verify_claim(signed_claim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_sign_verify_flow() {
@@ -212,11 +2079,58 @@ mod tests {
         assert!(valid);
     }
 
+    #[test]
+    fn test_assemble_signed_claim() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let public_key = key.verifying_key().to_bytes();
+        let signature = key.sign(&claim.to_signable_bytes().unwrap()).to_bytes();
+        assert_eq!(
+            assemble_signed_claim(&claim, &public_key, &signature).unwrap(),
+            sign_claim(&claim, &key).unwrap()
+        );
+
+        // A signature over other bytes, or by another key, is rejected
+        let other = key.sign(b"x").to_bytes();
+        assert!(matches!(
+            assemble_signed_claim(&claim, &public_key, &other),
+            Err(SdkError::SignatureError(_))
+        ));
+        let stranger = SigningKey::from_bytes(&[1u8; 32])
+            .verifying_key()
+            .to_bytes();
+        assert!(assemble_signed_claim(&claim, &stranger, &signature).is_err());
+    }
+
+    #[test]
+    fn test_metadata_json_is_canonical() {
+        let value: serde_json::Value = serde_json::from_str(
+            "{ \"b\": [1.0, {\"z\": null, \"a\": \"\\u00e9\"}], \"a\": true }",
+        )
+        .unwrap();
+        let claim = Claim::new_with_timestamp("x".to_string(), 1)
+            .metadata_json(value)
+            .unwrap();
+        let metadata = claim.metadata.clone().unwrap();
+        assert_eq!(metadata, r#"{"a":true,"b":[1,{"a":"é","z":null}]}"#);
+        let reparsed: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+        let again = Claim::new_with_timestamp("x".to_string(), 1)
+            .metadata_json(reparsed)
+            .unwrap();
+        assert_eq!(again.metadata.unwrap(), metadata);
+    }
+
     #[test]
     fn test_canonical_json_order() {
         let claim = Claim {
             data: "test".to_string(),
+            attachments: None,
+            expires_at: None,
+            kind: None,
             metadata: Some("meta".to_string()),
+            metadata_hash: None,
+            pow: None,
+            salt: None,
             timestamp: 123,
         };
         let json = serde_json::to_string(&claim).unwrap();
@@ -237,4 +2151,779 @@ mod tests {
         let result = verify_claim(&signed);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_bundle_reorder_detection() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let bundle = ClaimBundle::new(vec![
+            Claim::new_with_timestamp("first".to_string(), 1),
+            Claim::new_with_timestamp("second".to_string(), 2),
+        ]);
+
+        let mut signed = sign_bundle(&bundle, &key).expect("Sign failed");
+        assert!(verify_bundle(&signed).expect("Verify failed"));
+
+        // Swap the order of the claims
+        signed.bundle.claims.reverse();
+
+        assert!(verify_bundle(&signed).is_err());
+    }
+
+    #[test]
+    fn test_bundle_bytes_are_canonical() {
+        let first = Claim::new_with_timestamp("first".to_string(), 1);
+        let second = Claim::new_with_timestamp("second".to_string(), 2);
+        let bundle = ClaimBundle::new(vec![first.clone(), second.clone()]);
+        let mut expected = b"{\"claims\":[".to_vec();
+        expected.extend(first.to_signable_bytes().unwrap());
+        expected.push(b',');
+        expected.extend(second.to_signable_bytes().unwrap());
+        expected.extend(b"]}");
+        assert_eq!(bundle.to_signable_bytes().unwrap(), expected);
+
+        let over = ClaimBundle::new(vec![
+            first,
+            Claim::new_with_timestamp("late".to_string(), MAX_SAFE_TIMESTAMP + 1),
+        ]);
+        assert!(matches!(
+            over.to_signable_bytes(),
+            Err(SdkError::SerializationError(_))
+        ));
+        assert!(sign_bundle(&over, &SigningKey::from_bytes(&[0u8; 32])).is_err());
+
+        // A small-order key and signature that lenient verification accepts
+        let forged = SignedBundle {
+            bundle,
+            public_key: format!("01{}", "00".repeat(31)),
+            signature: format!("01{}", "00".repeat(63)),
+        };
+        assert!(verify_signature(
+            &forged.bundle.to_signable_bytes().unwrap(),
+            &forged.public_key,
+            &forged.signature
+        )
+        .is_ok());
+        assert!(verify_bundle(&forged).is_err());
+    }
+
+    #[test]
+    fn test_verify_accepts_prefixed_uppercase_hex() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("Hello World".to_string(), 123456789);
+
+        let mut signed = sign_claim(&claim, &key).expect("Sign failed");
+        signed.public_key = format!("0x{}", signed.public_key.to_uppercase());
+        signed.signature = format!("0X{}", signed.signature.to_uppercase());
+        assert!(verify_claim(&signed).expect("Verify failed"));
+
+        // Characters that are not hex digits are still rejected
+        signed.signature = format!("0xZZ{}", &signed.signature[4..]);
+        assert!(matches!(verify_claim(&signed), Err(SdkError::KeyError(_))));
+    }
+
+    #[test]
+    fn test_estimated_serialized_size_matches() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claim = Claim::new_with_timestamp("Size \"quoted\" data".to_string(), 42);
+        claim.metadata = Some("meta".to_string());
+
+        let signed = sign_claim(&claim, &key).expect("Sign failed");
+        let actual = serde_json::to_string(&signed).unwrap().len();
+        assert_eq!(estimated_serialized_size(&claim).unwrap(), actual);
+    }
+
+    #[test]
+    fn test_typed_claim_sign_verify() {
+        #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+        struct AuditRecord {
+            action: String,
+            actor: String,
+        }
+
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let record = AuditRecord {
+            action: "deploy".to_string(),
+            actor: "ci".to_string(),
+        };
+        let claim = Claim::new_with_timestamp(record, 123);
+        assert_eq!(
+            claim.to_signable_bytes().unwrap(),
+            br#"{"data":{"action":"deploy","actor":"ci"},"timestamp":123}"#
+        );
+
+        let mut signed = sign_claim(&claim, &key).expect("Sign failed");
+        assert!(verify_claim(&signed).expect("Verify failed"));
+
+        signed.claim.data.actor = "mallory".to_string();
+        assert!(verify_claim(&signed).is_err());
+    }
+
+    #[test]
+    fn test_weak_key_rejection() {
+        let test_key = SigningKey::from_bytes(&[0u8; 32]);
+        assert_eq!(test_key.verifying_key().as_bytes(), &ZERO_SEED_PUBLIC_KEY);
+
+        // The all-zeros encoding is a small-order point
+        let zero_point = VerifyingKey::from_bytes(&[0u8; 32]).unwrap();
+        assert!(is_weak_key(&zero_point));
+        assert!(!is_weak_key(
+            &SigningKey::from_bytes(&[7u8; 32]).verifying_key()
+        ));
+
+        let claim = Claim::new_with_timestamp("Test".to_string(), 1);
+        let signed = sign_claim(&claim, &test_key).unwrap();
+        let strict = VerifyOptions {
+            reject_weak_keys: true,
+        };
+        assert!(verify_claim_with_options(&signed, &VerifyOptions::default()).unwrap());
+        assert!(matches!(
+            verify_claim_with_options(&signed, &strict),
+            Err(SdkError::KeyError(_))
+        ));
+    }
+
+    #[test]
+    fn test_compact_roundtrip() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("Hello World".to_string(), 123456789);
+        let signed = sign_claim(&claim, &key).expect("Sign failed");
+
+        let full_json = serde_json::to_string(&signed).unwrap();
+        let compact_json =
+            serde_json::to_string(&CompactSignedClaim::from(signed.clone())).unwrap();
+        assert!(compact_json.len() < full_json.len());
+
+        let compact: CompactSignedClaim = serde_json::from_str(&compact_json).unwrap();
+        assert!(verify_compact_claim(&compact).expect("Verify failed"));
+
+        let restored = SignedClaim::from(compact);
+        assert_eq!(serde_json::to_string(&restored).unwrap(), full_json);
+        assert!(verify_claim(&restored).expect("Verify failed"));
+
+        let mut forged = CompactSignedClaim::from(signed);
+        forged.public_key = format!("01{}", "00".repeat(31));
+        forged.signature = format!("01{}", "00".repeat(63));
+        assert!(verify_claim(&SignedClaim::from(forged.clone())).unwrap());
+        assert!(matches!(
+            verify_compact_claim(&forged),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_validator_runs_before_signing() {
+        struct TimestampRange {
+            min: u64,
+            max: u64,
+        }
+
+        impl ClaimValidator for TimestampRange {
+            fn validate(&self, claim: &Claim) -> Result<()> {
+                if claim.timestamp < self.min || claim.timestamp > self.max {
+                    return Err(SdkError::SerializationError(
+                        "timestamp out of range".into(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let validator = TimestampRange { min: 100, max: 200 };
+
+        let ok = Claim::new_with_timestamp("in range".to_string(), 150);
+        let signed = sign_claim_validated(&ok, &key, &validator).expect("Sign failed");
+        assert!(verify_claim(&signed).unwrap());
+
+        let late = Claim::new_with_timestamp("too late".to_string(), 201);
+        assert!(matches!(
+            sign_claim_validated(&late, &key, &validator),
+            Err(SdkError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_raw_byte_accessors() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("Hello World".to_string(), 123456789);
+        let mut signed = sign_claim(&claim, &key).expect("Sign failed");
+
+        assert_eq!(
+            &signed.public_key_bytes().unwrap(),
+            key.verifying_key().as_bytes()
+        );
+        let sig = signed.signature_bytes().unwrap();
+        assert!(key
+            .verifying_key()
+            .verify(
+                &claim.to_signable_bytes().unwrap(),
+                &Signature::from_bytes(&sig)
+            )
+            .is_ok());
+
+        signed.signature.truncate(126);
+        assert!(matches!(
+            signed.signature_bytes(),
+            Err(SdkError::KeyError(_))
+        ));
+    }
+
+    #[test]
+    fn test_timestamp_byte_layout() {
+        // 1700000000 = 0x6553F100
+        assert_eq!(
+            timestamp_to_le_bytes(1700000000),
+            [0x00, 0xf1, 0x53, 0x65, 0x00, 0x00, 0x00, 0x00]
+        );
+        for ts in [0, 1, 1700000000, u64::MAX] {
+            assert_eq!(
+                timestamp_from_le_bytes(&timestamp_to_le_bytes(ts)).unwrap(),
+                ts
+            );
+        }
+        assert!(timestamp_from_le_bytes(&[0u8; 7]).is_err());
+    }
+
+    #[test]
+    fn test_pow_is_signed() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = mine_claim(
+            Claim::new_with_timestamp("ingest".to_string(), 1),
+            12,
+            u64::MAX,
+        )
+        .unwrap();
+        assert!(pow_zero_bits(&claim).unwrap() >= 12);
+
+        // The first qualifying nonce is out of reach of a smaller budget
+        let nonce: u64 = claim.pow.as_deref().unwrap().parse().unwrap();
+        let unmined = Claim::new_with_timestamp("ingest".to_string(), 1);
+        assert!(mine_claim(unmined.clone(), 12, nonce).is_err());
+        assert_eq!(mine_claim(unmined, 12, nonce + 1).unwrap(), claim);
+
+        let mut signed = sign_claim(&claim, &key).expect("Sign failed");
+        assert!(verify_pow(&signed, 12));
+        assert!(verify_claim(&signed).unwrap());
+
+        // Claims without a nonce never pass, even at difficulty 0
+        let plain = sign_claim(&Claim::new_with_timestamp("x".to_string(), 1), &key).unwrap();
+        assert!(!verify_pow(&plain, 0));
+
+        // Swapping the nonce breaks the signature
+        signed.claim.pow = Some("0".to_string());
+        assert!(verify_claim(&signed).is_err());
+        assert!(mine_claim(claim, 257, u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_notarization_layers() {
+        let author = SigningKey::from_bytes(&[1u8; 32]);
+        let notary = SigningKey::from_bytes(&[2u8; 32]);
+        let inner = sign_claim(
+            &Claim::new_with_timestamp("statement".to_string(), 10),
+            &author,
+        )
+        .expect("Sign failed");
+        let outer = sign_notarization(&inner, &notary, 20).expect("Notarize failed");
+        assert!(verify_notarization(&outer).unwrap());
+
+        // Tampering with the outer layer
+        let mut tampered = outer.clone();
+        tampered.claim.timestamp = 21;
+        assert!(verify_notarization(&tampered).is_err());
+
+        // Re-signing a tampered inner layer passes the outer check only
+        let mut forged_inner = inner.clone();
+        forged_inner.claim.data = "forged".to_string();
+        let forged = sign_notarization(&forged_inner, &notary, 20).unwrap();
+        assert!(verify_claim(&forged).unwrap());
+        assert!(verify_notarization(&forged).is_err());
+    }
+
+    #[test]
+    fn test_notarization_embeds_canonical_json() {
+        let author = SigningKey::from_bytes(&[0u8; 32]);
+        let notary = SigningKey::from_bytes(&[2u8; 32]);
+        // serde_json would write the reading as `1e21`
+        let inner = sign_claim(&Claim::new_with_timestamp(1e21, 10), &author).unwrap();
+        let outer = sign_notarization(&inner, &notary, 20).unwrap();
+        assert_eq!(
+            outer.claim.data,
+            format!(
+                r#"{{"claim":{{"data":1e+21,"timestamp":10}},"public_key":"{}","signature":"{}"}}"#,
+                inner.public_key, inner.signature
+            )
+        );
+        assert!(verify_notarization(&outer).unwrap());
+    }
+
+    #[test]
+    fn test_verify_raw_is_strict() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("x".to_string(), 1), &key).unwrap();
+        let msg = signed.signed_message().unwrap();
+        let pk = signed.public_key_bytes().unwrap();
+        let mut sig = signed.signature_bytes().unwrap();
+        assert!(verify_raw(&msg, &pk, &sig).unwrap());
+
+        // Identity point: small order, accepted by plain verification only
+        let mut identity = [0u8; PUBLIC_KEY_BYTES];
+        identity[0] = 1;
+        assert!(verify_raw(&msg, &identity, &sig).is_err());
+        sig[0] ^= 1;
+        assert!(matches!(
+            verify_raw(&msg, &pk, &sig),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_lenient_verification_trims_only_the_ends() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("x".to_string(), 1), &key).unwrap();
+
+        let mut padded = signed.clone();
+        padded.public_key = format!("\t{}\r\n", padded.public_key);
+        padded.signature = format!(" {} ", padded.signature);
+        assert!(matches!(verify_claim(&padded), Err(SdkError::KeyError(_))));
+        assert!(verify_claim_lenient(&padded).unwrap());
+
+        let mut split = signed;
+        split.signature.insert(64, ' ');
+        assert!(matches!(
+            verify_claim_lenient(&split),
+            Err(SdkError::KeyError(_))
+        ));
+    }
+
+    #[test]
+    fn test_aad_binding() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("Hello World".to_string(), 123456789);
+        let signed = sign_claim_aad(&claim, &key, b"label").expect("Sign failed");
+
+        // The AAD is not part of the transmitted claim
+        assert!(!serde_json::to_string(&signed).unwrap().contains("label"));
+        assert!(verify_claim_aad(&signed, b"label").unwrap());
+        assert!(verify_claim_aad(&signed, b"").is_err());
+        assert!(verify_claim(&signed).is_err());
+
+        let mut forged = signed;
+        forged.public_key = format!("01{}", "00".repeat(31));
+        forged.signature = format!("01{}", "00".repeat(63));
+        assert!(verify_claim(&forged).unwrap());
+        assert!(matches!(
+            verify_claim_aad(&forged, b"label"),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_timestamp_js_safe_boundary() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let max = Claim::new_with_timestamp("edge".to_string(), MAX_SAFE_TIMESTAMP);
+        assert!(verify_claim(&sign_claim(&max, &key).unwrap()).unwrap());
+
+        let over = Claim::new_with_timestamp("edge".to_string(), MAX_SAFE_TIMESTAMP + 1);
+        assert!(matches!(
+            sign_claim(&over, &key),
+            Err(SdkError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_compute_hash_multi_algorithms() {
+        let segments: [&[u8]; 3] = [b"a", b"", b"bc"];
+        assert_eq!(
+            compute_hash_multi(&segments, HashAlg::Sha256),
+            compute_hash(b"abc")
+        );
+        assert_eq!(
+            compute_hash_multi(&segments, HashAlg::Sha384),
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+             8086072ba1e7cc2358baeca134c825a7"
+        );
+        assert_eq!(compute_hash_multi(&segments, HashAlg::Sha512).len(), 128);
+        assert_eq!(
+            serde_json::to_string(&HashAlg::Sha512).unwrap(),
+            r#""sha512""#
+        );
+    }
+
+    #[test]
+    fn test_strict_verification_rejects_small_order_keys() {
+        // Identity public key with R = identity, s = 0 verifies for any message
+        let mut forged = sign_and_verify(
+            &Claim::new_with_timestamp("any".to_string(), 1),
+            &SigningKey::from_bytes(&[0u8; 32]),
+        )
+        .unwrap();
+        forged.public_key = format!("01{}", "00".repeat(31));
+        forged.signature = format!("01{}", "00".repeat(63));
+        assert!(verify_claim(&forged).unwrap());
+        assert!(matches!(
+            verify_claim_strict(&forged),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_ots_digest_known_value() {
+        // sha256sum of the file `{"data":"Test","timestamp":1}`
+        let claim = Claim::new_with_timestamp("Test".to_string(), 1);
+        assert_eq!(
+            hex::encode(to_ots_digest(&claim).unwrap()),
+            "04f3f86985e7ff5e7df0fdc209e779d5ed3d83fe2624492b079339a784871f87"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_and_hash_matches_separate_calls() {
+        let claim = Claim::new_with_timestamp("both".to_string(), 1);
+        let (bytes, hash) = canonicalize_and_hash(&claim, HashAlg::Sha384).unwrap();
+        assert_eq!(bytes, claim.to_signable_bytes().unwrap());
+        assert_eq!(hash, compute_hash_multi(&[&bytes], HashAlg::Sha384));
+
+        let over = Claim::new_with_timestamp("x".to_string(), MAX_SAFE_TIMESTAMP + 1);
+        assert!(canonicalize_and_hash(&over, HashAlg::Sha256).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vanity_keypair_budget() {
+        let key = generate_vanity_keypair("AB", 100_000).expect("prefix not found");
+        assert!(hex::encode(key.verifying_key().as_bytes()).starts_with("ab"));
+
+        // Zero attempts never succeed
+        assert!(matches!(
+            generate_vanity_keypair("", 0),
+            Err(SdkError::KeyError(_))
+        ));
+    }
+
+    #[test]
+    fn test_hex_error_positions() {
+        let mut pk = "ab".repeat(32);
+        pk.replace_range(41..42, "g");
+        pk.pop();
+        let err = decode_public_key_bytes(&pk).unwrap_err().to_string();
+        assert!(
+            err.ends_with("invalid hex at position 41; expected 64 chars, got 63"),
+            "{}",
+            err
+        );
+
+        // Positions count the 0x prefix
+        let err = decode_public_key_bytes(&format!("0x{}", pk))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("position 43;"), "{}", err);
+
+        let err = decode_signature_bytes(&"ab".repeat(31))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "Key format error: Invalid Hex Signature: length 31 bytes, expected 64"
+        );
+        let err = decode_public_key_bytes(&"ab".repeat(33))
+            .unwrap_err()
+            .to_string();
+        assert!(err.ends_with("length 33 bytes, expected 32"), "{}", err);
+        let err = decode_signature_bytes(&"a".repeat(127))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.ends_with("odd number of hex digits (127), expected 128"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_canonical_value_matches_bytes() {
+        let mut claim = Claim::new_with_timestamp("value".to_string(), 99);
+        claim.metadata = Some("meta".to_string());
+        let value = claim.to_canonical_value().unwrap();
+        let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["data", "metadata", "timestamp"]);
+        assert_eq!(
+            serde_json::to_vec(&value).unwrap(),
+            claim.to_signable_bytes().unwrap()
+        );
+
+        claim.timestamp = MAX_SAFE_TIMESTAMP + 1;
+        assert!(claim.to_canonical_value().is_err());
+    }
+
+    #[test]
+    fn test_claim_set_and_map_dedup() {
+        use alloc::collections::BTreeMap;
+        use std::collections::HashSet;
+
+        let a = Claim::new_with_timestamp("audit".to_string(), 1);
+        let b = Claim::new_with_timestamp("audit".to_string(), 1);
+        let c = Claim::new_with_timestamp("audit".to_string(), 2);
+
+        let set: HashSet<Claim> = [a.clone(), b.clone(), c.clone()].into_iter().collect();
+        assert_eq!(set.len(), 2);
+
+        let mut map = BTreeMap::new();
+        map.insert(c.clone(), "second");
+        map.insert(a.clone(), "first");
+        map.insert(b, "first again");
+        assert_eq!(map.len(), 2);
+        // Ordering follows the canonical bytes: `..."timestamp":1}` < `..."timestamp":2}`
+        assert_eq!(map.keys().collect::<Vec<_>>(), [&a, &c]);
+        assert_eq!(map[&a], "first again");
+    }
+
+    #[test]
+    fn test_uncanonicalizable_claims_keep_the_trait_contracts() {
+        use std::collections::{BTreeSet, HashSet};
+
+        #[derive(Clone)]
+        struct Unserializable;
+        impl Serialize for Unserializable {
+            fn serialize<S: serde::Serializer>(
+                &self,
+                _: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                Err(serde::ser::Error::custom("not serializable"))
+            }
+        }
+
+        let broken = Claim::new_with_timestamp(Unserializable, 1);
+        assert!(broken == broken.clone());
+        assert_eq!(broken.cmp(&broken.clone()), core::cmp::Ordering::Equal);
+        let set: HashSet<Claim<Unserializable>> =
+            [broken.clone(), broken.clone()].into_iter().collect();
+        assert_eq!(set.len(), 1);
+
+        let signed = SignedClaim {
+            claim: broken,
+            public_key: String::new(),
+            signature: String::new(),
+            signed_fields: None,
+            metadata_salt: None,
+            domain: None,
+        };
+        assert!(signed == signed.clone());
+
+        // Over-limit timestamps sort after every canonicalizable claim
+        let late = Claim::new_with_timestamp("a".to_string(), MAX_SAFE_TIMESTAMP + 1);
+        let later = Claim::new_with_timestamp("a".to_string(), MAX_SAFE_TIMESTAMP + 2);
+        let normal = Claim::new_with_timestamp("b".to_string(), 1);
+        let set: BTreeSet<_> = [later.clone(), normal.clone(), late.clone(), late.clone()]
+            .into_iter()
+            .collect();
+        assert_eq!(set.into_iter().collect::<Vec<_>>(), [normal, late, later]);
+    }
+
+    #[test]
+    fn test_signed_claims_sort_chronologically() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let other = SigningKey::from_bytes(&[1u8; 32]);
+        let sign = |data: &str, ts, key| {
+            sign_claim(&Claim::new_with_timestamp(data.to_string(), ts), key).unwrap()
+        };
+        // The timestamp decides before the claim hash does
+        let late = sign("a", 9, &key);
+        let (x, y) = (sign("x", 5, &key), sign("y", 5, &key));
+        let mut log = [late.clone(), y.clone(), x.clone()];
+        log.sort();
+        assert_eq!(log[2], late);
+        let hash = |s: &SignedClaim| claim_hash(&s.claim).unwrap();
+        assert!(hash(&log[0]) < hash(&log[1]));
+
+        // Same claim, different signer: ordered but not equal
+        let cosigned = sign("x", 5, &other);
+        assert_ne!(x, cosigned);
+        assert_eq!(x, x.clone());
+    }
+
+    #[test]
+    fn test_signed_claims_equal_ct() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("ct".to_string(), 1), &key).unwrap();
+        assert!(signed_claims_equal_ct(&signed, &signed.clone()));
+
+        let mut other_sig = signed.clone();
+        other_sig.signature.replace_range(126.., "00");
+        assert!(!signed_claims_equal_ct(&signed, &other_sig));
+
+        let mut other_claim = signed.clone();
+        other_claim.claim.timestamp = 2;
+        assert!(!signed_claims_equal_ct(&signed, &other_claim));
+
+        let mut garbled = signed.clone();
+        garbled.public_key = "zz".to_string();
+        assert!(!signed_claims_equal_ct(&garbled, &garbled.clone()));
+    }
+
+    #[test]
+    fn test_sign_precanonicalized_guard() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("external".to_string(), 7);
+        let canonical = claim.to_signable_bytes().unwrap();
+        let signed = sign_precanonicalized(&canonical, &key).unwrap();
+        assert_eq!(
+            signed.signature,
+            sign_claim(&claim, &key).unwrap().signature
+        );
+
+        for bad in [
+            &br#"{"data":"external", "timestamp":7}"#[..],
+            br#"{"data":"external","extra":1,"timestamp":7}"#,
+            br#"{"data":"external","timestamp":7.0}"#,
+        ] {
+            assert!(matches!(
+                sign_precanonicalized(bad, &key),
+                Err(SdkError::SerializationError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_base64url_round_trip() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("link".to_string(), 3), &key).unwrap();
+        let blob = signed.to_base64url().unwrap();
+        assert!(blob
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+        let decoded: SignedClaim = SignedClaim::from_base64url(&blob).unwrap();
+        assert_eq!(decoded.claim, signed.claim);
+        assert_eq!(decoded.signature, signed.signature);
+
+        // Valid base64url, but not a signed claim
+        let not_json = encoding::base64url_encode(b"{\"claim\":");
+        assert!(matches!(
+            SignedClaim::<String>::from_base64url(&not_json),
+            Err(SdkError::SerializationError(_))
+        ));
+        assert!(SignedClaim::<String>::from_base64url("a+b/").is_err());
+    }
+
+    #[test]
+    fn test_qr_payload_is_smaller_and_round_trips() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("qr".to_string(), 3), &key).unwrap();
+        let payload = signed.to_qr_payload().unwrap();
+        assert!(payload.len() < serde_json::to_string(&signed).unwrap().len());
+
+        let decoded: SignedClaim = SignedClaim::from_qr_payload(&payload).unwrap();
+        assert!(verify_claim(&decoded).unwrap());
+        assert_eq!(decoded.public_key, signed.public_key);
+
+        assert!(matches!(
+            SignedClaim::<String>::from_bytes(&[0u8; 95]),
+            Err(SdkError::SerializationError(_))
+        ));
+        assert!(SignedClaim::<String>::from_qr_payload("a").is_err());
+    }
+
+    #[test]
+    fn test_structured_data_is_canonicalized() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let data: serde_json::Value =
+            serde_json::from_str(r#"[{"b":1,"a":2},{"z":0,"y":0}]"#).unwrap();
+        let claim = Claim::new_with_timestamp(data, 1);
+        assert_eq!(
+            claim.to_signable_bytes().unwrap(),
+            br#"{"data":[{"a":2,"b":1},{"y":0,"z":0}],"timestamp":1}"#
+        );
+        assert!(verify_claim(&sign_claim(&claim, &key).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_millis_are_stored_as_seconds() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let from_millis = Claim::from_millis("t".to_string(), 1_700_000_000_500);
+        let from_secs = Claim::new_with_unit("t".to_string(), 1_700_000_000, TimeUnit::Seconds);
+        assert_eq!(from_millis, from_secs);
+        assert_eq!(
+            sign_claim(&from_millis, &key).unwrap().signature,
+            sign_claim(&from_secs, &key).unwrap().signature
+        );
+        assert_eq!(
+            Claim::new_with_timestamp((), u64::MAX).timestamp_millis(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_reject_empty_data_is_opt_in() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let empty = Claim::new_with_timestamp(String::new(), 1);
+        assert!(sign_claim(&empty, &key).is_ok());
+        assert!(matches!(
+            sign_claim_validated(&empty, &key, &reject_empty_data),
+            Err(SdkError::SerializationError(_))
+        ));
+
+        let filled = Claim::new_with_timestamp("x".to_string(), 1);
+        assert!(sign_claim_validated(&filled, &key, &reject_empty_data).is_ok());
+        assert!(reject_empty_data(&Claim::new_with_timestamp(serde_json::json!({}), 1)).is_err());
+        assert!(reject_empty_data(&Claim::new_with_timestamp(0u8, 1)).is_ok());
+    }
+
+    #[test]
+    fn test_signed_claim_canonical_form_is_pinned() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("pin".to_string(), 1), &key).unwrap();
+        let expected = format!(
+            r#"{{"claim":{{"data":"pin","timestamp":1}},"public_key":"{}","signature":"{}"}}"#,
+            signed.public_key, signed.signature
+        );
+        assert_eq!(signed.to_canonical_bytes().unwrap(), expected.as_bytes());
+        assert_eq!(
+            signed_claim_hash(&signed).unwrap(),
+            "a0a028295aa7604a84a52a5eec645ea0fc43a9fb587b47f27f67f3b1b7b69e2a"
+        );
+    }
+
+    #[test]
+    fn test_sign_claim_fields() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claim = Claim::new_with_timestamp("regulated".to_string(), 5);
+        claim.metadata = Some("advisory".to_string());
+        let signed = sign_claim_fields(&claim, &key, &["timestamp", "data", "data"]).unwrap();
+        assert_eq!(
+            signed.signed_fields.as_deref().unwrap(),
+            ["data".to_string(), "timestamp".to_string()]
+        );
+        assert_eq!(
+            signed.signed_message().unwrap(),
+            br#"["data","timestamp"]{"data":"regulated","timestamp":5}"#
+        );
+
+        // The field list survives (de)serialization
+        let json = serde_json::to_string(&signed).unwrap();
+        let parsed: SignedClaim = serde_json::from_str(&json).unwrap();
+        assert!(verify_claim_fields(&parsed).unwrap());
+
+        // Whole-claim verifiers refuse partial signatures
+        assert!(matches!(
+            verify_claim(&parsed),
+            Err(SdkError::PolicyError(_))
+        ));
+        assert!(verify_claim_strict(&parsed).is_err());
+        assert!(verify_compact_claim(&CompactSignedClaim::from(parsed.clone())).is_err());
+
+        // Dropping the field list changes the message
+        let mut stripped = parsed.clone();
+        stripped.signed_fields = None;
+        assert!(verify_claim(&stripped).is_err());
+
+        // Naming an absent field, or one that was not signed, fails
+        let mut widened = parsed;
+        widened.signed_fields = Some(vec!["data".into(), "expires_at".into(), "timestamp".into()]);
+        assert!(verify_claim_fields(&widened).is_err());
+        widened.claim.expires_at = Some(9);
+        assert!(verify_claim_fields(&widened).is_err());
+
+        assert!(sign_claim_fields(&claim, &key, &["pow"]).is_err());
+        assert!(sign_claim_fields(&claim, &key, &[]).is_err());
+    }
 }
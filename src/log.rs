@@ -0,0 +1,449 @@
+//! Hash-chained claim logs.
+//!
+//! Each entry commits to its predecessor:
+//! `entry_hash = SHA-256(prev_hash || canonical signed claim)`, with the first
+//! entry chaining from [`GENESIS_HASH`]. Rewriting, dropping or reordering any
+//! entry changes every hash after it.
+//!
+//! Claim signatures are verified strictly (see
+//! [`verify_claim_strict`](crate::verify_claim_strict)) on append, when the
+//! log is verified or merged, and for checkpoints.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{parse, sign_claim, verify_claim_strict, Claim, Result, SdkError, SignedClaim};
+
+/// The `prev_hash` of the first entry of every log (Hex encoded, all zeros).
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// A single link of a [`ClaimLog`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LogEntry {
+    /// Hash of this entry, chaining `prev_hash` and the signed claim (Hex encoded)
+    pub entry_hash: String,
+    /// Hash of the previous entry, or [`GENESIS_HASH`] (Hex encoded)
+    pub prev_hash: String,
+    /// The logged claim
+    pub signed: SignedClaim,
+}
+
+fn entry_hash(prev_hash: &str, signed: &SignedClaim) -> Result<String> {
+    let prev = crate::decode_hex(prev_hash)
+        .map_err(|e| SdkError::SerializationError(format!("Invalid prev_hash: {}", e)))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&prev);
+    hasher.update(signed.to_canonical_bytes()?);
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// An append-only, hash-chained log of signed claims.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ClaimLog {
+    entries: Vec<LogEntry>,
+}
+
+impl ClaimLog {
+    /// Create an empty log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify a signed claim and append it to the log
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, ClaimLog, sign_claim, generate_keypair};
+    /// let key = generate_keypair();
+    /// let mut log = ClaimLog::new();
+    /// log.append(sign_claim(&Claim::new_with_timestamp("boot".to_string(), 1), &key).unwrap()).unwrap();
+    /// log.append(sign_claim(&Claim::new_with_timestamp("login".to_string(), 2), &key).unwrap()).unwrap();
+    /// assert_eq!(log.len(), 2);
+    /// assert!(log.verify().unwrap());
+    /// ```
+    pub fn append(&mut self, signed: SignedClaim) -> Result<&LogEntry> {
+        verify_claim_strict(&signed)?;
+        let prev_hash = String::from(self.tip_hash());
+        let entry_hash = entry_hash(&prev_hash, &signed)?;
+        self.entries.push(LogEntry {
+            entry_hash,
+            prev_hash,
+            signed,
+        });
+        Ok(&self.entries[self.entries.len() - 1])
+    }
+
+    /// Like [`append`](Self::append), but require the claim's timestamp to be
+    /// strictly after the latest entry's
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, ClaimLog, sign_claim, generate_keypair};
+    /// let key = generate_keypair();
+    /// let mut log = ClaimLog::new();
+    /// log.append_strict(sign_claim(&Claim::new_with_timestamp("boot".to_string(), 5), &key).unwrap()).unwrap();
+    /// let backdated = sign_claim(&Claim::new_with_timestamp("login".to_string(), 5), &key).unwrap();
+    /// assert!(log.append_strict(backdated).is_err());
+    /// assert_eq!(log.len(), 1);
+    /// ```
+    pub fn append_strict(&mut self, signed: SignedClaim) -> Result<&LogEntry> {
+        if let Some(last) = self.entries.last() {
+            let previous = last.signed.claim.timestamp;
+            if signed.claim.timestamp <= previous {
+                return Err(SdkError::PolicyError(format!(
+                    "timestamp {} does not follow the previous entry's timestamp {}",
+                    signed.claim.timestamp, previous
+                )));
+            }
+        }
+        self.append(signed)
+    }
+
+    /// The entries of the log, oldest first
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    /// Number of entries in the log
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the log has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Hash of the latest entry, or [`GENESIS_HASH`] for an empty log
+    pub fn tip_hash(&self) -> &str {
+        self.entries
+            .last()
+            .map_or(GENESIS_HASH, |entry| entry.entry_hash.as_str())
+    }
+
+    /// Verify every link of the chain and every claim signature
+    pub fn verify(&self) -> Result<bool> {
+        let mut prev_hash = GENESIS_HASH;
+        for (index, entry) in self.entries.iter().enumerate() {
+            if entry.prev_hash != prev_hash
+                || entry_hash(prev_hash, &entry.signed)? != entry.entry_hash
+            {
+                return Err(SdkError::ProofError(format!(
+                    "log chain broken at entry {}",
+                    index
+                )));
+            }
+            verify_claim_strict(&entry.signed)?;
+            prev_hash = &entry.entry_hash;
+        }
+        Ok(true)
+    }
+}
+
+/// Result of [`merge_logs`].
+#[derive(Debug, Clone)]
+pub enum MergeOutcome {
+    /// One log is a prefix of the other; this is the longer of the two.
+    Merged(ClaimLog),
+    /// The logs diverge at `index`, where their entry hashes differ.
+    Fork {
+        /// Position of the first diverging entry
+        index: usize,
+        /// Entry hash at `index` in the first log
+        a_hash: String,
+        /// Entry hash at `index` in the second log
+        b_hash: String,
+    },
+}
+
+/// Merge two copies of a log, detecting forks
+///
+/// Both logs are verified first. A merge only succeeds when one log is a
+/// prefix of the other; otherwise the first point where the `prev_hash`
+/// chains diverge is reported.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, ClaimLog, MergeOutcome, merge_logs, sign_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let mut a = ClaimLog::new();
+/// a.append(sign_claim(&Claim::new_with_timestamp("boot".to_string(), 1), &key).unwrap()).unwrap();
+/// let mut b = a.clone();
+/// b.append(sign_claim(&Claim::new_with_timestamp("login".to_string(), 2), &key).unwrap()).unwrap();
+/// match merge_logs(&a, &b).unwrap() {
+///     MergeOutcome::Merged(merged) => assert_eq!(merged.tip_hash(), b.tip_hash()),
+///     MergeOutcome::Fork { .. } => unreachable!(),
+/// }
+/// ```
+pub fn merge_logs(a: &ClaimLog, b: &ClaimLog) -> Result<MergeOutcome> {
+    a.verify()?;
+    b.verify()?;
+
+    for (index, (ea, eb)) in a.entries.iter().zip(&b.entries).enumerate() {
+        if ea.entry_hash != eb.entry_hash {
+            return Ok(MergeOutcome::Fork {
+                index,
+                a_hash: ea.entry_hash.clone(),
+                b_hash: eb.entry_hash.clone(),
+            });
+        }
+    }
+
+    let longer = if b.len() > a.len() { b } else { a };
+    Ok(MergeOutcome::Merged(longer.clone()))
+}
+
+/// The `kind` of claims produced by [`checkpoint`].
+pub const CHECKPOINT_KIND: &str = "checkpoint";
+
+/// What a [`checkpoint`] records about a log, stored as its metadata JSON.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointMetadata {
+    /// Number of entries covered
+    pub count: u64,
+    /// Timestamp of the first entry's claim
+    pub first_timestamp: u64,
+    /// Timestamp of the last entry's claim
+    pub last_timestamp: u64,
+}
+
+/// Sign a checkpoint of `log` timestamped with the current system time (requires "std")
+///
+/// See [`checkpoint_at`].
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, ClaimLog, checkpoint, sign_claim, verify_checkpoint, generate_keypair};
+/// let (device, auditor) = (generate_keypair(), generate_keypair());
+/// let mut log = ClaimLog::new();
+/// log.append(sign_claim(&Claim::new_with_timestamp("boot".to_string(), 1), &device).unwrap()).unwrap();
+/// let snapshot = checkpoint(&log, &auditor).unwrap();
+/// assert_eq!(snapshot.claim.data, log.tip_hash());
+/// assert!(verify_checkpoint(&snapshot, &log).unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn checkpoint(log: &ClaimLog, key: &SigningKey) -> Result<SignedClaim> {
+    checkpoint_claim(log, Claim::new(String::from(log.tip_hash())), key)
+}
+
+/// Sign a checkpoint of `log` taken at `now`
+///
+/// The checkpoint's data is the log's [`tip_hash`](ClaimLog::tip_hash),
+/// which commits to every entry, and its metadata is the canonical JSON of
+/// a [`CheckpointMetadata`]. The log must not be empty.
+pub fn checkpoint_at(log: &ClaimLog, key: &SigningKey, now: u64) -> Result<SignedClaim> {
+    checkpoint_claim(
+        log,
+        Claim::new_with_timestamp(String::from(log.tip_hash()), now),
+        key,
+    )
+}
+
+fn checkpoint_claim(log: &ClaimLog, claim: Claim, key: &SigningKey) -> Result<SignedClaim> {
+    let (Some(first), Some(last)) = (log.entries.first(), log.entries.last()) else {
+        return Err(SdkError::ProofError(
+            "cannot checkpoint an empty log".to_string(),
+        ));
+    };
+    let metadata = CheckpointMetadata {
+        count: log.len() as u64,
+        first_timestamp: first.signed.claim.timestamp,
+        last_timestamp: last.signed.claim.timestamp,
+    };
+    let mut claim = claim.metadata_json(serde_json::to_value(metadata)?)?;
+    claim.kind = Some(CHECKPOINT_KIND.to_string());
+    sign_claim(&claim, key)
+}
+
+/// Verify a checkpoint against a log
+///
+/// Checks the checkpoint's signature, that the log is intact, and that its
+/// first `count` entries are exactly the ones checkpointed: the entry hash
+/// and time range must match. The log may have grown since. Whether the
+/// checkpoint's signer is a trusted auditor is up to the caller.
+pub fn verify_checkpoint(checkpoint: &SignedClaim, log: &ClaimLog) -> Result<bool> {
+    verify_claim_strict(checkpoint)?;
+    let mismatch = |reason: &str| SdkError::ProofError(format!("checkpoint {}", reason));
+    let claim = &checkpoint.claim;
+    if claim.kind.as_deref() != Some(CHECKPOINT_KIND) {
+        return Err(mismatch("claim has the wrong kind"));
+    }
+    let metadata: CheckpointMetadata = claim
+        .metadata
+        .as_deref()
+        .and_then(|metadata| parse::from_slice_unique(metadata.as_bytes()).ok())
+        .ok_or_else(|| mismatch("metadata is malformed"))?;
+
+    log.verify()?;
+    let covered = usize::try_from(metadata.count)
+        .ok()
+        .filter(|&count| count > 0 && count <= log.len())
+        .ok_or_else(|| {
+            mismatch(&format!(
+                "covers {} entries but the log has {}",
+                metadata.count,
+                log.len()
+            ))
+        })?;
+    let entries = &log.entries[..covered];
+    if entries[covered - 1].entry_hash != claim.data {
+        return Err(mismatch("hash does not match the log"));
+    }
+    if entries[0].signed.claim.timestamp != metadata.first_timestamp
+        || entries[covered - 1].signed.claim.timestamp != metadata.last_timestamp
+    {
+        return Err(mismatch("time range does not match the log"));
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+    use alloc::string::ToString;
+    use ed25519_dalek::SigningKey;
+
+    fn signed(data: &str, timestamp: u64) -> SignedClaim {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        sign_claim(
+            &Claim::new_with_timestamp(data.to_string(), timestamp),
+            &key,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_chain_tamper_detection() {
+        let mut log = ClaimLog::new();
+        assert_eq!(log.tip_hash(), GENESIS_HASH);
+        log.append(signed("a", 1)).unwrap();
+        log.append(signed("b", 2)).unwrap();
+        assert_eq!(log.entries()[1].prev_hash, log.entries()[0].entry_hash);
+        assert!(log.verify().unwrap());
+
+        // Swapping entries breaks the chain
+        let mut reordered = log.clone();
+        reordered.entries.swap(0, 1);
+        assert!(matches!(reordered.verify(), Err(SdkError::ProofError(_))));
+        // A small-order key and signature that lenient verification accepts
+        let mut weak = signed("c", 3);
+        weak.public_key = format!("01{}", "00".repeat(31));
+        weak.signature = format!("01{}", "00".repeat(63));
+        assert!(crate::verify_claim(&weak).unwrap());
+        assert!(matches!(
+            log.append(weak.clone()),
+            Err(SdkError::SignatureError(_))
+        ));
+        assert_eq!(log.len(), 2);
+
+        // Nor can one be smuggled into a deserialized log
+        let mut smuggled = log.clone();
+        let prev_hash = String::from(smuggled.tip_hash());
+        smuggled.entries.push(LogEntry {
+            entry_hash: entry_hash(&prev_hash, &weak).unwrap(),
+            prev_hash,
+            signed: weak,
+        });
+        assert!(matches!(
+            smuggled.verify(),
+            Err(SdkError::SignatureError(_))
+        ));
+        assert!(merge_logs(&log, &smuggled).is_err());
+    }
+
+    #[test]
+    fn test_append_strict_rejects_backdating() {
+        let mut log = ClaimLog::new();
+        log.append_strict(signed("a", 10)).unwrap();
+        for timestamp in [10, 9] {
+            let err = log.append_strict(signed("b", timestamp)).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains(&format!("timestamp {} does not", timestamp)));
+            assert!(err.to_string().contains("timestamp 10"));
+        }
+        // The lenient append still accepts equal timestamps
+        log.append(signed("b", 10)).unwrap();
+        log.append_strict(signed("c", 11)).unwrap();
+        assert_eq!(log.len(), 3);
+    }
+
+    #[test]
+    fn test_merge_detects_fork() {
+        let mut base = ClaimLog::new();
+        base.append(signed("shared", 1)).unwrap();
+
+        let mut a = base.clone();
+        a.append(signed("device a", 2)).unwrap();
+        let mut b = base.clone();
+        b.append(signed("device b", 2)).unwrap();
+        b.append(signed("device b again", 3)).unwrap();
+
+        match merge_logs(&a, &b).unwrap() {
+            MergeOutcome::Fork {
+                index,
+                a_hash,
+                b_hash,
+            } => {
+                assert_eq!(index, 1);
+                assert_eq!(a_hash, a.entries()[1].entry_hash);
+                assert_eq!(b_hash, b.entries()[1].entry_hash);
+            }
+            other => panic!("expected fork, got {:?}", other),
+        }
+
+        // A prefix merges into the longer log, in either argument order
+        for (x, y) in [(&b, &base), (&base, &b)] {
+            match merge_logs(x, y).unwrap() {
+                MergeOutcome::Merged(merged) => assert_eq!(merged.tip_hash(), b.tip_hash()),
+                other => panic!("expected merge, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_covers_a_log_prefix() {
+        let auditor = SigningKey::from_bytes(&[1u8; 32]);
+        let mut log = ClaimLog::new();
+        assert!(checkpoint_at(&log, &auditor, 0).is_err());
+        log.append(signed("boot", 10)).unwrap();
+        log.append(signed("login", 20)).unwrap();
+
+        let snapshot = checkpoint_at(&log, &auditor, 30).unwrap();
+        assert_eq!(
+            snapshot.claim.metadata.as_deref(),
+            Some(r#"{"count":2,"first_timestamp":10,"last_timestamp":20}"#)
+        );
+        assert_eq!(snapshot.claim.timestamp, 30);
+        assert!(verify_checkpoint(&snapshot, &log).unwrap());
+
+        // Still valid once the log grows
+        log.append(signed("logout", 40)).unwrap();
+        assert!(verify_checkpoint(&snapshot, &log).unwrap());
+
+        // A rewritten history no longer matches
+        let mut rewritten = ClaimLog::new();
+        rewritten.append(signed("boot", 10)).unwrap();
+        rewritten.append(signed("login as root", 20)).unwrap();
+        assert!(matches!(
+            verify_checkpoint(&snapshot, &rewritten),
+            Err(SdkError::ProofError(_))
+        ));
+        let mut short = ClaimLog::new();
+        short.append(signed("boot", 10)).unwrap();
+        assert!(verify_checkpoint(&snapshot, &short).is_err());
+
+        let mut forged = snapshot;
+        forged.claim.data = short.tip_hash().to_string();
+        assert!(matches!(
+            verify_checkpoint(&forged, &log),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+}
@@ -0,0 +1,112 @@
+//! Shared-secret authentication of claims (HMAC-SHA256).
+//!
+//! For closed systems where every party holds the same secret, a MAC over the
+//! claim's canonical bytes is cheaper than an Ed25519 signature. A MAC proves
+//! nothing to a third party, so it is returned as a bare hex string and never
+//! mixed into [`SignedClaim`](crate::SignedClaim).
+
+use alloc::string::String;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+use crate::{decode_hex_array, Claim, Result, SdkError};
+
+/// SHA-256 block size in bytes.
+const BLOCK_LEN: usize = 64;
+
+/// HMAC-SHA256 (RFC 2104).
+fn hmac_sha256(secret: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut key = Zeroizing::new([0u8; BLOCK_LEN]);
+    if secret.len() > BLOCK_LEN {
+        key[..32].copy_from_slice(&Sha256::digest(secret));
+    } else {
+        key[..secret.len()].copy_from_slice(secret);
+    }
+    let mut pad = Zeroizing::new([0u8; BLOCK_LEN]);
+
+    for (p, k) in pad.iter_mut().zip(key.iter()) {
+        *p = k ^ 0x36;
+    }
+    let inner = Sha256::new()
+        .chain_update(pad.as_slice())
+        .chain_update(message)
+        .finalize();
+
+    for (p, k) in pad.iter_mut().zip(key.iter()) {
+        *p = k ^ 0x5c;
+    }
+    Sha256::new()
+        .chain_update(pad.as_slice())
+        .chain_update(inner)
+        .finalize()
+        .into()
+}
+
+/// Compute the HMAC-SHA256 of a claim's canonical bytes (Hex encoded)
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, mac_claim, verify_mac};
+/// let claim = Claim::new_with_timestamp("Test".to_string(), 1);
+/// let mac = mac_claim(&claim, b"shared secret").unwrap();
+/// assert!(verify_mac(&claim, &mac, b"shared secret").unwrap());
+/// assert!(verify_mac(&claim, &mac, b"other secret").is_err());
+/// ```
+pub fn mac_claim<T: Serialize>(claim: &Claim<T>, secret: &[u8]) -> Result<String> {
+    Ok(hex::encode(hmac_sha256(
+        secret,
+        &claim.to_signable_bytes()?,
+    )))
+}
+
+/// Check a MAC produced by [`mac_claim`], in constant time
+pub fn verify_mac<T: Serialize>(claim: &Claim<T>, mac: &str, secret: &[u8]) -> Result<bool> {
+    let expected = decode_hex_array::<32>(mac, "MAC")?;
+    let actual = hmac_sha256(secret, &claim.to_signable_bytes()?);
+    if bool::from(actual.ct_eq(&expected)) {
+        Ok(true)
+    } else {
+        Err(SdkError::SignatureError("MAC mismatch".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_rfc4231_vectors() {
+        // Test case 2
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Test case 6: key longer than the block size
+        assert_eq!(
+            hex::encode(hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_tampered_claim_is_rejected() {
+        let claim = Claim::new_with_timestamp("mac".to_string(), 1);
+        let mac = mac_claim(&claim, b"k").unwrap();
+        let mut tampered = claim.clone();
+        tampered.timestamp = 2;
+        assert!(matches!(
+            verify_mac(&tampered, &mac, b"k"),
+            Err(SdkError::SignatureError(_))
+        ));
+        assert!(matches!(
+            verify_mac(&claim, "abcd", b"k"),
+            Err(SdkError::KeyError(_))
+        ));
+    }
+}
@@ -0,0 +1,678 @@
+//! Merkle batching of claims.
+//!
+//! Trees follow the RFC 9162 (Certificate Transparency v2) construction so that
+//! roots and inclusion proofs can be checked by any standard verifier:
+//!
+//! - leaf hash: `SHA-256(0x00 || canonical claim bytes)`
+//! - node hash: `SHA-256(0x01 || left || right)`
+//! - a tree of `n` leaves is split at the largest power of two smaller than `n`
+//!   (no leaf duplication for unbalanced trees).
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{public_key_hex, verify_signature_strict, Claim, ClaimId, Result, SdkError};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Compute the Merkle leaf hash of a claim: `SHA-256(0x00 || canonical bytes)`.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, merkle_leaf_hash};
+/// let leaf = merkle_leaf_hash(&Claim::new_with_timestamp("Test".to_string(), 1)).unwrap();
+/// assert_eq!(leaf.len(), 32);
+/// ```
+pub fn merkle_leaf_hash<T: Serialize>(claim: &Claim<T>) -> Result<[u8; 32]> {
+    let bytes = claim.to_signable_bytes()?;
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(&bytes);
+    Ok(hasher.finalize().into())
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Node hashes of every level of the tree, leaves first and the root last.
+///
+/// Nodes are paired left to right and an unpaired last node moves up a level
+/// unchanged, which builds the same tree as RFC 9162's split at the largest
+/// power of two. Each node is hashed once.
+fn build_levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+        let next = level
+            .chunks(2)
+            .filter_map(|pair| pair.iter().copied().reduce(|l, r| node_hash(&l, &r)))
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+/// Inclusion proof for the leaf at `index`, read from the built levels.
+fn level_proof(levels: &[Vec<[u8; 32]>], index: usize, tree_size: u64) -> MerkleProof {
+    let mut path = Vec::new();
+    let mut position = index;
+    for level in levels {
+        // The last node of a level may have no sibling and move up unchanged
+        if let Some(sibling) = level.get(position ^ 1) {
+            path.push(ClaimId(*sibling));
+        }
+        position >>= 1;
+    }
+    MerkleProof {
+        leaf_index: index as u64,
+        path,
+        tree_size,
+    }
+}
+
+/// A Merkle tree over canonical claim leaf hashes.
+///
+/// Every node hash is computed once when the tree is built, so each proof
+/// costs O(log n) and [`proofs`](Self::proofs) is O(n log n) overall.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// Node hashes by level, leaves first
+    levels: Vec<Vec<[u8; 32]>>,
+    root: [u8; 32],
+}
+
+impl MerkleTree {
+    /// Build a tree from claims, in order. At least one claim is required.
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, MerkleTree};
+    /// let claims: Vec<Claim> = (0..3)
+    ///     .map(|i| Claim::new_with_timestamp(format!("claim {}", i), i))
+    ///     .collect();
+    /// let tree = MerkleTree::from_claims(&claims).unwrap();
+    /// let proof = tree.proof(1).unwrap();
+    /// assert!(proof.verify(&claims[1], &tree.root_id()).unwrap());
+    /// ```
+    pub fn from_claims<T: Serialize>(claims: &[Claim<T>]) -> Result<Self> {
+        let leaves = claims
+            .iter()
+            .map(merkle_leaf_hash)
+            .collect::<Result<Vec<_>>>()?;
+        Self::from_leaves(leaves)
+    }
+
+    /// Build a tree from precomputed leaf hashes. At least one leaf is required.
+    pub fn from_leaves(leaves: Vec<[u8; 32]>) -> Result<Self> {
+        let levels = build_levels(leaves);
+        let root = levels
+            .last()
+            .and_then(|top| top.first())
+            .copied()
+            .ok_or_else(|| SdkError::ProofError("Merkle tree requires at least one leaf".into()))?;
+        Ok(Self { levels, root })
+    }
+
+    fn leaves(&self) -> &[[u8; 32]] {
+        self.levels.first().map_or(&[], Vec::as_slice)
+    }
+
+    /// Number of leaves in the tree
+    pub fn size(&self) -> u64 {
+        self.leaves().len() as u64
+    }
+
+    /// The root hash
+    pub fn root(&self) -> [u8; 32] {
+        self.root
+    }
+
+    /// The root hash as a typed ID
+    pub fn root_id(&self) -> ClaimId {
+        ClaimId(self.root)
+    }
+
+    /// Build the inclusion proof for the leaf at `index`, if it exists.
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.leaves().len() {
+            return None;
+        }
+        Some(level_proof(&self.levels, index, self.size()))
+    }
+
+    /// Inclusion proofs for every leaf, in order
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, MerkleTree};
+    /// let claims: Vec<Claim> = (0..5)
+    ///     .map(|i| Claim::new_with_timestamp(format!("claim {}", i), i))
+    ///     .collect();
+    /// let tree = MerkleTree::from_claims(&claims).unwrap();
+    /// for (claim, proof) in claims.iter().zip(tree.proofs()) {
+    ///     assert!(proof.verify(claim, &tree.root_id()).unwrap());
+    /// }
+    /// ```
+    pub fn proofs(&self) -> impl Iterator<Item = MerkleProof> + '_ {
+        (0..self.leaves().len()).map(|index| level_proof(&self.levels, index, self.size()))
+    }
+}
+
+/// An append-only Merkle tree that maintains its root as leaves arrive.
+///
+/// Internally this is a Merkle Mountain Range: only the roots of the perfect
+/// subtrees ("peaks") are kept, one per set bit of the size, so memory is
+/// O(log n) and each [`push`](Self::push) does O(log n) hashing. The root is
+/// the same as that of a [`MerkleTree`] over the same leaves.
+///
+/// Inclusion proofs need all leaf hashes, so they are only available from
+/// an accumulator created with [`with_proofs`](Self::with_proofs).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleAccumulator {
+    /// Peak hashes, largest subtree first
+    peaks: Vec<[u8; 32]>,
+    size: u64,
+    leaves: Option<Vec<[u8; 32]>>,
+}
+
+impl MerkleAccumulator {
+    /// An empty accumulator that keeps only the peaks
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, MerkleAccumulator, MerkleTree};
+    /// let claims: Vec<Claim> = (0..5)
+    ///     .map(|i| Claim::new_with_timestamp(format!("claim {}", i), i))
+    ///     .collect();
+    /// let mut acc = MerkleAccumulator::new();
+    /// for claim in &claims {
+    ///     acc.push_claim(claim).unwrap();
+    /// }
+    /// assert_eq!(acc.root(), Some(MerkleTree::from_claims(&claims).unwrap().root()));
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty accumulator that also retains leaf hashes, so it can issue
+    /// inclusion proofs (O(n) memory)
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{Claim, MerkleAccumulator};
+    /// let claims: Vec<Claim> = (0..3)
+    ///     .map(|i| Claim::new_with_timestamp(format!("claim {}", i), i))
+    ///     .collect();
+    /// let mut acc = MerkleAccumulator::with_proofs();
+    /// for claim in &claims {
+    ///     acc.push_claim(claim).unwrap();
+    /// }
+    /// let proof = acc.proof(1).unwrap();
+    /// assert!(proof.verify(&claims[1], &acc.root_id().unwrap()).unwrap());
+    /// ```
+    pub fn with_proofs() -> Self {
+        Self {
+            leaves: Some(Vec::new()),
+            ..Self::default()
+        }
+    }
+
+    /// Append a leaf hash (see [`merkle_leaf_hash`])
+    pub fn push(&mut self, leaf: [u8; 32]) {
+        if let Some(leaves) = &mut self.leaves {
+            leaves.push(leaf);
+        }
+        // Each trailing one bit of the old size is a peak of the same height
+        // as the new one, so merge until the heights differ.
+        let mut merged = leaf;
+        let mut size = self.size;
+        while size & 1 == 1 {
+            let left = self.peaks.pop().expect("one peak per set bit");
+            merged = node_hash(&left, &merged);
+            size >>= 1;
+        }
+        self.peaks.push(merged);
+        self.size += 1;
+    }
+
+    /// Append a claim's leaf hash
+    pub fn push_claim<T: Serialize>(&mut self, claim: &Claim<T>) -> Result<()> {
+        self.push(merkle_leaf_hash(claim)?);
+        Ok(())
+    }
+
+    /// Number of leaves appended so far
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The root hash, or `None` if no leaf has been appended
+    pub fn root(&self) -> Option<[u8; 32]> {
+        // An unbalanced tree splits off its largest perfect subtree first, so
+        // the root folds the peaks from the smallest up.
+        let (last, rest) = self.peaks.split_last()?;
+        Some(
+            rest.iter()
+                .rev()
+                .fold(*last, |right, left| node_hash(left, &right)),
+        )
+    }
+
+    /// The root hash as a typed ID, or `None` if no leaf has been appended
+    pub fn root_id(&self) -> Option<ClaimId> {
+        self.root().map(ClaimId)
+    }
+
+    /// Build the inclusion proof for the leaf at `index` against the current
+    /// root, if the leaf exists and this accumulator retains leaves
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        let leaves = self.leaves.as_ref()?;
+        if index >= leaves.len() {
+            return None;
+        }
+        Some(level_proof(&build_levels(leaves.clone()), index, self.size))
+    }
+}
+
+/// An inclusion proof (audit path) for one leaf of a [`MerkleTree`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// Zero-based position of the leaf in the tree
+    pub leaf_index: u64,
+    /// Sibling hashes from the leaf up to the root (Hex encoded when serialized)
+    pub path: Vec<ClaimId>,
+    /// Number of leaves in the tree the proof was generated from
+    pub tree_size: u64,
+}
+
+impl MerkleProof {
+    /// Recompute the root implied by this proof for the given leaf hash
+    /// (RFC 9162, section 2.1.3.2).
+    pub fn root_for_leaf(&self, leaf: &[u8; 32]) -> Result<[u8; 32]> {
+        if self.leaf_index >= self.tree_size {
+            return Err(SdkError::ProofError(format!(
+                "leaf index {} out of range for tree size {}",
+                self.leaf_index, self.tree_size
+            )));
+        }
+
+        let mut fn_ = self.leaf_index;
+        let mut sn = self.tree_size - 1;
+        let mut r = *leaf;
+        for p in &self.path {
+            if sn == 0 {
+                return Err(SdkError::ProofError("inclusion path is too long".into()));
+            }
+            if fn_ & 1 == 1 || fn_ == sn {
+                r = node_hash(&p.0, &r);
+                while fn_ & 1 == 0 && fn_ != 0 {
+                    fn_ >>= 1;
+                    sn >>= 1;
+                }
+            } else {
+                r = node_hash(&r, &p.0);
+            }
+            fn_ >>= 1;
+            sn >>= 1;
+        }
+        if sn != 0 {
+            return Err(SdkError::ProofError("inclusion path is too short".into()));
+        }
+        Ok(r)
+    }
+
+    /// Verify that `claim` is included under `root`
+    pub fn verify<T: Serialize>(&self, claim: &Claim<T>, root: &ClaimId) -> Result<bool> {
+        if self.root_for_leaf(&merkle_leaf_hash(claim)?)? != root.0 {
+            return Err(SdkError::ProofError(
+                "claim is not included under the Merkle root".into(),
+            ));
+        }
+        Ok(true)
+    }
+}
+
+/// Signed payload of a [`SignedRoot`]. Fields are ordered alphabetically (JCS).
+#[derive(Serialize)]
+struct RootPayload<'a> {
+    root: &'a ClaimId,
+    tree_size: u64,
+}
+
+/// A Merkle root committed to by a signer.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SignedRoot {
+    /// The public key of the signer (Hex encoded)
+    pub public_key: String,
+    /// The Merkle root (Hex encoded when serialized)
+    pub root: ClaimId,
+    /// The signature of the serialized root payload (Hex encoded)
+    pub signature: String,
+    /// Number of leaves committed to by the root
+    pub tree_size: u64,
+}
+
+impl SignedRoot {
+    /// Canonical serialization for signing: `{"root":"<hex>","tree_size":<n>}`
+    pub fn to_signable_bytes(&self) -> Result<Vec<u8>> {
+        root_signable_bytes(&self.root, self.tree_size)
+    }
+}
+
+fn root_signable_bytes(root: &ClaimId, tree_size: u64) -> Result<Vec<u8>> {
+    let json = serde_json::to_string(&RootPayload { root, tree_size })?;
+    Ok(json.into_bytes())
+}
+
+/// Sign the root of a Merkle tree
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, MerkleTree, sign_root, verify_root, generate_keypair};
+/// let tree = MerkleTree::from_claims(&[Claim::new("Test Claim".to_string())]).unwrap();
+/// let signed_root = sign_root(&tree, &generate_keypair()).unwrap();
+/// assert!(verify_root(&signed_root).unwrap());
+/// ```
+pub fn sign_root(tree: &MerkleTree, key: &SigningKey) -> Result<SignedRoot> {
+    let root = tree.root_id();
+    let bytes = root_signable_bytes(&root, tree.size())?;
+    let signature = key.sign(&bytes);
+
+    Ok(SignedRoot {
+        public_key: public_key_hex(key),
+        root,
+        signature: hex::encode(signature.to_bytes()),
+        tree_size: tree.size(),
+    })
+}
+
+/// Verify the signature over a Merkle root
+///
+/// The signature is verified strictly, as
+/// [`verify_claim_strict`](crate::verify_claim_strict) does, so every receipt
+/// anchored to the root inherits the same acceptance rule.
+pub fn verify_root(signed_root: &SignedRoot) -> Result<bool> {
+    let msg_bytes = signed_root.to_signable_bytes()?;
+    verify_signature_strict(&msg_bytes, &signed_root.public_key, &signed_root.signature)?;
+    Ok(true)
+}
+
+/// Verify that a claim is part of a batch the signer committed to
+///
+/// This checks, in order, that the root signature is valid and that the
+/// proof places the claim under that root. Failures are distinguishable:
+/// a bad root signature or key yields [`SdkError::SignatureError`] /
+/// [`SdkError::KeyError`], while a proof that does not match the signed
+/// root (wrong claim, wrong tree size, malformed path) yields
+/// [`SdkError::ProofError`].
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, MerkleTree, sign_root, verify_anchored, generate_keypair};
+/// let claims: Vec<Claim> = (0..5)
+///     .map(|i| Claim::new_with_timestamp(format!("claim {}", i), i))
+///     .collect();
+/// let tree = MerkleTree::from_claims(&claims).unwrap();
+/// let signed_root = sign_root(&tree, &generate_keypair()).unwrap();
+/// let proof = tree.proof(3).unwrap();
+/// assert!(verify_anchored(&claims[3], &proof, &signed_root).unwrap());
+/// ```
+pub fn verify_anchored<T: Serialize>(
+    claim: &Claim<T>,
+    proof: &MerkleProof,
+    root_sig: &SignedRoot,
+) -> Result<bool> {
+    verify_root(root_sig)?;
+    if proof.tree_size != root_sig.tree_size {
+        return Err(SdkError::ProofError(format!(
+            "proof is for tree size {} but root commits to {}",
+            proof.tree_size, root_sig.tree_size
+        )));
+    }
+    proof.verify(claim, &root_sig.root)
+}
+
+/// Proof that one claim belongs to a signed batch.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BatchReceipt {
+    /// Inclusion proof for the claim
+    pub proof: MerkleProof,
+    /// The signed root of the batch
+    pub signed_root: SignedRoot,
+}
+
+impl BatchReceipt {
+    /// Verify the root signature and the claim's inclusion under it
+    /// (see [`verify_anchored`])
+    pub fn verify<T: Serialize>(&self, claim: &Claim<T>) -> Result<bool> {
+        verify_anchored(claim, &self.proof, &self.signed_root)
+    }
+}
+
+/// Output of [`anchor_batch`]: the signed root and one receipt per claim.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AnchoredBatch {
+    /// Receipts in the same order as the input claims
+    pub receipts: Vec<BatchReceipt>,
+    /// The signed root, for publishing or anchoring on-chain
+    pub signed_root: SignedRoot,
+}
+
+/// Build a Merkle tree over `claims`, sign its root once and issue a receipt
+/// for every claim
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, anchor_batch, generate_keypair};
+/// let claims: Vec<Claim> = (0..4)
+///     .map(|i| Claim::new_with_timestamp(format!("claim {}", i), i))
+///     .collect();
+/// let batch = anchor_batch(&claims, &generate_keypair()).unwrap();
+/// for (claim, receipt) in claims.iter().zip(&batch.receipts) {
+///     assert!(receipt.verify(claim).unwrap());
+/// }
+/// assert!(batch.receipts[0].verify(&claims[1]).is_err());
+/// ```
+pub fn anchor_batch<T: Serialize>(claims: &[Claim<T>], key: &SigningKey) -> Result<AnchoredBatch> {
+    let tree = MerkleTree::from_claims(claims)?;
+    let signed_root = sign_root(&tree, key)?;
+    let receipts = tree
+        .proofs()
+        .map(|proof| BatchReceipt {
+            proof,
+            signed_root: signed_root.clone(),
+        })
+        .collect();
+    Ok(AnchoredBatch {
+        receipts,
+        signed_root,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    fn claims(n: u64) -> Vec<Claim> {
+        (0..n)
+            .map(|i| Claim::new_with_timestamp(format!("claim {}", i), i))
+            .collect()
+    }
+
+    #[test]
+    fn test_proofs_for_all_sizes() {
+        for n in 1..=17 {
+            let claims = claims(n);
+            let tree = MerkleTree::from_claims(&claims).unwrap();
+            let root = tree.root_id();
+            for (i, claim) in claims.iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+                assert!(proof.verify(claim, &root).unwrap(), "n={} i={}", n, i);
+            }
+            assert!(tree.proof(n as usize).is_none());
+        }
+    }
+
+    #[test]
+    fn test_rfc9162_root_shape() {
+        let claims = claims(3);
+        let leaves: Vec<_> = claims
+            .iter()
+            .map(|c| merkle_leaf_hash(c).unwrap())
+            .collect();
+        let tree = MerkleTree::from_claims(&claims).unwrap();
+        // Unbalanced trees are split at the largest power of two: ((0,1),2)
+        let expected = node_hash(&node_hash(&leaves[0], &leaves[1]), &leaves[2]);
+        assert_eq!(tree.root(), expected);
+    }
+
+    #[test]
+    fn test_levels_match_the_rfc9162_split() {
+        // RFC 9162, section 2.1.1: split at the largest power of two below n
+        fn reference_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+            if leaves.len() == 1 {
+                return leaves[0];
+            }
+            let k = 1 << (usize::BITS - (leaves.len() - 1).leading_zeros() - 1);
+            node_hash(&reference_root(&leaves[..k]), &reference_root(&leaves[k..]))
+        }
+
+        for n in 1..=33u64 {
+            let leaves: Vec<_> = claims(n)
+                .iter()
+                .map(|c| merkle_leaf_hash(c).unwrap())
+                .collect();
+            let tree = MerkleTree::from_leaves(leaves.clone()).unwrap();
+            assert_eq!(tree.root(), reference_root(&leaves), "n={}", n);
+            let proofs: Vec<_> = tree.proofs().collect();
+            assert_eq!(proofs.len() as u64, n);
+            for (i, proof) in proofs.iter().enumerate() {
+                assert_eq!(Some(proof), tree.proof(i).as_ref());
+                assert_eq!(proof.root_for_leaf(&leaves[i]).unwrap(), tree.root());
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_anchored_failure_modes() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claims = claims(4);
+        let tree = MerkleTree::from_claims(&claims).unwrap();
+        let signed_root = sign_root(&tree, &key).unwrap();
+        let proof = tree.proof(2).unwrap();
+
+        assert!(verify_anchored(&claims[2], &proof, &signed_root).unwrap());
+
+        // Claim not in the batch
+        let outsider = Claim::new_with_timestamp("outsider".to_string(), 9);
+        assert!(matches!(
+            verify_anchored(&outsider, &proof, &signed_root),
+            Err(SdkError::ProofError(_))
+        ));
+
+        // Root that was not signed by the key
+        let mut forged = signed_root.clone();
+        forged.root = ClaimId([0u8; 32]);
+        assert!(matches!(
+            verify_anchored(&claims[2], &proof, &forged),
+            Err(SdkError::SignatureError(_))
+        ));
+
+        // A small-order key and signature that lenient verification accepts
+        let mut weak = signed_root.clone();
+        weak.public_key = format!("01{}", "00".repeat(31));
+        weak.signature = format!("01{}", "00".repeat(63));
+        assert!(crate::verify_signature(
+            &weak.to_signable_bytes().unwrap(),
+            &weak.public_key,
+            &weak.signature
+        )
+        .is_ok());
+        assert!(matches!(
+            verify_anchored(&claims[2], &proof, &weak),
+            Err(SdkError::SignatureError(_))
+        ));
+
+        // Proof from a different tree size
+        let mut resized = proof.clone();
+        resized.tree_size = 5;
+        assert!(matches!(
+            verify_anchored(&claims[2], &resized, &signed_root),
+            Err(SdkError::ProofError(_))
+        ));
+    }
+
+    #[test]
+    fn test_anchor_batch_receipts() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claims = claims(5);
+        let batch = anchor_batch(&claims, &key).unwrap();
+        assert_eq!(batch.receipts.len(), 5);
+        assert_eq!(batch.signed_root.tree_size, 5);
+        assert!(batch.receipts[4].verify(&claims[4]).unwrap());
+
+        // Receipts are self-contained once serialized
+        let json = serde_json::to_string(&batch.receipts[2]).unwrap();
+        assert!(json.contains(&format!(r#""root":"{}""#, batch.signed_root.root)));
+        let receipt: BatchReceipt = serde_json::from_str(&json).unwrap();
+        assert!(receipt.verify(&claims[2]).unwrap());
+
+        assert!(anchor_batch::<String>(&[], &key).is_err());
+    }
+
+    #[test]
+    fn test_accumulator_matches_tree() {
+        let claims = claims(33);
+        let mut acc = MerkleAccumulator::with_proofs();
+        let mut lean = MerkleAccumulator::new();
+        assert_eq!(acc.root(), None);
+        for (n, claim) in claims.iter().enumerate() {
+            acc.push_claim(claim).unwrap();
+            lean.push_claim(claim).unwrap();
+            let tree = MerkleTree::from_claims(&claims[..=n]).unwrap();
+            assert_eq!(acc.size(), tree.size());
+            assert_eq!(acc.root(), Some(tree.root()), "n={}", n + 1);
+            assert_eq!(lean.root(), acc.root());
+            // Only one peak per set bit of the size is kept
+            assert_eq!(lean.peaks.len(), (n + 1).count_ones() as usize);
+        }
+        assert!(lean.proof(0).is_none());
+    }
+
+    #[test]
+    fn test_accumulator_proofs_as_it_grows() {
+        let claims = claims(17);
+        let mut acc = MerkleAccumulator::with_proofs();
+        for (n, claim) in claims.iter().enumerate() {
+            acc.push_claim(claim).unwrap();
+            let root = acc.root_id().unwrap();
+            for (i, included) in claims[..=n].iter().enumerate() {
+                let proof = acc.proof(i).unwrap();
+                assert_eq!(proof.tree_size, n as u64 + 1);
+                assert!(
+                    proof.verify(included, &root).unwrap(),
+                    "n={} i={}",
+                    n + 1,
+                    i
+                );
+            }
+            assert!(acc.proof(n + 1).is_none());
+        }
+
+        // A proof is bound to the size it was issued at
+        let old = acc.proof(3).unwrap();
+        acc.push([0u8; 32]);
+        assert!(old.verify(&claims[3], &acc.root_id().unwrap()).is_err());
+    }
+}
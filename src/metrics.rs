@@ -0,0 +1,179 @@
+//! Verification outcome counters.
+//!
+//! With the `metrics` feature enabled, every claim checked by
+//! [`verify_claim`](crate::verify_claim), [`verify_claim_strict`](crate::verify_claim_strict),
+//! [`verify_compact_claim`](crate::verify_compact_claim),
+//! [`verify_claim_aad`](crate::verify_claim_aad) or
+//! [`verify_batch`](crate::verify_batch) is counted by outcome through
+//! the [`metrics`](https://docs.rs/metrics) crate facade, as the counter
+//! [`VERIFY_COUNTER`] with an `outcome` label. Any installed exporter (for
+//! example `metrics-exporter-prometheus`) picks the counts up. They are also
+//! kept in process-wide atomics, readable with [`verify_metrics`] when no
+//! exporter is installed. Without the feature, recording compiles to nothing.
+
+#[cfg(feature = "metrics")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Name of the counter emitted through the `metrics` facade.
+#[cfg(feature = "metrics")]
+pub const VERIFY_COUNTER: &str = "provn_verify_total";
+
+/// Outcome of a single verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VerifyMetric {
+    /// The signature verified
+    Valid,
+    /// Everything decoded, but the signature did not match the claim
+    Tampered,
+    /// The claim, public key or signature could not be decoded
+    DecodeError,
+}
+
+#[cfg(feature = "metrics")]
+impl VerifyMetric {
+    /// Value of the `outcome` label (`"valid"`, `"tampered"`, `"decode_error"`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerifyMetric::Valid => "valid",
+            VerifyMetric::Tampered => "tampered",
+            VerifyMetric::DecodeError => "decode_error",
+        }
+    }
+}
+
+/// Snapshot of the verification counters.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VerifyCounts {
+    /// Verifications that failed to decode
+    pub decode_error: u64,
+    /// Verifications whose signature did not match
+    pub tampered: u64,
+    /// Successful verifications
+    pub valid: u64,
+}
+
+#[cfg(feature = "metrics")]
+static VALID: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "metrics")]
+static TAMPERED: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "metrics")]
+static DECODE_ERROR: AtomicU64 = AtomicU64::new(0);
+
+/// Current values of the process-wide verification counters
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_claim, verify_metrics, generate_keypair};
+/// let before = verify_metrics().valid;
+/// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &generate_keypair()).unwrap();
+/// verify_claim(&signed).unwrap();
+/// assert!(verify_metrics().valid > before);
+/// ```
+#[cfg(feature = "metrics")]
+pub fn verify_metrics() -> VerifyCounts {
+    VerifyCounts {
+        decode_error: DECODE_ERROR.load(Ordering::Relaxed),
+        tampered: TAMPERED.load(Ordering::Relaxed),
+        valid: VALID.load(Ordering::Relaxed),
+    }
+}
+
+#[cfg(feature = "metrics")]
+pub(crate) fn record(metric: VerifyMetric) {
+    let counter = match metric {
+        VerifyMetric::Valid => &VALID,
+        VerifyMetric::Tampered => &TAMPERED,
+        VerifyMetric::DecodeError => &DECODE_ERROR,
+    };
+    counter.fetch_add(1, Ordering::Relaxed);
+    ::metrics::counter!(VERIFY_COUNTER, "outcome" => metric.as_str()).increment(1);
+}
+
+#[cfg(not(feature = "metrics"))]
+#[inline(always)]
+pub(crate) fn record(_metric: VerifyMetric) {}
+
+#[cfg(all(test, feature = "metrics"))]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, verify_batch, verify_claim, Claim};
+    use alloc::collections::BTreeMap;
+    use alloc::string::{String, ToString};
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use ed25519_dalek::SigningKey;
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+    use std::sync::Mutex;
+
+    /// Totals per `name{outcome}` of the counters registered with it.
+    #[derive(Default)]
+    struct Tally(Arc<Mutex<BTreeMap<String, u64>>>);
+
+    struct TallyCounter(String, Arc<Mutex<BTreeMap<String, u64>>>);
+
+    impl CounterFn for TallyCounter {
+        fn increment(&self, value: u64) {
+            *self.1.lock().unwrap().entry(self.0.clone()).or_default() += value;
+        }
+
+        fn absolute(&self, value: u64) {
+            self.1.lock().unwrap().insert(self.0.clone(), value);
+        }
+    }
+
+    impl Recorder for Tally {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            let labels: Vec<String> = key.labels().map(|l| l.value().to_string()).collect();
+            let name = alloc::format!("{}{{{}}}", key.name(), labels.join(","));
+            Counter::from_arc(Arc::new(TallyCounter(name, self.0.clone())))
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            Gauge::noop()
+        }
+
+        fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+            Histogram::noop()
+        }
+    }
+
+    #[test]
+    fn test_outcomes_are_counted() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("m".to_string(), 1), &key).unwrap();
+        let mut tampered = signed.clone();
+        tampered.claim.data = "n".to_string();
+        let mut garbled = signed.clone();
+        garbled.signature = "zz".to_string();
+
+        let before = verify_metrics();
+        let tally = Tally::default();
+        metrics::with_local_recorder(&tally, || {
+            verify_claim(&signed).unwrap();
+            assert!(verify_claim(&tampered).is_err());
+            let results = verify_batch(&[signed.clone(), garbled.clone(), signed.clone()]);
+            assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 2);
+        });
+        let after = verify_metrics();
+
+        let expected: BTreeMap<String, u64> = [
+            ("provn_verify_total{decode_error}".to_string(), 1),
+            ("provn_verify_total{tampered}".to_string(), 1),
+            ("provn_verify_total{valid}".to_string(), 3),
+        ]
+        .into();
+        assert_eq!(*tally.0.lock().unwrap(), expected);
+
+        // Other tests may verify concurrently, so only lower bounds hold
+        assert!(after.valid >= before.valid + 3);
+        assert!(after.tampered > before.tampered);
+        assert!(after.decode_error > before.decode_error);
+    }
+}
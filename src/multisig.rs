@@ -0,0 +1,221 @@
+//! Claims signed by several parties.
+//!
+//! Every signature in a [`MultiSignedClaim`] covers the same canonical claim
+//! bytes as [`sign_claim`](crate::sign_claim). Signatures are kept sorted by
+//! raw public key bytes, one per signer, so the serialized form (and any hash
+//! of it) does not depend on the order in which the parties signed, and one
+//! party cannot be counted twice.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    canonicalize, decode_public_key_bytes, public_key_hex, verify_signature, Claim, Result,
+    SdkError, PUBLIC_KEY_BYTES,
+};
+
+/// One party's signature over a [`MultiSignedClaim`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ClaimSignature {
+    /// The public key of the signer (Hex encoded)
+    pub public_key: String,
+    /// The signature of the serialized claim (Hex encoded)
+    pub signature: String,
+}
+
+impl ClaimSignature {
+    /// Sort key: raw public key bytes, with undecodable keys first. Two
+    /// encodings of one key (e.g. upper- and lower-case hex) are the same
+    /// signer.
+    fn sort_key(&self) -> Option<[u8; PUBLIC_KEY_BYTES]> {
+        decode_public_key_bytes(&self.public_key).ok()
+    }
+}
+
+/// A claim with signatures from several parties.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, MultiSignedClaim, generate_keypair};
+/// let (alice, bob) = (generate_keypair(), generate_keypair());
+/// let mut multi = MultiSignedClaim::new(Claim::new_with_timestamp("Joint statement".to_string(), 1));
+/// multi.add_signature(&bob).unwrap();
+/// multi.add_signature(&alice).unwrap();
+/// assert_eq!(multi.signatures.len(), 2);
+/// assert!(multi.verify().unwrap());
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MultiSignedClaim<T = String> {
+    /// The claim every signature covers
+    pub claim: Claim<T>,
+    /// The signatures, sorted by public key bytes (see
+    /// [`canonicalize_signatures`](MultiSignedClaim::canonicalize_signatures))
+    pub signatures: Vec<ClaimSignature>,
+}
+
+/// Equal when the claims are equal (see [`Claim`]) and the signatures match
+/// in order; call
+/// [`canonicalize_signatures`](MultiSignedClaim::canonicalize_signatures)
+/// first to compare regardless of order.
+impl<T: Serialize> PartialEq for MultiSignedClaim<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.claim == other.claim && self.signatures == other.signatures
+    }
+}
+
+impl<T: Serialize> Eq for MultiSignedClaim<T> {}
+
+impl<T: Serialize> MultiSignedClaim<T> {
+    /// Start a multi-signed claim with no signatures yet
+    pub fn new(claim: Claim<T>) -> Self {
+        Self {
+            claim,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Sign the claim with `key`, inserting the signature in canonical order
+    ///
+    /// Signing again with a key that already signed is a no-op, since
+    /// Ed25519 signatures are deterministic.
+    pub fn add_signature(&mut self, key: &SigningKey) -> Result<()> {
+        let signature = key.sign(&self.claim.to_signable_bytes()?);
+        let entry = ClaimSignature {
+            public_key: public_key_hex(key),
+            signature: hex::encode(signature.to_bytes()),
+        };
+        if let Err(position) = self
+            .signatures
+            .binary_search_by(|existing| existing.sort_key().cmp(&entry.sort_key()))
+        {
+            self.signatures.insert(position, entry);
+        }
+        Ok(())
+    }
+
+    /// Sort the signatures into canonical order, keeping the first signature
+    /// of each signer
+    ///
+    /// Use this on multi-signed claims assembled or deserialized from
+    /// elsewhere before hashing, anchoring or verifying them.
+    pub fn canonicalize_signatures(&mut self) {
+        self.signatures.sort_by_key(ClaimSignature::sort_key);
+        self.signatures
+            .dedup_by(|later, first| later.sort_key() == first.sort_key());
+    }
+
+    /// Canonical (RFC 8785) bytes of the claim and its signatures
+    ///
+    /// Stable across signing orders once the signatures are canonical.
+    pub fn to_canonical_bytes(&self) -> Result<Vec<u8>> {
+        self.claim.check_timestamp()?;
+        canonicalize(self)
+    }
+
+    /// Verify every signature over the claim
+    ///
+    /// Fails if there are no signatures, any one of them is invalid, or the
+    /// signatures are not in canonical order with one per signer, so a
+    /// repeated entry cannot make one party look like several.
+    pub fn verify(&self) -> Result<bool> {
+        if self.signatures.is_empty() {
+            return Err(SdkError::SignatureError(
+                "multi-signed claim has no signatures".to_string(),
+            ));
+        }
+        let message = self.claim.to_signable_bytes()?;
+        let mut previous: Option<[u8; PUBLIC_KEY_BYTES]> = None;
+        for entry in &self.signatures {
+            let key = decode_public_key_bytes(&entry.public_key)?;
+            if previous.is_some_and(|previous| previous >= key) {
+                return Err(SdkError::SignatureError(
+                    "multi-signed claim repeats a signer or is not in canonical order".to_string(),
+                ));
+            }
+            previous = Some(key);
+            verify_signature(&message, &entry.public_key, &entry.signature)?;
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_order_does_not_change_the_canonical_form() {
+        let keys: Vec<SigningKey> = (0u8..4).map(|i| SigningKey::from_bytes(&[i; 32])).collect();
+        let claim = Claim::new_with_timestamp("joint".to_string(), 1);
+
+        let mut forward = MultiSignedClaim::new(claim.clone());
+        for key in &keys {
+            forward.add_signature(key).unwrap();
+        }
+        let mut backward = MultiSignedClaim::new(claim);
+        for key in keys.iter().rev() {
+            backward.add_signature(key).unwrap();
+        }
+        backward.add_signature(&keys[2]).unwrap();
+
+        assert_eq!(forward, backward);
+        assert_eq!(
+            forward.to_canonical_bytes().unwrap(),
+            backward.to_canonical_bytes().unwrap()
+        );
+        assert!(forward.verify().unwrap());
+
+        let mut external = forward.clone();
+        external.signatures.reverse();
+        external.signatures.push(forward.signatures[0].clone());
+        let mut upper = forward.signatures[1].clone();
+        upper.public_key = upper.public_key.to_uppercase();
+        external.signatures.push(upper);
+        assert_ne!(external, forward);
+        external.canonicalize_signatures();
+        assert_eq!(external, forward);
+    }
+
+    #[test]
+    fn test_repeated_or_unordered_signers_are_rejected() {
+        let mut multi = MultiSignedClaim::new(Claim::new_with_timestamp("joint".to_string(), 1));
+        for i in 0u8..2 {
+            multi
+                .add_signature(&SigningKey::from_bytes(&[i; 32]))
+                .unwrap();
+        }
+        assert!(multi.verify().unwrap());
+
+        let mut repeated = multi.clone();
+        let mut upper = repeated.signatures[1].clone();
+        upper.public_key = upper.public_key.to_uppercase();
+        repeated.signatures.push(upper);
+        assert!(matches!(
+            repeated.verify(),
+            Err(SdkError::SignatureError(_))
+        ));
+
+        let mut unordered = multi;
+        unordered.signatures.reverse();
+        assert!(matches!(
+            unordered.verify(),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_every_signature_must_verify() {
+        let mut multi = MultiSignedClaim::new(Claim::new_with_timestamp("joint".to_string(), 1));
+        assert!(multi.verify().is_err());
+        multi
+            .add_signature(&SigningKey::from_bytes(&[0u8; 32]))
+            .unwrap();
+        multi
+            .add_signature(&SigningKey::from_bytes(&[1u8; 32]))
+            .unwrap();
+        multi.signatures[1].signature = multi.signatures[0].signature.clone();
+        assert!(matches!(multi.verify(), Err(SdkError::SignatureError(_))));
+    }
+}
@@ -0,0 +1,150 @@
+//! Layered attestations carried in claim metadata.
+//!
+//! A claim's `metadata` may hold the JSON of another [`SignedClaim`], which
+//! may in turn nest a further one. [`verify_nested`] walks that chain down
+//! to a bounded depth, so a hostile claim cannot make a verifier recurse
+//! without limit.
+
+use alloc::vec::Vec;
+use serde::Serialize;
+
+use crate::{parse, verify_claim, Claim, Result, SignedClaim};
+
+/// Nesting depth checked by [`verify_nested`].
+pub const DEFAULT_NESTING_DEPTH: usize = 4;
+
+/// Results of verifying the claims nested inside a signed claim.
+#[derive(Debug)]
+pub struct NestedVerification {
+    /// Outcome for each nested claim, outermost first. The walk stops at the
+    /// first claim that fails, since its metadata cannot be trusted.
+    pub nested: Vec<Result<bool>>,
+    /// Whether the last claim checked nests a further claim beyond the depth limit
+    pub truncated: bool,
+}
+
+impl NestedVerification {
+    /// Whether every nested claim verified and none was left unchecked
+    pub fn all_valid(&self) -> bool {
+        !self.truncated && self.nested.iter().all(|result| result.is_ok())
+    }
+}
+
+fn nested_claim<T>(claim: &Claim<T>) -> Option<SignedClaim<serde_json::Value>> {
+    let metadata = claim.metadata.as_deref()?;
+    parse::from_slice_unique(metadata.as_bytes()).ok()
+}
+
+/// Verify a signed claim and the claims nested in its metadata, up to
+/// [`DEFAULT_NESTING_DEPTH`] levels deep
+///
+/// Fails if the outer claim itself does not verify.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_nested, generate_keypair};
+/// let (lab, auditor) = (generate_keypair(), generate_keypair());
+/// let result = sign_claim(&Claim::new_with_timestamp("sample 17: negative".to_string(), 1), &lab).unwrap();
+/// let mut review = Claim::new_with_timestamp("reviewed".to_string(), 2);
+/// review.metadata = Some(serde_json::to_string(&result).unwrap());
+/// let review = sign_claim(&review, &auditor).unwrap();
+///
+/// let verification = verify_nested(&review).unwrap();
+/// assert_eq!(verification.nested.len(), 1);
+/// assert!(verification.all_valid());
+/// ```
+pub fn verify_nested<T: Serialize>(signed: &SignedClaim<T>) -> Result<NestedVerification> {
+    verify_nested_with_depth(signed, DEFAULT_NESTING_DEPTH)
+}
+
+/// Like [`verify_nested`], checking at most `max_depth` nested claims
+pub fn verify_nested_with_depth<T: Serialize>(
+    signed: &SignedClaim<T>,
+    max_depth: usize,
+) -> Result<NestedVerification> {
+    verify_claim(signed)?;
+    let mut verification = NestedVerification {
+        nested: Vec::new(),
+        truncated: false,
+    };
+    let mut next = nested_claim(&signed.claim);
+    while let Some(inner) = next {
+        if verification.nested.len() == max_depth {
+            verification.truncated = true;
+            break;
+        }
+        let result = verify_claim(&inner);
+        let valid = result.is_ok();
+        verification.nested.push(result);
+        if !valid {
+            break;
+        }
+        next = nested_claim(&inner.claim);
+    }
+    Ok(verification)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, SdkError};
+    use alloc::string::{String, ToString};
+    use ed25519_dalek::SigningKey;
+
+    /// A chain of `layers` claims, each nesting the previous one.
+    fn chain(layers: u64) -> SignedClaim {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut metadata: Option<String> = None;
+        let mut signed = None;
+        for layer in 0..layers {
+            let mut claim = Claim::new_with_timestamp(layer.to_string(), layer + 1);
+            claim.metadata = metadata;
+            let layer = sign_claim(&claim, &key).unwrap();
+            metadata = Some(serde_json::to_string(&layer).unwrap());
+            signed = Some(layer);
+        }
+        signed.unwrap()
+    }
+
+    #[test]
+    fn test_depth_limit() {
+        let verification = verify_nested(&chain(3)).unwrap();
+        assert_eq!(verification.nested.len(), 2);
+        assert!(!verification.truncated && verification.all_valid());
+
+        let deep = chain(DEFAULT_NESTING_DEPTH as u64 + 2);
+        let verification = verify_nested(&deep).unwrap();
+        assert_eq!(verification.nested.len(), DEFAULT_NESTING_DEPTH);
+        assert!(verification.truncated && !verification.all_valid());
+        assert!(verify_nested_with_depth(&deep, 10).unwrap().all_valid());
+        assert!(verify_nested_with_depth(&deep, 0).unwrap().truncated);
+
+        // Plain metadata is not a nested claim
+        let mut plain = Claim::new_with_timestamp("x".to_string(), 1);
+        plain.metadata = Some("note".to_string());
+        let plain = sign_claim(&plain, &SigningKey::from_bytes(&[0u8; 32])).unwrap();
+        assert!(verify_nested(&plain).unwrap().nested.is_empty());
+    }
+
+    #[test]
+    fn test_tampered_inner_claim_is_reported() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut inner = chain(2);
+        inner.claim.data = "tampered".to_string();
+        let mut outer = Claim::new_with_timestamp("outer".to_string(), 9);
+        outer.metadata = Some(serde_json::to_string(&inner).unwrap());
+        let outer = sign_claim(&outer, &key).unwrap();
+
+        let verification = verify_nested(&outer).unwrap();
+        assert_eq!(verification.nested.len(), 1);
+        assert!(matches!(
+            verification.nested[0],
+            Err(SdkError::SignatureError(_))
+        ));
+        assert!(!verification.all_valid());
+
+        let mut forged = outer;
+        forged.claim.data = "forged".to_string();
+        assert!(verify_nested(&forged).is_err());
+    }
+}
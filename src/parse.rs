@@ -0,0 +1,255 @@
+//! Hardened parsing of untrusted claim JSON.
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use core::fmt;
+use serde::de::{self, DeserializeOwned, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+
+use crate::{Result, SdkError, SignedClaim};
+
+/// Bounds applied by [`SignedClaim::from_json_limited`] before deserializing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// Maximum size of the whole JSON document in bytes
+    pub max_total_bytes: usize,
+    /// Maximum length of any single string (object keys included), in encoded bytes
+    pub max_string_len: usize,
+    /// Maximum nesting depth of objects and arrays
+    pub max_depth: usize,
+}
+
+impl Default for ParseLimits {
+    /// 64 KiB documents, 16 KiB strings and 16 levels of nesting.
+    fn default() -> Self {
+        Self {
+            max_total_bytes: 64 * 1024,
+            max_string_len: 16 * 1024,
+            max_depth: 16,
+        }
+    }
+}
+
+fn limit_error(what: &str, limit: usize) -> SdkError {
+    SdkError::SerializationError(format!("{} exceeds limit of {}", what, limit))
+}
+
+/// Single pass over the raw JSON enforcing `limits`, without building any values.
+fn check_limits(json: &str, limits: &ParseLimits) -> Result<()> {
+    if json.len() > limits.max_total_bytes {
+        return Err(limit_error("document size", limits.max_total_bytes));
+    }
+
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut string_len = 0usize;
+    for &b in json.as_bytes() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+                continue;
+            }
+            string_len += 1;
+            if string_len > limits.max_string_len {
+                return Err(limit_error("string length", limits.max_string_len));
+            }
+            continue;
+        }
+        match b {
+            b'"' => {
+                in_string = true;
+                string_len = 0;
+            }
+            b'{' | b'[' => {
+                depth += 1;
+                if depth > limits.max_depth {
+                    return Err(limit_error("nesting depth", limits.max_depth));
+                }
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Walks a JSON document without keeping it, failing on a repeated object key.
+struct UniqueKeys;
+
+impl<'de> Deserialize<'de> for UniqueKeys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(UniqueKeys)
+    }
+}
+
+impl<'de> Visitor<'de> for UniqueKeys {
+    type Value = UniqueKeys;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> core::result::Result<Self, E> {
+        Ok(UniqueKeys)
+    }
+
+    fn visit_i64<E>(self, _: i64) -> core::result::Result<Self, E> {
+        Ok(UniqueKeys)
+    }
+
+    fn visit_u64<E>(self, _: u64) -> core::result::Result<Self, E> {
+        Ok(UniqueKeys)
+    }
+
+    fn visit_f64<E>(self, _: f64) -> core::result::Result<Self, E> {
+        Ok(UniqueKeys)
+    }
+
+    fn visit_str<E>(self, _: &str) -> core::result::Result<Self, E> {
+        Ok(UniqueKeys)
+    }
+
+    fn visit_unit<E>(self) -> core::result::Result<Self, E> {
+        Ok(UniqueKeys)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> core::result::Result<Self, A::Error> {
+        while seq.next_element::<UniqueKeys>()?.is_some() {}
+        Ok(UniqueKeys)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> core::result::Result<Self, A::Error> {
+        let mut seen = BTreeSet::new();
+        // Keys are compared after unescaping, so `"d\u0061ta"` repeats `"data"`
+        while let Some(key) = map.next_key::<String>()? {
+            if !seen.insert(key.clone()) {
+                return Err(de::Error::custom(format!("duplicate key '{}'", key)));
+            }
+            map.next_value::<UniqueKeys>()?;
+        }
+        Ok(UniqueKeys)
+    }
+}
+
+/// Reject JSON containing an object with the same key twice, at any depth
+///
+/// Parsers disagree on which duplicate wins, so such input could be read as
+/// different claims by different verifiers. RFC 8785 requires canonicalized
+/// input to be free of duplicates.
+///
+/// # Example
+/// ```
+/// use provn_sdk::reject_duplicate_keys;
+/// assert!(reject_duplicate_keys(br#"{"data":"x","timestamp":1}"#).is_ok());
+/// assert!(reject_duplicate_keys(br#"{"data":"x","data":"y"}"#).is_err());
+/// ```
+pub fn reject_duplicate_keys(json: &[u8]) -> Result<()> {
+    serde_json::from_slice::<UniqueKeys>(json)?;
+    Ok(())
+}
+
+/// `serde_json::from_slice` that first rejects duplicate object keys.
+pub(crate) fn from_slice_unique<T: DeserializeOwned>(json: &[u8]) -> Result<T> {
+    reject_duplicate_keys(json)?;
+    Ok(serde_json::from_slice(json)?)
+}
+
+impl<T: DeserializeOwned> SignedClaim<T> {
+    /// Deserialize an untrusted `SignedClaim` with bounded resource usage
+    ///
+    /// The document size, string lengths and nesting depth are checked in a
+    /// single pass before any allocation-heavy deserialization happens, and
+    /// duplicate object keys are rejected (see [`reject_duplicate_keys`]).
+    /// Violations return [`SdkError::SerializationError`].
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{ParseLimits, SignedClaim};
+    /// let json = r#"{"claim":{"data":"x","timestamp":1},"public_key":"00","signature":"00"}"#;
+    /// let limits = ParseLimits { max_string_len: 4, ..ParseLimits::default() };
+    /// assert!(SignedClaim::<String>::from_json_limited(json, limits).is_err());
+    /// ```
+    pub fn from_json_limited(json: &str, limits: ParseLimits) -> Result<Self> {
+        check_limits(json, &limits)?;
+        from_slice_unique(json.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, verify_claim, Claim};
+    use alloc::string::{String, ToString};
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_limits_enforced() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("data".to_string(), 1), &key).unwrap();
+        let json = serde_json::to_string(&signed).unwrap();
+
+        let parsed =
+            SignedClaim::<String>::from_json_limited(&json, ParseLimits::default()).unwrap();
+        assert!(verify_claim(&parsed).unwrap());
+
+        let tight = |limits: ParseLimits| SignedClaim::<String>::from_json_limited(&json, limits);
+        let defaults = ParseLimits::default();
+        assert!(tight(ParseLimits {
+            max_total_bytes: json.len() - 1,
+            ..defaults
+        })
+        .is_err());
+        // The 128-char signature is the longest string
+        assert!(tight(ParseLimits {
+            max_string_len: 127,
+            ..defaults
+        })
+        .is_err());
+        assert!(tight(ParseLimits {
+            max_string_len: 128,
+            ..defaults
+        })
+        .is_ok());
+        assert!(tight(ParseLimits {
+            max_depth: 1,
+            ..defaults
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_escaped_quotes_do_not_end_strings() {
+        let deep =
+            r#"{"claim":{"data":"\"}}}}[[[[","timestamp":1},"public_key":"","signature":""}"#;
+        let limits = ParseLimits {
+            max_depth: 2,
+            ..ParseLimits::default()
+        };
+        assert!(check_limits(deep, &limits).is_ok());
+        assert!(check_limits("[[[", &limits).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_keys_rejected() {
+        let dup =
+            r#"{"claim":{"data":"x","data":"y","timestamp":1},"public_key":"","signature":""}"#;
+        assert!(matches!(
+            SignedClaim::<String>::from_json_limited(dup, ParseLimits::default()),
+            Err(SdkError::SerializationError(_))
+        ));
+
+        // Nested inside arrays, and spelled differently through escapes
+        let nested = br#"{"data":[{"a":1},{"b":1,"\u0062":2}],"timestamp":1}"#;
+        match reject_duplicate_keys(nested) {
+            Err(SdkError::SerializationError(msg)) => assert!(msg.contains("'b'"), "{}", msg),
+            other => panic!("expected duplicate key error, got {:?}", other),
+        }
+        assert!(reject_duplicate_keys(br#"[{"a":1},{"a":1}]"#).is_ok());
+    }
+}
@@ -0,0 +1,367 @@
+//! Policy-driven verification.
+//!
+//! [`verify_with_policy`] combines the signature check with the optional
+//! checks that used to need separate `verify_claim_*` variants. The default
+//! [`VerifyPolicy`] only checks the signature, exactly like [`verify_claim`].
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::Serialize;
+
+use crate::{
+    decode_public_key, decode_public_key_bytes, is_weak_key, verify_claim, verify_claim_strict,
+    Result, SdkError, SignedClaim,
+};
+
+/// The checks applied by [`verify_with_policy`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifyPolicy {
+    /// If set, only these public keys (Hex encoded) are accepted
+    pub allowed_keys: Option<Vec<String>>,
+    /// If set, reject claims timestamped more than this many seconds before
+    /// `now`, whatever their `expires_at`
+    pub max_age: Option<u64>,
+    /// If set, reject claims timestamped more than this many seconds after `now`
+    pub max_clock_skew: Option<u64>,
+    /// Current time (UTC seconds) for time-based checks; defaults to the
+    /// system clock when the "std" feature is enabled
+    pub now: Option<u64>,
+    /// Reject claims whose `expires_at` is at or before `now`
+    pub require_not_expired: bool,
+    /// Reject keys classified as weak by [`is_weak_key`](crate::is_weak_key)
+    pub reject_weak_keys: bool,
+    /// Use Ed25519 strict verification, rejecting malleable signatures and
+    /// small-order keys
+    pub strict: bool,
+}
+
+/// Result of [`verify_with_policy`].
+#[derive(Debug)]
+pub enum VerifyOutcome {
+    /// The signature is valid and every policy check passed
+    Valid,
+    /// The signer is not in [`VerifyPolicy::allowed_keys`]
+    KeyNotAllowed,
+    /// The signer's key is weak and [`VerifyPolicy::reject_weak_keys`] is set
+    WeakKey,
+    /// The claim expired at `expires_at`
+    Expired {
+        /// The claim's expiry (UTC seconds)
+        expires_at: u64,
+    },
+    /// The claim is older than [`VerifyPolicy::max_age`]
+    TooOld {
+        /// Seconds between the claim's timestamp and `now`
+        age: u64,
+        /// The allowed age
+        max_age: u64,
+    },
+    /// The claim's timestamp is further in the future than the allowed skew
+    FromFuture {
+        /// The claim's timestamp (UTC seconds)
+        timestamp: u64,
+    },
+    /// The claim could not be decoded, the signature did not verify, or the
+    /// current time was needed but unknown
+    Invalid(SdkError),
+}
+
+impl VerifyOutcome {
+    /// Whether the claim was accepted
+    pub fn is_valid(&self) -> bool {
+        matches!(self, VerifyOutcome::Valid)
+    }
+
+    /// Convert into the `Result<bool>` convention of [`verify_claim`]
+    pub fn into_result(self) -> Result<bool> {
+        match self {
+            VerifyOutcome::Valid => Ok(true),
+            VerifyOutcome::KeyNotAllowed => Err(SdkError::PolicyError(
+                "public key is not in the allowed set".into(),
+            )),
+            VerifyOutcome::WeakKey => Err(SdkError::KeyError("Weak Public Key Rejected".into())),
+            VerifyOutcome::Expired { expires_at } => Err(SdkError::PolicyError(format!(
+                "claim expired at {}",
+                expires_at
+            ))),
+            VerifyOutcome::TooOld { age, max_age } => Err(SdkError::PolicyError(format!(
+                "claim is {} seconds old, more than the allowed {}",
+                age, max_age
+            ))),
+            VerifyOutcome::FromFuture { timestamp } => Err(SdkError::PolicyError(format!(
+                "claim timestamp {} is beyond the allowed clock skew",
+                timestamp
+            ))),
+            VerifyOutcome::Invalid(e) => Err(e),
+        }
+    }
+}
+
+fn current_time(policy: &VerifyPolicy) -> Result<u64> {
+    if let Some(now) = policy.now {
+        return Ok(now);
+    }
+    #[cfg(feature = "std")]
+    {
+        Ok(std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs())
+    }
+    #[cfg(not(feature = "std"))]
+    Err(SdkError::PolicyError(
+        "VerifyPolicy::now is required without the std feature".into(),
+    ))
+}
+
+/// Verify a signed claim against a [`VerifyPolicy`]
+///
+/// Key checks run first, then the signature, then time-based checks.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, VerifyOutcome, VerifyPolicy, sign_claim, verify_with_policy, generate_keypair};
+/// let mut claim = Claim::new_with_timestamp("ticket".to_string(), 1000);
+/// claim.expires_at = Some(2000);
+/// let signed = sign_claim(&claim, &generate_keypair()).unwrap();
+///
+/// assert!(verify_with_policy(&signed, &VerifyPolicy::default()).is_valid());
+///
+/// let policy = VerifyPolicy { now: Some(2500), require_not_expired: true, ..Default::default() };
+/// assert!(matches!(
+///     verify_with_policy(&signed, &policy),
+///     VerifyOutcome::Expired { expires_at: 2000 }
+/// ));
+/// ```
+pub fn verify_with_policy<T: Serialize>(
+    signed: &SignedClaim<T>,
+    policy: &VerifyPolicy,
+) -> VerifyOutcome {
+    match check_policy(signed, policy) {
+        Ok(outcome) => outcome,
+        Err(e) => VerifyOutcome::Invalid(e),
+    }
+}
+
+fn check_policy<T: Serialize>(
+    signed: &SignedClaim<T>,
+    policy: &VerifyPolicy,
+) -> Result<VerifyOutcome> {
+    if let Some(allowed) = &policy.allowed_keys {
+        let signer = decode_public_key_bytes(&signed.public_key)?;
+        if !allowed
+            .iter()
+            .any(|key| decode_public_key_bytes(key).is_ok_and(|key| key == signer))
+        {
+            return Ok(VerifyOutcome::KeyNotAllowed);
+        }
+    }
+    if policy.reject_weak_keys && is_weak_key(&decode_public_key(&signed.public_key)?) {
+        return Ok(VerifyOutcome::WeakKey);
+    }
+
+    if policy.strict {
+        verify_claim_strict(signed)?;
+    } else {
+        verify_claim(signed)?;
+    }
+
+    let claim = &signed.claim;
+    if policy.require_not_expired {
+        if let Some(expires_at) = claim.expires_at {
+            if current_time(policy)? >= expires_at {
+                return Ok(VerifyOutcome::Expired { expires_at });
+            }
+        }
+    }
+    if let Some(max_age) = policy.max_age {
+        let age = current_time(policy)?.saturating_sub(claim.timestamp);
+        if age > max_age {
+            return Ok(VerifyOutcome::TooOld { age, max_age });
+        }
+    }
+    if let Some(skew) = policy.max_clock_skew {
+        if claim.timestamp > current_time(policy)?.saturating_add(skew) {
+            return Ok(VerifyOutcome::FromFuture {
+                timestamp: claim.timestamp,
+            });
+        }
+    }
+    Ok(VerifyOutcome::Valid)
+}
+
+/// Remaining validity reported by [`verify_and_remaining`] for claims
+/// without an `expires_at`.
+pub const NO_EXPIRY: u64 = u64::MAX;
+
+/// Verify a signed claim and return how many seconds it stays valid after `now`
+///
+/// Claims without an `expires_at` return [`NO_EXPIRY`]. A claim is expired
+/// once `now >= expires_at`, as with [`VerifyPolicy::require_not_expired`],
+/// so a successful result is never 0.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, NO_EXPIRY, sign_claim, verify_and_remaining, generate_keypair};
+/// let key = generate_keypair();
+/// let mut claim = Claim::new_with_timestamp("session".to_string(), 1000);
+/// claim.expires_at = Some(4600);
+/// let signed = sign_claim(&claim, &key).unwrap();
+/// assert_eq!(verify_and_remaining(&signed, 1000).unwrap(), 3600);
+/// assert!(verify_and_remaining(&signed, 4600).is_err());
+///
+/// let forever = sign_claim(&Claim::new_with_timestamp("root".to_string(), 1000), &key).unwrap();
+/// assert_eq!(verify_and_remaining(&forever, 1000).unwrap(), NO_EXPIRY);
+/// ```
+pub fn verify_and_remaining<T: Serialize>(signed: &SignedClaim<T>, now: u64) -> Result<u64> {
+    let policy = VerifyPolicy {
+        now: Some(now),
+        require_not_expired: true,
+        ..Default::default()
+    };
+    verify_with_policy(signed, &policy).into_result()?;
+    Ok(signed
+        .claim
+        .expires_at
+        .map_or(NO_EXPIRY, |expires_at| expires_at - now))
+}
+
+/// Verify a signed claim and reject it if it is more than `max_age_secs`
+/// seconds older than `now`
+///
+/// This is a freshness check made by the verifier, independent of the
+/// claim's own `expires_at`. Claims timestamped after `now` count as age 0;
+/// bound those with [`VerifyPolicy::max_clock_skew`].
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_claim_max_age, generate_keypair};
+/// let signed = sign_claim(&Claim::new_with_timestamp("nonce 42".to_string(), 1000), &generate_keypair()).unwrap();
+/// assert!(verify_claim_max_age(&signed, 1030, 30).unwrap());
+/// let err = verify_claim_max_age(&signed, 1031, 30).unwrap_err();
+/// assert!(err.to_string().contains("31 seconds old"));
+/// ```
+pub fn verify_claim_max_age<T: Serialize>(
+    signed: &SignedClaim<T>,
+    now: u64,
+    max_age_secs: u64,
+) -> Result<bool> {
+    let policy = VerifyPolicy {
+        max_age: Some(max_age_secs),
+        now: Some(now),
+        ..Default::default()
+    };
+    verify_with_policy(signed, &policy).into_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+    use alloc::string::ToString;
+    use alloc::vec;
+    use ed25519_dalek::SigningKey;
+
+    fn signed(timestamp: u64, expires_at: Option<u64>) -> SignedClaim {
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let mut claim = Claim::new_with_timestamp("p".to_string(), timestamp);
+        claim.expires_at = expires_at;
+        sign_claim(&claim, &key).unwrap()
+    }
+
+    #[test]
+    fn test_default_policy_is_crypto_only() {
+        let claim = signed(u64::from(u32::MAX), Some(1));
+        assert!(verify_with_policy(&claim, &VerifyPolicy::default()).is_valid());
+
+        let mut tampered = claim.clone();
+        tampered.claim.data = "q".to_string();
+        assert!(matches!(
+            verify_with_policy(&tampered, &VerifyPolicy::default()),
+            VerifyOutcome::Invalid(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_policy_checks() {
+        let claim = signed(1000, Some(2000));
+        let at = |now| VerifyPolicy {
+            now: Some(now),
+            require_not_expired: true,
+            max_clock_skew: Some(60),
+            strict: true,
+            ..Default::default()
+        };
+        assert!(verify_with_policy(&claim, &at(1999)).is_valid());
+        assert!(matches!(
+            verify_with_policy(&claim, &at(2000)),
+            VerifyOutcome::Expired { expires_at: 2000 }
+        ));
+        assert!(matches!(
+            verify_with_policy(&claim, &at(939)),
+            VerifyOutcome::FromFuture { timestamp: 1000 }
+        ));
+
+        let allowed = VerifyPolicy {
+            allowed_keys: Some(vec![claim.public_key.to_uppercase()]),
+            ..Default::default()
+        };
+        assert!(verify_with_policy(&claim, &allowed).is_valid());
+        let other = VerifyPolicy {
+            allowed_keys: Some(vec!["00".repeat(32)]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            verify_with_policy(&claim, &other).into_result(),
+            Err(SdkError::PolicyError(_))
+        ));
+    }
+
+    #[test]
+    fn test_remaining_validity() {
+        let claim = signed(1000, Some(2000));
+        assert_eq!(verify_and_remaining(&claim, 1999).unwrap(), 1);
+        assert!(matches!(
+            verify_and_remaining(&claim, 2000),
+            Err(SdkError::PolicyError(_))
+        ));
+        assert_eq!(
+            verify_and_remaining(&signed(1000, None), 0).unwrap(),
+            NO_EXPIRY
+        );
+
+        let mut tampered = claim;
+        tampered.claim.expires_at = Some(3000);
+        assert!(matches!(
+            verify_and_remaining(&tampered, 1000),
+            Err(SdkError::SignatureError(_))
+        ));
+    }
+
+    #[test]
+    fn test_max_age() {
+        // The claim's own expiry does not extend the allowed age
+        let claim = signed(1000, Some(5000));
+        assert!(verify_claim_max_age(&claim, 1060, 60).unwrap());
+        assert!(verify_claim_max_age(&claim, 900, 0).unwrap());
+        assert!(matches!(
+            verify_with_policy(
+                &claim,
+                &VerifyPolicy {
+                    max_age: Some(60),
+                    now: Some(1061),
+                    ..Default::default()
+                }
+            ),
+            VerifyOutcome::TooOld {
+                age: 61,
+                max_age: 60
+            }
+        ));
+        assert!(matches!(
+            verify_claim_max_age(&claim, 1061, 60),
+            Err(SdkError::PolicyError(_))
+        ));
+    }
+}
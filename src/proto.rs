@@ -0,0 +1,188 @@
+//! Protocol Buffers encoding of signed claims.
+//!
+//! [`ProtoSignedClaim`] is the Rust form of this message, encoded with the
+//! standard protobuf wire format, so any generated client can read it:
+//!
+//! ```proto
+//! syntax = "proto3";
+//! package provn.v1;
+//!
+//! message FieldList {
+//!   repeated string fields = 1;
+//! }
+//!
+//! message SignedClaim {
+//!   bytes claim = 1;                // canonical (RFC 8785) JSON of the claim
+//!   bytes public_key = 2;           // raw Ed25519 public key, 32 bytes
+//!   bytes signature = 3;            // raw Ed25519 signature, 64 bytes
+//!   FieldList signed_fields = 4;    // see SignedClaim::signed_fields
+//!   optional string metadata_salt = 5;
+//!   optional string domain = 6;
+//! }
+//! ```
+//!
+//! The claim travels as its canonical bytes, which are exactly what the
+//! signature covers, so a decoded claim always verifies like the original.
+//! Keys and signatures come back hex encoded. The messages implement
+//! [`prost::Message`], so `encode_to_vec` and `decode` come from `prost`.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::{parse, Result, SdkError, SignedClaim, PUBLIC_KEY_BYTES, SIGNATURE_BYTES};
+
+/// The protobuf `provn.v1.SignedClaim` message.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ProtoSignedClaim {
+    /// Canonical JSON of the claim
+    #[prost(bytes = "vec", tag = "1")]
+    pub claim: Vec<u8>,
+    /// Raw public key of the signer
+    #[prost(bytes = "vec", tag = "2")]
+    pub public_key: Vec<u8>,
+    /// Raw signature
+    #[prost(bytes = "vec", tag = "3")]
+    pub signature: Vec<u8>,
+    /// Claim fields covered by the signature, if not all of them
+    #[prost(message, optional, tag = "4")]
+    pub signed_fields: Option<ProtoFieldList>,
+    /// Salt of the metadata commitment (Hex encoded)
+    #[prost(string, optional, tag = "5")]
+    pub metadata_salt: Option<String>,
+    /// Signing domain
+    #[prost(string, optional, tag = "6")]
+    pub domain: Option<String>,
+}
+
+/// The protobuf `provn.v1.FieldList` message.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ProtoFieldList {
+    /// Names of the signed claim fields
+    #[prost(string, repeated, tag = "1")]
+    pub fields: Vec<String>,
+}
+
+impl<T: Serialize> SignedClaim<T> {
+    /// Convert to the protobuf message (requires the "prost" feature)
+    ///
+    /// # Example
+    /// ```
+    /// use prost::Message;
+    /// use provn_sdk::{Claim, ProtoSignedClaim, SignedClaim, sign_claim, verify_claim, generate_keypair};
+    /// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &generate_keypair()).unwrap();
+    /// let wire = signed.to_proto().unwrap().encode_to_vec();
+    /// let decoded: SignedClaim = SignedClaim::from_proto(ProtoSignedClaim::decode(&wire[..]).unwrap()).unwrap();
+    /// assert!(verify_claim(&decoded).unwrap());
+    /// ```
+    pub fn to_proto(&self) -> Result<ProtoSignedClaim> {
+        Ok(ProtoSignedClaim {
+            claim: self.claim.to_signable_bytes()?,
+            public_key: self.public_key_bytes()?.to_vec(),
+            signature: self.signature_bytes()?.to_vec(),
+            signed_fields: self
+                .signed_fields
+                .clone()
+                .map(|fields| ProtoFieldList { fields }),
+            metadata_salt: self.metadata_salt.clone(),
+            domain: self.domain.clone(),
+        })
+    }
+}
+
+impl<T: DeserializeOwned> SignedClaim<T> {
+    /// Convert from the protobuf message (requires the "prost" feature)
+    ///
+    /// Fails if the key or signature has the wrong length or the claim is not
+    /// valid JSON; the signature itself is not checked.
+    pub fn from_proto(proto: ProtoSignedClaim) -> Result<Self> {
+        if proto.public_key.len() != PUBLIC_KEY_BYTES || proto.signature.len() != SIGNATURE_BYTES {
+            return Err(SdkError::KeyError(format!(
+                "protobuf key and signature must be {} and {} bytes, got {} and {}",
+                PUBLIC_KEY_BYTES,
+                SIGNATURE_BYTES,
+                proto.public_key.len(),
+                proto.signature.len()
+            )));
+        }
+        Ok(SignedClaim {
+            claim: parse::from_slice_unique(&proto.claim)?,
+            public_key: hex::encode(&proto.public_key),
+            signature: hex::encode(&proto.signature),
+            signed_fields: proto.signed_fields.map(|list| list.fields),
+            metadata_salt: proto.metadata_salt,
+            domain: proto.domain,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        sign_claim, sign_claim_fields, sign_claim_with_domain, verify_claim_fields, Claim,
+    };
+    use alloc::string::ToString;
+    use alloc::vec;
+    use ed25519_dalek::SigningKey;
+    use prost::Message;
+
+    #[test]
+    fn test_wire_format() {
+        let message = ProtoSignedClaim {
+            claim: b"{}".to_vec(),
+            public_key: vec![1; 32],
+            signature: vec![2; 64],
+            signed_fields: Some(ProtoFieldList {
+                fields: vec!["data".to_string()],
+            }),
+            metadata_salt: None,
+            domain: Some("d".to_string()),
+        };
+        let wire = message.encode_to_vec();
+        let mut expected = vec![0x0a, 2, b'{', b'}', 0x12, 32];
+        expected.extend_from_slice(&[1; 32]);
+        expected.extend_from_slice(&[0x1a, 64]);
+        expected.extend_from_slice(&[2; 64]);
+        expected.extend_from_slice(&[0x22, 6, 0x0a, 4, b'd', b'a', b't', b'a', 0x32, 1, b'd']);
+        assert_eq!(wire, expected);
+
+        // Unknown fields of every wire type are skipped
+        let mut extended = wire.clone();
+        extended.extend_from_slice(&[
+            0x38, 0x96, 0x01, 0x41, 0, 0, 0, 0, 0, 0, 0, 0, 0x4d, 0, 0, 0, 0,
+        ]);
+        assert_eq!(ProtoSignedClaim::decode(&extended[..]).unwrap(), message);
+        assert!(ProtoSignedClaim::decode(&wire[..wire.len() - 1]).is_err());
+        assert!(ProtoSignedClaim::decode(&[0x0b][..]).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip_preserves_verifiability() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("x".to_string(), 1);
+        for signed in [
+            sign_claim(&claim, &key).unwrap(),
+            sign_claim_fields(&claim, &key, &["data"]).unwrap(),
+            sign_claim_with_domain(&claim, &key, "provn-v1").unwrap(),
+        ] {
+            let proto = signed.to_proto().unwrap();
+            assert_eq!(proto.public_key, key.verifying_key().to_bytes());
+            let decoded: SignedClaim = SignedClaim::from_proto(
+                ProtoSignedClaim::decode(&proto.encode_to_vec()[..]).unwrap(),
+            )
+            .unwrap();
+            assert_eq!(decoded, signed);
+            assert!(verify_claim_fields(&decoded).unwrap());
+        }
+
+        let mut short = sign_claim(&claim, &key).unwrap().to_proto().unwrap();
+        short.signature.pop();
+        assert!(matches!(
+            SignedClaim::<String>::from_proto(short),
+            Err(SdkError::KeyError(_))
+        ));
+    }
+}
@@ -0,0 +1,191 @@
+//! Redactable metadata.
+//!
+//! A claim signed with [`sign_claim_redactable`] commits to its `metadata`
+//! through a salted hash instead of the value:
+//!
+//! - a fresh random 16-byte salt is stored in [`SignedClaim::metadata_salt`];
+//! - `metadata_hash = SHA-256(salt || metadata)`, Hex encoded;
+//! - the signed bytes are the canonical claim with `metadata` removed and
+//!   `metadata_hash` in its place.
+//!
+//! [`redact_metadata`] drops the metadata and the salt and stores the hash in
+//! [`Claim::metadata_hash`], so the redacted claim's ordinary canonical bytes
+//! are exactly what was signed. It verifies with [`verify_claim`](crate::verify_claim)
+//! like any other claim, while the salt keeps low-entropy metadata from being
+//! guessed from the hash.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use ed25519_dalek::{Signer, SigningKey};
+use rand::{CryptoRng, RngCore};
+use serde::Serialize;
+
+use crate::{compute_hash_multi, public_key_hex, Claim, HashAlg, Result, SdkError, SignedClaim};
+
+/// Length of the salt committed with redactable metadata, in bytes.
+pub const METADATA_SALT_BYTES: usize = 16;
+
+fn metadata_hash(salt: &str, metadata: &str) -> Result<String> {
+    let salt = crate::decode_hex(salt)
+        .map_err(|e| SdkError::SerializationError(format!("Invalid Hex Metadata Salt: {}", e)))?;
+    if salt.len() != METADATA_SALT_BYTES {
+        return Err(SdkError::SerializationError(format!(
+            "metadata salt must be {} bytes, got {}",
+            METADATA_SALT_BYTES,
+            salt.len()
+        )));
+    }
+    Ok(compute_hash_multi(
+        &[&salt, metadata.as_bytes()],
+        HashAlg::Sha256,
+    ))
+}
+
+/// The claim as signed under a metadata commitment: `metadata` replaced by
+/// its salted `metadata_hash`.
+pub(crate) fn committed_value<T: Serialize>(
+    claim: &Claim<T>,
+    salt: &str,
+) -> Result<serde_json::Value> {
+    let (Some(metadata), None) = (&claim.metadata, &claim.metadata_hash) else {
+        return Err(SdkError::SerializationError(
+            "a metadata salt requires metadata and no metadata_hash".to_string(),
+        ));
+    };
+    let hash = metadata_hash(salt, metadata)?;
+    let mut value = claim.to_canonical_value()?;
+    let object = value
+        .as_object_mut()
+        .ok_or_else(|| SdkError::SerializationError("claim is not a JSON object".to_string()))?;
+    object.remove("metadata");
+    object.insert("metadata_hash".to_string(), hash.into());
+    Ok(value)
+}
+
+/// Sign a claim so that its metadata can later be removed with [`redact_metadata`]
+///
+/// Fails if the claim has no metadata or already carries a `metadata_hash`.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, redact_metadata, sign_claim_redactable, verify_claim, generate_keypair};
+/// let mut claim = Claim::new_with_timestamp("Shipment received".to_string(), 1);
+/// claim.metadata = Some("signed for by J. Doe".to_string());
+/// let signed = sign_claim_redactable(&claim, &generate_keypair(), &mut rand::rngs::OsRng).unwrap();
+/// assert!(verify_claim(&signed).unwrap());
+///
+/// let redacted = redact_metadata(&signed).unwrap();
+/// assert!(redacted.claim.metadata.is_none());
+/// assert!(verify_claim(&redacted).unwrap());
+/// ```
+pub fn sign_claim_redactable<T, R>(
+    claim: &Claim<T>,
+    key: &SigningKey,
+    rng: &mut R,
+) -> Result<SignedClaim<T>>
+where
+    T: Serialize + Clone,
+    R: RngCore + CryptoRng,
+{
+    let mut salt = [0u8; METADATA_SALT_BYTES];
+    rng.fill_bytes(&mut salt);
+    let salt = hex::encode(salt);
+    let message = crate::canonicalize(&committed_value(claim, &salt)?)?;
+    let signature = key.sign(&message);
+
+    Ok(SignedClaim {
+        claim: claim.clone(),
+        metadata_salt: Some(salt),
+        domain: None,
+        public_key: public_key_hex(key),
+        signature: hex::encode(signature.to_bytes()),
+        signed_fields: None,
+    })
+}
+
+/// Remove the metadata of a claim signed with [`sign_claim_redactable`]
+///
+/// The signature is kept as is; the result carries the salted
+/// `metadata_hash` instead of the metadata and still verifies. Fails if the
+/// claim has no metadata salt or no metadata.
+pub fn redact_metadata<T: Serialize + Clone>(signed: &SignedClaim<T>) -> Result<SignedClaim<T>> {
+    let salt = signed.metadata_salt.as_deref().ok_or_else(|| {
+        SdkError::SerializationError("claim was not signed with redactable metadata".to_string())
+    })?;
+    let metadata = signed.claim.metadata.as_deref().ok_or_else(|| {
+        SdkError::SerializationError("claim has no metadata to redact".to_string())
+    })?;
+    let mut redacted = signed.clone();
+    redacted.claim.metadata_hash = Some(metadata_hash(salt, metadata)?);
+    redacted.claim.metadata = None;
+    redacted.metadata_salt = None;
+    Ok(redacted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, verify_claim};
+    use rand::rngs::OsRng;
+
+    fn claim() -> Claim {
+        let mut claim = Claim::new_with_timestamp("core".to_string(), 1);
+        claim.metadata = Some("secret".to_string());
+        claim
+    }
+
+    #[test]
+    fn test_commitment_scheme() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim_redactable(&claim(), &key, &mut OsRng).unwrap();
+        let salt = signed.metadata_salt.clone().unwrap();
+        assert_eq!(salt.len(), 2 * METADATA_SALT_BYTES);
+
+        let redacted = redact_metadata(&signed).unwrap();
+        let expected_hash =
+            compute_hash_multi(&[&hex::decode(&salt).unwrap(), b"secret"], HashAlg::Sha256);
+        assert_eq!(
+            redacted.claim.to_signable_bytes().unwrap(),
+            format!(
+                r#"{{"data":"core","metadata_hash":"{}","timestamp":1}}"#,
+                expected_hash
+            )
+            .into_bytes()
+        );
+        assert_eq!(
+            redacted.signed_message().unwrap(),
+            signed.signed_message().unwrap()
+        );
+        assert!(verify_claim(&redacted).unwrap());
+        assert!(!serde_json::to_string(&redacted).unwrap().contains("secret"));
+    }
+
+    #[test]
+    fn test_tampering_and_misuse() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim_redactable(&claim(), &key, &mut OsRng).unwrap();
+
+        let mut altered = signed.clone();
+        altered.claim.metadata = Some("public".to_string());
+        assert!(verify_claim(&altered).is_err());
+        let mut forged = redact_metadata(&signed).unwrap();
+        forged.claim.metadata_hash = Some("00".repeat(32));
+        assert!(verify_claim(&forged).is_err());
+
+        // Ordinary claims and claims without metadata cannot be redacted
+        assert!(redact_metadata(&sign_claim(&claim(), &key).unwrap()).is_err());
+        let mut stripped = signed.clone();
+        stripped.claim.metadata = None;
+        assert!(redact_metadata(&stripped).is_err());
+
+        // The salt must have exactly METADATA_SALT_BYTES bytes
+        for salt in ["", "00", &"00".repeat(METADATA_SALT_BYTES + 1)] {
+            let mut resalted = signed.clone();
+            resalted.metadata_salt = Some(salt.to_string());
+            assert!(redact_metadata(&resalted).is_err());
+            assert!(verify_claim(&resalted).is_err());
+        }
+        let bare = Claim::new_with_timestamp("core".to_string(), 1);
+        assert!(sign_claim_redactable(&bare, &key, &mut OsRng).is_err());
+    }
+}
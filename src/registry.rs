@@ -0,0 +1,123 @@
+//! Attributing verified claims to organizations.
+//!
+//! Cryptographic verification says which key signed a claim; a
+//! [`KeyRegistry`] says who that key belongs to. Keeping the two apart lets
+//! the registry live in a database, a directory service or a config file.
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use ed25519_dalek::VerifyingKey;
+use serde::Serialize;
+
+use crate::{decode_public_key, verify_claim, Result, SignedClaim};
+
+/// Maps public keys to the organization that owns them.
+pub trait KeyRegistry {
+    /// The organization owning `pk`, if it is registered
+    fn lookup(&self, pk: &VerifyingKey) -> Option<String>;
+}
+
+/// An in-memory [`KeyRegistry`], mainly for tests and small deployments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryRegistry {
+    orgs: BTreeMap<[u8; 32], String>,
+}
+
+impl MemoryRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `pk` as owned by `org`, returning the previous owner if any
+    pub fn insert(&mut self, pk: &VerifyingKey, org: impl Into<String>) -> Option<String> {
+        self.orgs.insert(pk.to_bytes(), org.into())
+    }
+
+    /// Remove `pk` from the registry, returning its owner if it was registered
+    pub fn remove(&mut self, pk: &VerifyingKey) -> Option<String> {
+        self.orgs.remove(pk.as_bytes())
+    }
+
+    /// Number of registered keys
+    pub fn len(&self) -> usize {
+        self.orgs.len()
+    }
+
+    /// Whether no keys are registered
+    pub fn is_empty(&self) -> bool {
+        self.orgs.is_empty()
+    }
+}
+
+impl KeyRegistry for MemoryRegistry {
+    fn lookup(&self, pk: &VerifyingKey) -> Option<String> {
+        self.orgs.get(pk.as_bytes()).cloned()
+    }
+}
+
+/// Verify a signed claim and look up the organization owning its key
+///
+/// Fails with the same errors as [`verify_claim`] if the claim is invalid, so
+/// an attribution is only ever returned for a verified claim. An unregistered
+/// signer yields `Ok((true, None))`.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, MemoryRegistry, sign_claim, verify_and_attribute, generate_keypair};
+/// let key = generate_keypair();
+/// let mut registry = MemoryRegistry::new();
+/// registry.insert(&key.verifying_key(), "Acme Corp");
+/// let signed = sign_claim(&Claim::new("Test Claim".to_string()), &key).unwrap();
+/// assert_eq!(
+///     verify_and_attribute(&signed, &registry).unwrap(),
+///     (true, Some("Acme Corp".to_string()))
+/// );
+/// ```
+pub fn verify_and_attribute<T: Serialize, R: KeyRegistry + ?Sized>(
+    signed_claim: &SignedClaim<T>,
+    registry: &R,
+) -> Result<(bool, Option<String>)> {
+    let valid = verify_claim(signed_claim)?;
+    let pk = decode_public_key(&signed_claim.public_key)?;
+    Ok((valid, registry.lookup(&pk)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+    use alloc::string::ToString;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_attribution_requires_a_valid_signature() {
+        let acme = SigningKey::from_bytes(&[0u8; 32]);
+        let stranger = SigningKey::from_bytes(&[1u8; 32]);
+        let mut registry = MemoryRegistry::new();
+        assert!(registry.insert(&acme.verifying_key(), "Acme").is_none());
+        assert_eq!(registry.len(), 1);
+
+        let claim = Claim::new_with_timestamp("r".to_string(), 1);
+        let signed = sign_claim(&claim, &acme).unwrap();
+        assert_eq!(
+            verify_and_attribute(&signed, &registry).unwrap(),
+            (true, Some("Acme".to_string()))
+        );
+        let unknown = sign_claim(&claim, &stranger).unwrap();
+        assert_eq!(
+            verify_and_attribute(&unknown, &registry).unwrap(),
+            (true, None)
+        );
+
+        let mut forged = unknown.clone();
+        forged.public_key = signed.public_key.clone();
+        assert!(verify_and_attribute(&forged, &registry).is_err());
+
+        assert_eq!(
+            registry.remove(&acme.verifying_key()).as_deref(),
+            Some("Acme")
+        );
+        assert!(registry.is_empty());
+    }
+}
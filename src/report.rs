@@ -0,0 +1,261 @@
+//! Human- and script-friendly verification summaries.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use ed25519_dalek::{Signer, SigningKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    claim_hash, compute_hash, decode_public_key, is_weak_key, to_did_key, verify_claim, Claim,
+    Result, SdkError, SignedClaim,
+};
+
+/// Length of [`VerificationReport::signer_fingerprint`] in hex characters.
+const FINGERPRINT_HEX_LEN: usize = 16;
+
+/// Everything a `provn verify` style command needs to print about a claim.
+///
+/// Serializes to JSON for scripting; fields that could not be derived (for
+/// example the `did:key` of an undecodable public key) are `null`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// SHA-256 of the claim's canonical bytes (Hex encoded)
+    pub claim_hash: Option<String>,
+    /// `did:key` of the signer
+    pub did_key: Option<String>,
+    /// Why verification failed, if it did
+    pub error: Option<String>,
+    /// First 16 hex characters of the SHA-256 of the raw public key
+    pub signer_fingerprint: Option<String>,
+    /// Claim timestamp (UTC seconds)
+    pub timestamp: u64,
+    /// Claim timestamp as RFC 3339, e.g. `2023-11-14T22:13:20Z`
+    pub timestamp_utc: String,
+    /// Whether the signature is valid
+    pub valid: bool,
+    /// Non-fatal findings, such as a weak key, expiry or a timestamp in the future
+    pub warnings: Vec<String>,
+}
+
+/// Format UTC seconds as an RFC 3339 timestamp.
+fn format_utc(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let secs = timestamp % 86_400;
+    // Civil-from-days, proleptic Gregorian calendar (H. Hinnant)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3_600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+/// Build a [`VerificationReport`] relative to the current system time (requires "std")
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verification_report, generate_keypair};
+/// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1700000000), &generate_keypair()).unwrap();
+/// let report = verification_report(&signed);
+/// assert!(report.valid);
+/// assert_eq!(report.timestamp_utc, "2023-11-14T22:13:20Z");
+/// println!("{}", serde_json::to_string_pretty(&report).unwrap());
+/// ```
+#[cfg(feature = "std")]
+pub fn verification_report<T: Serialize>(signed: &SignedClaim<T>) -> VerificationReport {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    verification_report_at(signed, now)
+}
+
+/// Build a [`VerificationReport`], treating `now` (UTC seconds) as the current time
+pub fn verification_report_at<T: Serialize>(
+    signed: &SignedClaim<T>,
+    now: u64,
+) -> VerificationReport {
+    let pk = decode_public_key(&signed.public_key).ok();
+    let mut warnings = Vec::new();
+    if pk.as_ref().is_some_and(is_weak_key) {
+        warnings.push("signed with a weak or well-known test key".to_string());
+    }
+    if signed.claim.timestamp > now {
+        warnings.push(format!(
+            "timestamp is {} seconds in the future",
+            signed.claim.timestamp - now
+        ));
+    }
+    if let Some(expires_at) = signed.claim.expires_at.filter(|&at| at <= now) {
+        warnings.push(format!("claim expired at {}", format_utc(expires_at)));
+    }
+    let error = verify_claim(signed).err().map(|e| e.to_string());
+
+    VerificationReport {
+        claim_hash: claim_hash(&signed.claim).ok(),
+        did_key: pk.as_ref().map(to_did_key),
+        valid: error.is_none(),
+        error,
+        signer_fingerprint: pk.map(|pk| {
+            let mut fingerprint = hex::encode(Sha256::digest(pk.as_bytes()));
+            fingerprint.truncate(FINGERPRINT_HEX_LEN);
+            fingerprint
+        }),
+        timestamp: signed.claim.timestamp,
+        timestamp_utc: format_utc(signed.claim.timestamp),
+        warnings,
+    }
+}
+
+/// Describe a claim's canonical form for debugging signature mismatches
+///
+/// The report has four labelled sections: the claim as pretty-printed JSON,
+/// its canonical JSON, the hex of the canonical bytes and their SHA-256 (the
+/// [`claim_hash`]). Comparing the canonical sections from two platforms
+/// shows exactly where their signed bytes diverge.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, debug_canonical};
+/// let report = debug_canonical(&Claim::new_with_timestamp("Test".to_string(), 1)).unwrap();
+/// assert!(report.contains("canonical JSON:\n{\"data\":\"Test\",\"timestamp\":1}\n"));
+/// println!("{}", report);
+/// ```
+pub fn debug_canonical<T: Serialize>(claim: &Claim<T>) -> Result<String> {
+    let pretty = serde_json::to_string_pretty(claim)
+        .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+    let canonical = claim.to_signable_bytes()?;
+    Ok(format!(
+        "pretty JSON:\n{}\ncanonical JSON:\n{}\ncanonical hex ({} bytes):\n{}\nSHA-256:\n{}\n",
+        pretty,
+        String::from_utf8_lossy(&canonical),
+        canonical.len(),
+        hex::encode(&canonical),
+        compute_hash(&canonical)
+    ))
+}
+
+/// What [`preview_sign`] would sign, and the signature it would produce.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct SignPreview {
+    /// The exact bytes the signature covers
+    pub canonical_bytes: Vec<u8>,
+    /// The canonical bytes as text
+    pub canonical_json: String,
+    /// SHA-256 of the canonical bytes (Hex encoded)
+    pub claim_hash: String,
+    /// The signature [`sign_claim`](crate::sign_claim) would return (Hex encoded)
+    pub signature: String,
+}
+
+/// Show everything about signing `claim` with `key` without producing a
+/// [`SignedClaim`], e.g. for a confirmation screen
+///
+/// Ed25519 is deterministic, so the previewed signature is what a later
+/// [`sign_claim`](crate::sign_claim) of the same claim with the same key returns.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, preview_sign, sign_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let claim = Claim::new_with_timestamp("Transfer 100 to Bob".to_string(), 1);
+/// let preview = preview_sign(&claim, &key).unwrap();
+/// assert_eq!(preview.canonical_json, r#"{"data":"Transfer 100 to Bob","timestamp":1}"#);
+/// assert_eq!(preview.signature, sign_claim(&claim, &key).unwrap().signature);
+/// ```
+pub fn preview_sign<T: Serialize>(claim: &Claim<T>, key: &SigningKey) -> Result<SignPreview> {
+    let canonical_bytes = claim.to_signable_bytes()?;
+    let canonical_json = String::from_utf8(canonical_bytes.clone())
+        .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+    Ok(SignPreview {
+        claim_hash: compute_hash(&canonical_bytes),
+        signature: hex::encode(key.sign(&canonical_bytes).to_bytes()),
+        canonical_json,
+        canonical_bytes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+
+    #[test]
+    fn test_debug_canonical_sections() {
+        let mut claim = Claim::new_with_timestamp("x".to_string(), 1);
+        claim.metadata = Some("m".to_string());
+        let report = debug_canonical(&claim).unwrap();
+        let canonical = r#"{"data":"x","metadata":"m","timestamp":1}"#;
+        let expected_tail = format!(
+            "canonical JSON:\n{}\ncanonical hex ({} bytes):\n{}\nSHA-256:\n{}\n",
+            canonical,
+            canonical.len(),
+            hex::encode(canonical),
+            claim_hash(&claim).unwrap()
+        );
+        assert!(report.starts_with("pretty JSON:\n{\n  \"data\": \"x\",\n"));
+        assert!(report.ends_with(&expected_tail));
+    }
+
+    #[test]
+    fn test_preview_matches_sign_claim() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claim = Claim::new_with_timestamp("x".to_string(), 1);
+        claim.expires_at = Some(2);
+        let preview = preview_sign(&claim, &key).unwrap();
+        let signed = sign_claim(&claim, &key).unwrap();
+        assert_eq!(preview.canonical_bytes, claim.to_signable_bytes().unwrap());
+        assert_eq!(
+            preview.canonical_json.as_bytes(),
+            &preview.canonical_bytes[..]
+        );
+        assert_eq!(preview.claim_hash, claim_hash(&claim).unwrap());
+        assert_eq!(preview.signature, signed.signature);
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc(4_102_444_799), "2099-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_report_warnings_and_errors() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("x".to_string(), 100), &key).unwrap();
+
+        let report = verification_report_at(&signed, 40);
+        assert!(report.valid && report.error.is_none());
+        assert_eq!(report.did_key.unwrap(), to_did_key(&key.verifying_key()));
+        assert_eq!(
+            report.signer_fingerprint.unwrap().len(),
+            FINGERPRINT_HEX_LEN
+        );
+        // The zero key is weak, and the claim is 60 seconds ahead of `now`
+        assert_eq!(report.warnings.len(), 2);
+        assert!(report.warnings[1].contains("60 seconds"));
+
+        let mut tampered = signed.clone();
+        tampered.claim.data = "y".to_string();
+        let report = verification_report_at(&tampered, 100);
+        assert!(!report.valid);
+        assert!(report.error.is_some());
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["valid"], false);
+    }
+}
@@ -0,0 +1,214 @@
+//! Signed key rotation histories.
+//!
+//! A [`KeyHistory`] starts from a genesis key and records each rotation as a
+//! claim signed by the *outgoing* key, whose `data` is the incoming public key
+//! (Hex encoded), whose `metadata` is [`ROTATION_METADATA`] and whose
+//! `timestamp` is the moment of rotation. Key `i` is valid from its own
+//! activation up to (excluding) the timestamp of the next rotation; once
+//! rotated out, a key is treated as revoked for any later claim.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decode_public_key_bytes, sign_claim, verify_claim, Claim, Result, SdkError, SignedClaim,
+};
+
+/// Metadata marking a claim as a key rotation statement.
+pub const ROTATION_METADATA: &str = "provn:key-rotation";
+
+/// The period during which one key of a [`KeyHistory`] was valid.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct KeyWindow {
+    /// The public key (Hex encoded)
+    pub public_key: String,
+    /// First timestamp at which the key is valid (inclusive)
+    pub valid_from: u64,
+    /// Timestamp at which the key was rotated out (exclusive), if it was
+    pub valid_until: Option<u64>,
+}
+
+impl KeyWindow {
+    /// Whether the key was valid at `timestamp`
+    pub fn contains(&self, timestamp: u64) -> bool {
+        timestamp >= self.valid_from && self.valid_until.is_none_or(|until| timestamp < until)
+    }
+}
+
+/// An ordered, signed list of key rotations.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KeyHistory {
+    /// The first key of the history (Hex encoded)
+    pub genesis_key: String,
+    /// Rotation statements, oldest first, each signed by the key it retires
+    pub rotations: Vec<SignedClaim>,
+    /// When the genesis key became valid (UTC seconds)
+    pub valid_from: u64,
+}
+
+impl KeyHistory {
+    /// Start a history with `genesis` valid from `valid_from`
+    pub fn new(genesis: &VerifyingKey, valid_from: u64) -> Self {
+        Self {
+            genesis_key: hex::encode(genesis.as_bytes()),
+            rotations: Vec::new(),
+            valid_from,
+        }
+    }
+
+    /// Retire the current key in favour of `next`, effective at `at`
+    ///
+    /// `current` must be the currently active key and `at` must be later
+    /// than the activation of the current key.
+    pub fn rotate(&mut self, current: &SigningKey, next: &VerifyingKey, at: u64) -> Result<()> {
+        let windows = self.windows()?;
+        let active = &windows[windows.len() - 1];
+        if decode_public_key_bytes(&active.public_key)? != current.verifying_key().to_bytes() {
+            return Err(SdkError::KeyError(
+                "rotation must be signed by the active key".into(),
+            ));
+        }
+        if at <= active.valid_from {
+            return Err(SdkError::KeyError(format!(
+                "rotation at {} does not follow activation at {}",
+                at, active.valid_from
+            )));
+        }
+
+        let mut claim = Claim::new_with_timestamp(hex::encode(next.as_bytes()), at);
+        claim.metadata = Some(ROTATION_METADATA.to_string());
+        self.rotations.push(sign_claim(&claim, current)?);
+        Ok(())
+    }
+
+    /// Verify the rotation chain and return the validity window of every key
+    pub fn windows(&self) -> Result<Vec<KeyWindow>> {
+        let mut windows = Vec::with_capacity(self.rotations.len() + 1);
+        let mut active = KeyWindow {
+            public_key: hex::encode(decode_public_key_bytes(&self.genesis_key)?),
+            valid_from: self.valid_from,
+            valid_until: None,
+        };
+
+        for (i, rotation) in self.rotations.iter().enumerate() {
+            let broken = |reason: &str| {
+                SdkError::KeyError(format!("key history broken at rotation {}: {}", i, reason))
+            };
+            verify_claim(rotation)?;
+            if hex::encode(rotation.public_key_bytes()?) != active.public_key {
+                return Err(broken("not signed by the active key"));
+            }
+            if rotation.claim.metadata.as_deref() != Some(ROTATION_METADATA) {
+                return Err(broken("not a rotation statement"));
+            }
+            if rotation.claim.timestamp <= active.valid_from {
+                return Err(broken("timestamps are not increasing"));
+            }
+
+            let next = KeyWindow {
+                public_key: hex::encode(decode_public_key_bytes(&rotation.claim.data)?),
+                valid_from: rotation.claim.timestamp,
+                valid_until: None,
+            };
+            active.valid_until = Some(rotation.claim.timestamp);
+            windows.push(core::mem::replace(&mut active, next));
+        }
+        windows.push(active);
+        Ok(windows)
+    }
+}
+
+/// Verify a claim signed by any key of a rotation history
+///
+/// The claim must verify, and its signer must have been the active key at
+/// the claim's timestamp. A key rotated out before the claim was made is
+/// rejected even though its signature is valid.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, KeyHistory, sign_claim, verify_with_key_history, generate_keypair};
+/// let old_key = generate_keypair();
+/// let new_key = generate_keypair();
+/// let mut history = KeyHistory::new(&old_key.verifying_key(), 0);
+/// history.rotate(&old_key, &new_key.verifying_key(), 1000).unwrap();
+///
+/// let early = sign_claim(&Claim::new_with_timestamp("early".to_string(), 500), &old_key).unwrap();
+/// assert!(verify_with_key_history(&early, &history).unwrap());
+///
+/// let late = sign_claim(&Claim::new_with_timestamp("late".to_string(), 1500), &old_key).unwrap();
+/// assert!(verify_with_key_history(&late, &history).is_err());
+/// ```
+pub fn verify_with_key_history(signed_claim: &SignedClaim, history: &KeyHistory) -> Result<bool> {
+    verify_claim(signed_claim)?;
+    let signer = hex::encode(signed_claim.public_key_bytes()?);
+    let timestamp = signed_claim.claim.timestamp;
+
+    let windows = history.windows()?;
+    let mut known = false;
+    for window in windows.iter().filter(|w| w.public_key == signer) {
+        if window.contains(timestamp) {
+            return Ok(true);
+        }
+        known = true;
+    }
+    Err(SdkError::KeyError(if known {
+        format!("signing key was not valid at timestamp {}", timestamp)
+    } else {
+        "signing key is not part of the key history".into()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn claim_at(signer: &SigningKey, timestamp: u64) -> SignedClaim {
+        sign_claim(
+            &Claim::new_with_timestamp("c".to_string(), timestamp),
+            signer,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_windows_across_rotations() {
+        let (a, b, c) = (key(1), key(2), key(3));
+        let mut history = KeyHistory::new(&a.verifying_key(), 100);
+        history.rotate(&a, &b.verifying_key(), 200).unwrap();
+        history.rotate(&b, &c.verifying_key(), 300).unwrap();
+
+        let windows = history.windows().unwrap();
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[1].valid_from, 200);
+        assert_eq!(windows[1].valid_until, Some(300));
+        assert_eq!(windows[2].valid_until, None);
+
+        assert!(verify_with_key_history(&claim_at(&a, 150), &history).unwrap());
+        assert!(verify_with_key_history(&claim_at(&b, 299), &history).unwrap());
+        assert!(verify_with_key_history(&claim_at(&c, 10_000), &history).unwrap());
+        // Revoked before the claim, or before the key existed
+        assert!(verify_with_key_history(&claim_at(&a, 200), &history).is_err());
+        assert!(verify_with_key_history(&claim_at(&c, 250), &history).is_err());
+        assert!(verify_with_key_history(&claim_at(&key(9), 150), &history).is_err());
+    }
+
+    #[test]
+    fn test_rotation_rules() {
+        let (a, b) = (key(1), key(2));
+        let mut history = KeyHistory::new(&a.verifying_key(), 100);
+        // Only the active key may rotate, and only forwards in time
+        assert!(history.rotate(&b, &b.verifying_key(), 200).is_err());
+        assert!(history.rotate(&a, &b.verifying_key(), 100).is_err());
+
+        history.rotate(&a, &b.verifying_key(), 200).unwrap();
+        history.rotations[0].claim.data = hex::encode(key(9).verifying_key().as_bytes());
+        assert!(history.windows().is_err());
+    }
+}
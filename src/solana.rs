@@ -0,0 +1,196 @@
+//! Bridging Solana's ed25519 precompile with claim semantics.
+//!
+//! The precompile instruction data is laid out as
+//! `num_signatures: u8, padding: u8` followed by one 14-byte
+//! `Ed25519SignatureOffsets` record per signature (seven little-endian
+//! `u16`s). Only records whose data lives in the precompile instruction
+//! itself (instruction index `u16::MAX`) can be resolved from the data alone.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::{parse, verify_claim, Claim, Result, SdkError, SignedClaim};
+
+const HEADER_LEN: usize = 2;
+const OFFSETS_LEN: usize = 14;
+/// Instruction index meaning "the precompile instruction itself".
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// One `(public key, signature, message)` triple checked by the precompile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ed25519PrecompileEntry {
+    /// The signed message
+    pub message: Vec<u8>,
+    /// The signer's Ed25519 public key
+    pub public_key: [u8; 32],
+    /// The Ed25519 signature
+    pub signature: [u8; 64],
+}
+
+fn malformed(reason: &str) -> SdkError {
+    SdkError::SerializationError(format!("malformed ed25519 instruction: {}", reason))
+}
+
+fn read_u16(data: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([data[at], data[at + 1]])
+}
+
+fn slice(data: &[u8], offset: u16, len: usize, instruction_index: u16) -> Result<&[u8]> {
+    if instruction_index != CURRENT_INSTRUCTION {
+        return Err(malformed("data in another instruction is not supported"));
+    }
+    let start = offset as usize;
+    data.get(start..start + len)
+        .ok_or_else(|| malformed("offset out of bounds"))
+}
+
+/// Parse the data of an ed25519 precompile instruction
+///
+/// # Example
+/// ```
+/// use provn_sdk::parse_ed25519_instruction;
+/// // No signatures
+/// assert!(parse_ed25519_instruction(&[0, 0]).unwrap().is_empty());
+/// assert!(parse_ed25519_instruction(&[1, 0]).is_err());
+/// ```
+pub fn parse_ed25519_instruction(data: &[u8]) -> Result<Vec<Ed25519PrecompileEntry>> {
+    let count = *data.first().ok_or_else(|| malformed("empty data"))? as usize;
+    if data.len() < HEADER_LEN + count * OFFSETS_LEN {
+        return Err(malformed("truncated offsets"));
+    }
+
+    (0..count)
+        .map(|i| {
+            let at = HEADER_LEN + i * OFFSETS_LEN;
+            let field = |n: usize| read_u16(data, at + 2 * n);
+            let signature = slice(data, field(0), 64, field(1))?;
+            let public_key = slice(data, field(2), 32, field(3))?;
+            let message = slice(data, field(4), field(5) as usize, field(6))?;
+            Ok(Ed25519PrecompileEntry {
+                message: message.to_vec(),
+                public_key: public_key.try_into().expect("slice is 32 bytes"),
+                signature: signature.try_into().expect("slice is 64 bytes"),
+            })
+        })
+        .collect()
+}
+
+/// Recover and verify the signed claims checked by an ed25519 precompile instruction
+///
+/// Every message must be the canonical bytes of a [`Claim`]; messages that
+/// parse but are not canonical are rejected, since other verifiers would
+/// compute different bytes for the same claim.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{claims_from_ed25519_instruction, Claim, sign_claim, generate_keypair};
+/// let signed = sign_claim(&Claim::new_with_timestamp("on-chain".to_string(), 1), &generate_keypair()).unwrap();
+/// let message = signed.claim.to_signable_bytes().unwrap();
+///
+/// // Layout: header, one offsets record, then signature, public key and message
+/// let (sig_at, pk_at, msg_at) = (16u16, 80u16, 112u16);
+/// let mut data = vec![1u8, 0];
+/// for v in [sig_at, u16::MAX, pk_at, u16::MAX, msg_at, message.len() as u16, u16::MAX] {
+///     data.extend_from_slice(&v.to_le_bytes());
+/// }
+/// data.extend_from_slice(&signed.signature_bytes().unwrap());
+/// data.extend_from_slice(&signed.public_key_bytes().unwrap());
+/// data.extend_from_slice(&message);
+///
+/// let claims = claims_from_ed25519_instruction(&data).unwrap();
+/// assert_eq!(claims[0].claim, signed.claim);
+/// ```
+pub fn claims_from_ed25519_instruction(data: &[u8]) -> Result<Vec<SignedClaim>> {
+    parse_ed25519_instruction(data)?
+        .into_iter()
+        .map(|entry| {
+            let claim: Claim = parse::from_slice_unique(&entry.message)?;
+            if claim.to_signable_bytes()? != entry.message {
+                return Err(SdkError::SerializationError(
+                    "precompile message is not a canonical claim".into(),
+                ));
+            }
+            let signed = SignedClaim {
+                claim,
+                public_key: hex::encode(entry.public_key),
+                signature: hex::encode(entry.signature),
+                signed_fields: None,
+                metadata_salt: None,
+                domain: None,
+            };
+            verify_claim(&signed)?;
+            Ok(signed)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sign_claim;
+    use alloc::string::ToString;
+    use ed25519_dalek::SigningKey;
+
+    fn instruction(signature: &[u8], public_key: &[u8], message: &[u8]) -> Vec<u8> {
+        let sig_at = (HEADER_LEN + OFFSETS_LEN) as u16;
+        let pk_at = sig_at + 64;
+        let msg_at = pk_at + 32;
+        let mut data = alloc::vec![1u8, 0];
+        for v in [
+            sig_at,
+            CURRENT_INSTRUCTION,
+            pk_at,
+            CURRENT_INSTRUCTION,
+            msg_at,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        data.extend_from_slice(signature);
+        data.extend_from_slice(public_key);
+        data.extend_from_slice(message);
+        data
+    }
+
+    #[test]
+    fn test_rejects_non_canonical_message() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let message = br#"{"timestamp":1,"data":"x"}"#;
+        let signature = ed25519_dalek::Signer::sign(&key, message).to_bytes();
+        let data = instruction(&signature, key.verifying_key().as_bytes(), message);
+
+        // The precompile would accept this signature, but it is not a claim
+        assert_eq!(
+            parse_ed25519_instruction(&data).unwrap()[0].message,
+            message
+        );
+        assert!(matches!(
+            claims_from_ed25519_instruction(&data),
+            Err(SdkError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_rejects_bad_offsets() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let signed = sign_claim(&Claim::new_with_timestamp("x".to_string(), 1), &key).unwrap();
+        let message = signed.claim.to_signable_bytes().unwrap();
+        let data = instruction(
+            &signed.signature_bytes().unwrap(),
+            &signed.public_key_bytes().unwrap(),
+            &message,
+        );
+        assert_eq!(claims_from_ed25519_instruction(&data).unwrap().len(), 1);
+
+        // Message size running past the end of the data
+        let mut oversized = data.clone();
+        oversized[HEADER_LEN + 10] = 0xff;
+        assert!(parse_ed25519_instruction(&oversized).is_err());
+        // Public key in a different instruction
+        let mut foreign = data.clone();
+        foreign[HEADER_LEN + 6] = 0;
+        assert!(parse_ed25519_instruction(&foreign).is_err());
+        assert!(parse_ed25519_instruction(&data[..HEADER_LEN + 13]).is_err());
+    }
+}
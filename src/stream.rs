@@ -0,0 +1,350 @@
+//! Signing payloads too large to hold in memory.
+//!
+//! The payload is streamed through SHA-256 in fixed-size chunks and only the
+//! digest is stored in the claim, so memory use stays flat regardless of the
+//! payload size. The resulting claim is an ordinary signed claim whose `data`
+//! is the hex digest of the payload.
+//!
+//! [`sign_file_claim`] packages the common case of signing a file on disk:
+//! the claim's `metadata` additionally records the file size, and
+//! [`verify_file_claim`] checks both against the file.
+//!
+//! Exported logs are verified the same way: [`verify_ndjson_file`] reads one
+//! signed claim per line and never holds more than [`MAX_NDJSON_LINE_BYTES`]
+//! of it in memory.
+
+use std::io::{BufRead, BufReader, ErrorKind, Read};
+use std::path::Path;
+
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{canonicalize, parse, sign_claim, verify_claim, Claim, Result, SdkError, SignedClaim};
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Longest line [`verify_ndjson`] buffers; longer lines count as failed.
+///
+/// Equal to the default [`ParseLimits::max_total_bytes`](crate::ParseLimits).
+pub const MAX_NDJSON_LINE_BYTES: usize = 64 * 1024;
+
+/// Hex SHA-256 of everything read from `reader`, and the number of bytes read.
+///
+/// Interrupted reads are retried, as [`std::io::copy`] does.
+fn hash_reader<R: Read>(mut reader: R) -> Result<(String, u64)> {
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; CHUNK_SIZE];
+    let mut len = 0u64;
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(n) => n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => {
+                return Err(SdkError::SerializationError(format!(
+                    "failed to read payload: {}",
+                    e
+                )))
+            }
+        };
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        len += n as u64;
+    }
+    Ok((hex::encode(hasher.finalize()), len))
+}
+
+/// Sign a payload read from `reader` without buffering it
+///
+/// # Example
+/// ```
+/// use provn_sdk::{sign_claim_streaming, verify_claim_streaming, generate_keypair};
+/// let payload = vec![7u8; 100_000];
+/// let signed = sign_claim_streaming(&payload[..], 1700000000, &generate_keypair()).unwrap();
+/// assert_eq!(signed.claim.data.len(), 64);
+/// assert!(verify_claim_streaming(&signed, &payload[..]).unwrap());
+/// ```
+pub fn sign_claim_streaming<R: Read>(
+    reader: R,
+    timestamp: u64,
+    key: &SigningKey,
+) -> Result<SignedClaim> {
+    let (digest, _) = hash_reader(reader)?;
+    let claim = Claim::new_with_timestamp(digest, timestamp);
+    sign_claim(&claim, key)
+}
+
+/// Verify a claim produced by [`sign_claim_streaming`] against the payload
+///
+/// Fails if the signature is invalid or the streamed payload does not hash to
+/// the digest in the claim.
+pub fn verify_claim_streaming<R: Read>(signed: &SignedClaim, reader: R) -> Result<bool> {
+    verify_claim(signed)?;
+    let (digest, _) = hash_reader(reader)?;
+    if digest != signed.claim.data.to_ascii_lowercase() {
+        return Err(SdkError::ProofError(
+            "payload does not match the signed digest".to_string(),
+        ));
+    }
+    Ok(true)
+}
+
+/// The `metadata` of a claim made by [`sign_file_claim`], as canonical JSON.
+#[derive(Serialize, Deserialize)]
+struct FileMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metadata: Option<String>,
+    size: u64,
+}
+
+fn open(path: &Path) -> Result<std::fs::File> {
+    std::fs::File::open(path).map_err(|e| {
+        SdkError::SerializationError(format!("failed to open {}: {}", path.display(), e))
+    })
+}
+
+/// [`hash_reader`] over the file at `path`; like it, retries interrupted reads.
+fn hash_file(path: &Path) -> Result<(String, u64)> {
+    hash_reader(open(path)?)
+}
+
+/// Sign the contents of a file, timestamped with the current time
+///
+/// The file is streamed through SHA-256; the claim's `data` is the hex
+/// digest and its `metadata` is the canonical JSON object
+/// `{"metadata":<metadata>,"size":<bytes>}` (`metadata` omitted when `None`).
+///
+/// # Example
+/// ```
+/// use provn_sdk::{sign_file_claim, verify_file_claim, generate_keypair};
+/// let path = std::env::temp_dir().join("provn-doc-file-claim.txt");
+/// std::fs::write(&path, b"Internal Audit Memo #1234").unwrap();
+/// let signed = sign_file_claim(&path, &generate_keypair(), Some("audit memo".to_string())).unwrap();
+/// assert_eq!(signed.claim.metadata.as_deref(), Some(r#"{"metadata":"audit memo","size":25}"#));
+/// assert!(verify_file_claim(&path, &signed).unwrap());
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn sign_file_claim(
+    path: &Path,
+    key: &SigningKey,
+    metadata: Option<String>,
+) -> Result<SignedClaim> {
+    let (digest, size) = hash_file(path)?;
+    let metadata = canonicalize(&FileMetadata { metadata, size })?;
+    let mut claim = Claim::new(digest);
+    claim.metadata =
+        Some(String::from_utf8(metadata).map_err(|e| SdkError::SerializationError(e.to_string()))?);
+    sign_claim(&claim, key)
+}
+
+/// Verify a claim made by [`sign_file_claim`] against the file at `path`
+///
+/// Fails if the signature is invalid, or with [`SdkError::ProofError`] if the
+/// file's size or digest differs from the signed ones.
+pub fn verify_file_claim(path: &Path, signed: &SignedClaim) -> Result<bool> {
+    verify_claim(signed)?;
+    let signed_size = signed
+        .claim
+        .metadata
+        .as_deref()
+        .and_then(|metadata| parse::from_slice_unique::<FileMetadata>(metadata.as_bytes()).ok())
+        .ok_or_else(|| {
+            SdkError::ProofError("claim metadata does not record a file size".to_string())
+        })?
+        .size;
+    let (digest, size) = hash_file(path)?;
+    if size != signed_size {
+        return Err(SdkError::ProofError(format!(
+            "file size {} does not match the signed size {}",
+            size, signed_size
+        )));
+    }
+    if digest != signed.claim.data.to_ascii_lowercase() {
+        return Err(SdkError::ProofError(
+            "file does not match the signed digest".to_string(),
+        ));
+    }
+    Ok(true)
+}
+
+/// Counts from verifying a newline-delimited file of signed claims.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifySummary {
+    /// Line numbers (1-based) of claims that were malformed or did not verify
+    pub failed_lines: Vec<usize>,
+    /// Number of claims checked; blank lines are skipped
+    pub total: usize,
+    /// Number of claims that verified
+    pub valid: usize,
+}
+
+impl VerifySummary {
+    /// Number of claims that failed
+    pub fn invalid(&self) -> usize {
+        self.failed_lines.len()
+    }
+}
+
+/// Verify newline-delimited signed claims from `reader`, one line at a time
+///
+/// A line longer than [`MAX_NDJSON_LINE_BYTES`] is reported as failed and
+/// skipped without being buffered.
+///
+/// # Example
+/// ```
+/// use provn_sdk::verify_ndjson;
+/// let summary = verify_ndjson(&b"\n{}\n"[..]).unwrap();
+/// assert_eq!((summary.total, summary.failed_lines), (1, vec![2]));
+/// ```
+pub fn verify_ndjson<R: BufRead>(mut reader: R) -> Result<VerifySummary> {
+    let mut summary = VerifySummary::default();
+    let mut line = Vec::new();
+    let mut line_number = 0;
+    let read_error =
+        |e: std::io::Error| SdkError::SerializationError(format!("failed to read claims: {}", e));
+    loop {
+        line.clear();
+        let n = (&mut reader)
+            .take(MAX_NDJSON_LINE_BYTES as u64 + 1)
+            .read_until(b'\n', &mut line)
+            .map_err(read_error)?;
+        if n == 0 {
+            break;
+        }
+        line_number += 1;
+        if line.len() > MAX_NDJSON_LINE_BYTES && line.last() != Some(&b'\n') {
+            reader.skip_until(b'\n').map_err(read_error)?;
+            summary.total += 1;
+            summary.failed_lines.push(line_number);
+            continue;
+        }
+        if line.trim_ascii().is_empty() {
+            continue;
+        }
+        summary.total += 1;
+        let verified = parse::from_slice_unique::<SignedClaim>(line.trim_ascii())
+            .and_then(|signed| verify_claim(&signed));
+        match verified {
+            Ok(_) => summary.valid += 1,
+            Err(_) => summary.failed_lines.push(line_number),
+        }
+    }
+    Ok(summary)
+}
+
+/// Verify a newline-delimited file of signed claims without loading it whole
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_ndjson_file, generate_keypair};
+/// let signed = sign_claim(&Claim::new_with_timestamp("Test".to_string(), 1), &generate_keypair()).unwrap();
+/// let path = std::env::temp_dir().join("provn-doc-claims.ndjson");
+/// std::fs::write(&path, format!("{}\nnot json\n", serde_json::to_string(&signed).unwrap())).unwrap();
+/// let summary = verify_ndjson_file(&path).unwrap();
+/// assert_eq!((summary.valid, summary.failed_lines), (1, vec![2]));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn verify_ndjson_file(path: &Path) -> Result<VerifySummary> {
+    verify_ndjson(BufReader::new(open(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute_hash;
+
+    #[test]
+    fn test_streaming_matches_in_memory_hash() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        // Spans several chunks with a partial final chunk
+        let payload: Vec<u8> = (0..(3 * CHUNK_SIZE + 17)).map(|i| i as u8).collect();
+        let signed = sign_claim_streaming(&payload[..], 5, &key).unwrap();
+        assert_eq!(signed.claim.data, compute_hash(&payload));
+        assert!(verify_claim_streaming(&signed, &payload[..]).unwrap());
+
+        let mut altered = payload.clone();
+        altered[CHUNK_SIZE] ^= 1;
+        assert!(matches!(
+            verify_claim_streaming(&signed, &altered[..]),
+            Err(SdkError::ProofError(_))
+        ));
+    }
+
+    /// Fails every other read with `Interrupted`, as a signal would.
+    struct Interrupting<R>(R, bool);
+
+    impl<R: Read> Read for Interrupting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(ErrorKind::Interrupted.into());
+            }
+            self.0.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_interrupted_reads_are_retried() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let payload = vec![3u8; 2 * CHUNK_SIZE + 1];
+        let signed = sign_claim_streaming(Interrupting(&payload[..], false), 5, &key).unwrap();
+        assert_eq!(signed.claim.data, compute_hash(&payload));
+        assert!(verify_claim_streaming(&signed, Interrupting(&payload[..], false)).unwrap());
+    }
+
+    #[test]
+    fn test_file_claim_checks_size_and_digest() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let path = std::env::temp_dir().join(format!("provn-file-claim-{}", std::process::id()));
+        std::fs::write(&path, b"hello").unwrap();
+        let signed = sign_file_claim(&path, &key, None).unwrap();
+        assert_eq!(signed.claim.data, compute_hash(b"hello"));
+        assert_eq!(signed.claim.metadata.as_deref(), Some(r#"{"size":5}"#));
+        assert!(verify_file_claim(&path, &signed).unwrap());
+
+        std::fs::write(&path, b"hello!").unwrap();
+        let err = verify_file_claim(&path, &signed).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid proof: file size 6 does not match the signed size 5"
+        );
+        std::fs::write(&path, b"jello").unwrap();
+        assert!(matches!(
+            verify_file_claim(&path, &signed),
+            Err(SdkError::ProofError(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+        assert!(verify_file_claim(&path, &signed).is_err());
+    }
+
+    #[test]
+    fn test_ndjson_failures_are_reported_by_line() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let line = |data: &str| {
+            let signed = sign_claim(&Claim::new_with_timestamp(data.to_string(), 1), &key).unwrap();
+            serde_json::to_string(&signed).unwrap()
+        };
+        let tampered = line("a").replace(r#""data":"a""#, r#""data":"b""#);
+        // Lines: valid (CRLF), blank, tampered, truncated JSON, invalid UTF-8, valid without newline
+        let mut input = format!("{}\r\n\n{}\n{{\n", line("a"), tampered).into_bytes();
+        input.extend_from_slice(b"\xff\n");
+        input.extend_from_slice(line("c").as_bytes());
+
+        let summary = verify_ndjson(&input[..]).unwrap();
+        assert_eq!(summary.total, 5);
+        assert_eq!(summary.valid, 2);
+        assert_eq!(summary.failed_lines, [3, 4, 5]);
+        assert_eq!(summary.invalid(), 3);
+
+        // An oversized line fails alone; the lines after it are still read
+        let mut input = vec![b' '; MAX_NDJSON_LINE_BYTES + 1];
+        input.push(b'\n');
+        input.extend_from_slice(format!("{}\n", line("a")).as_bytes());
+        input.extend_from_slice(&vec![b'x'; 3 * MAX_NDJSON_LINE_BYTES]);
+        let summary = verify_ndjson(&input[..]).unwrap();
+        assert_eq!((summary.total, summary.valid), (3, 1));
+        assert_eq!(summary.failed_lines, [1, 3]);
+    }
+}
@@ -0,0 +1,90 @@
+//! Authoritative time sources for signing.
+//!
+//! [`sign_claim_trusted_time`] takes the claim timestamp from a
+//! [`TrustedTime`] source instead of the local clock, so servers can plug in
+//! NTP, Roughtime or a hardware clock in one place. If the source fails,
+//! nothing is signed.
+
+use alloc::string::String;
+use ed25519_dalek::SigningKey;
+
+use crate::{sign_claim, Claim, Result, SignedClaim};
+
+/// A source of the current time (UTC seconds).
+///
+/// Closures of the form `Fn() -> Result<u64>` implement it automatically.
+pub trait TrustedTime {
+    /// The current time, or an error if it cannot be established
+    fn now(&self) -> Result<u64>;
+}
+
+impl<F> TrustedTime for F
+where
+    F: Fn() -> Result<u64>,
+{
+    fn now(&self) -> Result<u64> {
+        self()
+    }
+}
+
+/// The local system clock (requires "std").
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl TrustedTime for SystemClock {
+    /// Fails if the clock reads before the Unix epoch
+    fn now(&self) -> Result<u64> {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .map_err(|e| crate::SdkError::TimeError(alloc::format!("system clock: {}", e)))
+    }
+}
+
+/// Build a claim timestamped by `time` and sign it
+///
+/// # Example
+/// ```
+/// use provn_sdk::{SdkError, SystemClock, sign_claim_trusted_time, generate_keypair};
+/// let key = generate_keypair();
+/// let signed = sign_claim_trusted_time("Backup completed".to_string(), &key, &SystemClock).unwrap();
+/// assert!(signed.claim.timestamp > 1_700_000_000);
+///
+/// let unreachable = || Err(SdkError::TimeError("NTP server unreachable".into()));
+/// assert!(sign_claim_trusted_time("Backup completed".to_string(), &key, &unreachable).is_err());
+/// ```
+pub fn sign_claim_trusted_time<C: TrustedTime + ?Sized>(
+    data: String,
+    key: &SigningKey,
+    time: &C,
+) -> Result<SignedClaim> {
+    let timestamp = time.now()?;
+    sign_claim(&Claim::new_with_timestamp(data, timestamp), key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{verify_claim, SdkError};
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_timestamp_comes_from_the_source() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let fixed = || Ok(1_234);
+        let signed = sign_claim_trusted_time("t".to_string(), &key, &fixed).unwrap();
+        assert_eq!(signed.claim.timestamp, 1_234);
+        assert!(verify_claim(&signed).unwrap());
+
+        let failing = || Err(SdkError::TimeError("no response".to_string()));
+        assert!(matches!(
+            sign_claim_trusted_time("t".to_string(), &key, &failing),
+            Err(SdkError::TimeError(_))
+        ));
+        // Timestamps a JavaScript verifier cannot represent are still rejected
+        let far = || Ok(u64::MAX);
+        assert!(sign_claim_trusted_time("t".to_string(), &key, &far).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! Canonicalization tracing.
+//!
+//! With the `trace` feature enabled, [`canonicalize`](crate::canonicalize)
+//! (and so [`Claim::to_signable_bytes`](crate::Claim::to_signable_bytes))
+//! runs inside a `canonicalize` debug span of the [`tracing`](https://docs.rs/tracing)
+//! crate and reports each decision it makes as a debug event under the
+//! target [`TRACE_TARGET`]:
+//!
+//! - `sorted object keys`, with the `keys` of an object after sorting by
+//!   UTF-16 code units;
+//! - `formatted number`, with the number as parsed (`input`) and the
+//!   ECMAScript formatting written to the output (`output`);
+//! - `canonicalized`, with the length (`len`) of the canonical bytes.
+//!
+//! Comparing this output with another implementation's shows exactly where
+//! two canonical forms diverge. Enable it with any subscriber, e.g.
+//! `RUST_LOG=provn_sdk::canonicalize=debug` under `tracing-subscriber`'s
+//! `EnvFilter`. Without the feature, spans and events compile to nothing.
+
+/// Target of the spans and events emitted while canonicalizing.
+#[cfg(feature = "trace")]
+pub const TRACE_TARGET: &str = "provn_sdk::canonicalize";
+
+#[cfg(not(feature = "trace"))]
+macro_rules! emit {
+    ($($field:tt)*) => {};
+}
+
+/// Emit a debug event under [`TRACE_TARGET`]; fields are only evaluated
+/// when a subscriber is interested.
+#[cfg(feature = "trace")]
+macro_rules! emit {
+    ($($field:tt)*) => {
+        ::tracing::debug!(target: $crate::trace::TRACE_TARGET, $($field)*)
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! span {
+    () => {};
+}
+
+/// Enter a `canonicalize` debug span until the end of the enclosing block.
+#[cfg(feature = "trace")]
+macro_rules! span {
+    () => {
+        let _span = ::tracing::debug_span!(target: $crate::trace::TRACE_TARGET, "canonicalize")
+            .entered();
+    };
+}
+
+pub(crate) use emit;
+pub(crate) use span;
+
+#[cfg(all(test, feature = "trace"))]
+mod tests {
+    use super::*;
+    use crate::canonicalize;
+    use alloc::format;
+    use alloc::string::String;
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use core::fmt::Debug;
+    use std::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Records every event as `canonicalize/message key=value ...`.
+    #[derive(Default, Clone)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    struct Line(String);
+
+    impl Visit for Line {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            match field.name() {
+                "message" => self.0 += &format!("{:?}", value),
+                name => self.0 += &format!(" {}={:?}", name, value),
+            }
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            metadata.target() == TRACE_TARGET
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            assert_eq!(span.metadata().name(), "canonicalize");
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut line = Line(String::from("canonicalize/"));
+            event.record(&mut line);
+            self.0.lock().unwrap().push(line.0);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_steps_are_traced() {
+        let recorder = Recorder::default();
+        let bytes = tracing::subscriber::with_default(recorder.clone(), || {
+            canonicalize(&serde_json::json!({"b": 1e30, "a": 4.50})).unwrap()
+        });
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                r#"canonicalize/sorted object keys keys=["a", "b"]"#,
+                "canonicalize/formatted number input=4.5 output=4.5",
+                "canonicalize/formatted number input=1e+30 output=1e+30",
+                format!("canonicalize/canonicalized len={}", bytes.len()).as_str(),
+            ]
+        );
+    }
+}
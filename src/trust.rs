@@ -0,0 +1,140 @@
+//! Verification against a trust anchor compiled into the binary.
+//!
+//! Set `PROVN_TRUSTED_PUBKEY` to the hex encoded public key when building:
+//!
+//! ```text
+//! PROVN_TRUSTED_PUBKEY=3b6a27bc...da29 cargo build --release
+//! ```
+//!
+//! The value is parsed at compile time, so a malformed key fails the build,
+//! and cargo rebuilds the crate whenever the variable changes. Binaries that
+//! trust a key of their own can embed it with [`trusted_key_from_hex`] and
+//! call [`verify_claim_anchored`] instead.
+
+use serde::Serialize;
+
+use crate::{decode_public_key_bytes, verify_claim, Result, SdkError, SignedClaim};
+
+/// The public key from `PROVN_TRUSTED_PUBKEY` at build time, if it was set.
+pub const TRUSTED_PUBKEY: Option<[u8; 32]> = match option_env!("PROVN_TRUSTED_PUBKEY") {
+    Some(hex) => Some(trusted_key_from_hex(hex)),
+    None => None,
+};
+
+const fn hex_nibble(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => panic!("trusted public key must be hex encoded"),
+    }
+}
+
+/// Parse a hex encoded public key in a const context
+///
+/// An optional `0x` prefix is accepted. Panics (failing compilation when used
+/// in a `const`) if the input is not exactly 64 hex characters.
+///
+/// # Example
+/// ```
+/// use provn_sdk::trusted_key_from_hex;
+/// const KEY: [u8; 32] =
+///     trusted_key_from_hex("0x3b6a27bcceb6a42d62a3a8d02a6f0d73653215771de243a63ac048a18b59da29");
+/// assert_eq!(KEY[0], 0x3b);
+/// ```
+pub const fn trusted_key_from_hex(hex: &str) -> [u8; 32] {
+    let bytes = hex.as_bytes();
+    let offset = if bytes.len() >= 2 && bytes[0] == b'0' && (bytes[1] == b'x' || bytes[1] == b'X') {
+        2
+    } else {
+        0
+    };
+    assert!(
+        bytes.len() - offset == 64,
+        "trusted public key must be 64 hex characters"
+    );
+    let mut key = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        key[i] = hex_nibble(bytes[offset + 2 * i]) << 4 | hex_nibble(bytes[offset + 2 * i + 1]);
+        i += 1;
+    }
+    key
+}
+
+/// Verify a claim that must be signed by `trusted`
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim, verify_claim_anchored, generate_keypair};
+/// let key = generate_keypair();
+/// let signed = sign_claim(&Claim::new("Test".to_string()), &key).unwrap();
+/// assert!(verify_claim_anchored(&signed, &key.verifying_key().to_bytes()).unwrap());
+/// assert!(verify_claim_anchored(&signed, &[0u8; 32]).is_err());
+/// ```
+pub fn verify_claim_anchored<T: Serialize>(
+    signed: &SignedClaim<T>,
+    trusted: &[u8; 32],
+) -> Result<bool> {
+    if decode_public_key_bytes(&signed.public_key)? != *trusted {
+        return Err(SdkError::KeyError(
+            "claim is not signed by the trusted key".into(),
+        ));
+    }
+    verify_claim(signed)
+}
+
+/// Verify a claim against [`TRUSTED_PUBKEY`]
+///
+/// Fails with a `KeyError` if the crate was built without
+/// `PROVN_TRUSTED_PUBKEY`.
+pub fn verify_claim_trusted<T: Serialize>(signed: &SignedClaim<T>) -> Result<bool> {
+    match &TRUSTED_PUBKEY {
+        Some(trusted) => verify_claim_anchored(signed, trusted),
+        None => Err(SdkError::KeyError(
+            "no trusted key compiled in; build with PROVN_TRUSTED_PUBKEY set".into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+    use alloc::string::ToString;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_const_hex_parsing() {
+        const KEY: [u8; 32] = trusted_key_from_hex(
+            "3B6A27BCCEB6A42D62A3A8D02A6F0D73653215771DE243A63AC048A18B59DA29",
+        );
+        assert_eq!(
+            KEY,
+            SigningKey::from_bytes(&[0u8; 32])
+                .verifying_key()
+                .to_bytes()
+        );
+        assert_eq!(
+            trusted_key_from_hex(&alloc::format!("0x{}", "ff".repeat(32))),
+            [0xff; 32]
+        );
+    }
+
+    #[test]
+    fn test_anchored_rejects_other_signers() {
+        let trusted = SigningKey::from_bytes(&[0u8; 32]);
+        let other = SigningKey::from_bytes(&[1u8; 32]);
+        let claim = Claim::new_with_timestamp("anchor".to_string(), 1);
+        let anchor = trusted.verifying_key().to_bytes();
+
+        assert!(verify_claim_anchored(&sign_claim(&claim, &trusted).unwrap(), &anchor).unwrap());
+        assert!(matches!(
+            verify_claim_anchored(&sign_claim(&claim, &other).unwrap(), &anchor),
+            Err(SdkError::KeyError(_))
+        ));
+        if TRUSTED_PUBKEY.is_none() {
+            assert!(verify_claim_trusted(&sign_claim(&claim, &trusted).unwrap()).is_err());
+        }
+    }
+}
@@ -0,0 +1,565 @@
+//! Checking claims against RFC 3161 timestamp authority (TSA) tokens
+//! (requires the "rfc3161" feature).
+//!
+//! A claim's `timestamp` is asserted by its signer. A TSA token adds an
+//! independent witness: the authority signs the hash of the claim together
+//! with the time it saw it. [`TsaToken::verify`] decodes a DER token (or a
+//! whole `TimeStampResp`), checks the authority's CMS signature with its
+//! public key and only then exposes the `TSTInfo` fields, so a [`TsaToken`]
+//! always comes from a token the authority signed.
+//!
+//! The CMS signature must cover signed attributes whose `contentType` is
+//! `id-ct-TSTInfo` and whose `messageDigest` is the digest of the `TSTInfo`.
+//! RSA PKCS #1 v1.5 and ECDSA P-256 authorities are supported, with SHA-256,
+//! SHA-384 or SHA-512. Certificate chains are not validated: the caller
+//! supplies the key it trusts.
+//!
+//! A token is *consistent* with a claim when
+//!
+//! - its `messageImprint` is the hash of the claim's canonical bytes, under
+//!   the hash algorithm named in the token, and
+//! - `claim.timestamp <= gen_time <= claim.timestamp + tolerance`: the
+//!   authority cannot have seen the claim before it was made, and a claim
+//!   timestamped long after the fact is not vouched for either.
+
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use serde::Serialize;
+
+use crate::{verify_claim, HashAlg, Result, SdkError, SignedClaim};
+
+/// Default tolerance between a claim's timestamp and its TSA token, in seconds.
+pub const TSA_TOLERANCE_SECS: u64 = 300;
+
+const INTEGER: u8 = 0x02;
+const BIT_STRING: u8 = 0x03;
+const OCTET_STRING: u8 = 0x04;
+const OID: u8 = 0x06;
+const GENERALIZED_TIME: u8 = 0x18;
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+const CONTEXT_0: u8 = 0xa0;
+
+const SIGNED_DATA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x07, 0x02];
+const TST_INFO: &[u8] = &[
+    0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x10, 0x01, 0x04,
+];
+const CONTENT_TYPE: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x03];
+const MESSAGE_DIGEST: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x04];
+const SHA256: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01];
+const SHA384: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x02];
+const SHA512: &[u8] = &[0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03];
+const RSA_ENCRYPTION: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+const SHA256_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0b];
+const SHA384_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0c];
+const SHA512_WITH_RSA: &[u8] = &[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x0d];
+const EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const PRIME256V1: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07];
+const ECDSA_WITH_SHA256: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x02];
+const ECDSA_WITH_SHA384: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x03];
+const ECDSA_WITH_SHA512: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x04, 0x03, 0x04];
+
+fn malformed(what: &str) -> SdkError {
+    SdkError::SerializationError(format!("invalid TSA token: {}", what))
+}
+
+/// A forward-only reader over DER elements.
+struct Der<'a>(&'a [u8]);
+
+impl<'a> Der<'a> {
+    /// Read the next element as its tag, contents and whole encoding.
+    fn next_element(&mut self) -> Result<(u8, &'a [u8], &'a [u8])> {
+        let input = self.0;
+        let [tag, first, rest @ ..] = input else {
+            return Err(malformed("truncated element"));
+        };
+        let (len, rest) = if *first < 0x80 {
+            (usize::from(*first), rest)
+        } else {
+            let n = usize::from(first & 0x7f);
+            if n == 0 || n > 4 || rest.len() < n || rest[0] == 0 {
+                return Err(malformed("unsupported length encoding"));
+            }
+            let len = rest[..n]
+                .iter()
+                .fold(0usize, |len, &b| len << 8 | usize::from(b));
+            if len < 0x80 {
+                return Err(malformed("non-minimal length"));
+            }
+            (len, &rest[n..])
+        };
+        if rest.len() < len {
+            return Err(malformed("truncated element"));
+        }
+        let header = input.len() - rest.len();
+        self.0 = &rest[len..];
+        Ok((*tag, &rest[..len], &input[..header + len]))
+    }
+
+    /// Read the next element, which must have tag `tag`, as its contents.
+    fn expect(&mut self, tag: u8) -> Result<&'a [u8]> {
+        let (found, contents, _) = self.next_element()?;
+        if found != tag {
+            return Err(malformed(&format!(
+                "expected tag {:#04x}, got {:#04x}",
+                tag, found
+            )));
+        }
+        Ok(contents)
+    }
+
+    fn skip_if(&mut self, tag: u8) -> Result<()> {
+        if self.0.first() == Some(&tag) {
+            self.next_element()?;
+        }
+        Ok(())
+    }
+
+    /// Read an `AlgorithmIdentifier` as its OID, ignoring its parameters.
+    fn algorithm(&mut self) -> Result<&'a [u8]> {
+        Der(self.expect(SEQUENCE)?).expect(OID)
+    }
+}
+
+fn hash_alg(oid: &[u8]) -> Result<HashAlg> {
+    match oid {
+        SHA256 => Ok(HashAlg::Sha256),
+        SHA384 => Ok(HashAlg::Sha384),
+        SHA512 => Ok(HashAlg::Sha512),
+        _ => Err(malformed("unsupported hash algorithm")),
+    }
+}
+
+fn digest(alg: HashAlg, data: &[u8]) -> Vec<u8> {
+    use sha2::Digest;
+    match alg {
+        HashAlg::Sha256 => sha2::Sha256::digest(data).to_vec(),
+        HashAlg::Sha384 => sha2::Sha384::digest(data).to_vec(),
+        HashAlg::Sha512 => sha2::Sha512::digest(data).to_vec(),
+    }
+}
+
+/// Parse a DER `GeneralizedTime` (`YYYYMMDDHHMMSS[.f]Z`) as UTC seconds.
+fn generalized_time(bytes: &[u8]) -> Result<u64> {
+    let invalid = || malformed("invalid genTime");
+    let (digits, rest) = bytes.split_at_checked(14).ok_or_else(invalid)?;
+    if !digits.iter().all(u8::is_ascii_digit) || rest.last() != Some(&b'Z') {
+        return Err(invalid());
+    }
+    let fraction = &rest[..rest.len() - 1];
+    if let [b'.', decimals @ ..] = fraction {
+        if decimals.is_empty() || !decimals.iter().all(u8::is_ascii_digit) {
+            return Err(invalid());
+        }
+    } else if !fraction.is_empty() {
+        return Err(invalid());
+    }
+    let field = |range: core::ops::Range<usize>| {
+        digits[range]
+            .iter()
+            .fold(0i64, |n, d| n * 10 + i64::from(d - b'0'))
+    };
+    let (year, month, day) = (field(0..4), field(4..6), field(6..8));
+    let (hour, minute, second) = (field(8..10), field(10..12), field(12..14));
+    if year < 1970
+        || !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(invalid());
+    }
+    // Days-from-civil, proleptic Gregorian calendar (H. Hinnant)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Ok((days * 86_400 + hour * 3_600 + minute * 60 + second) as u64)
+}
+
+/// A TSA public key, decoded from a DER `SubjectPublicKeyInfo`.
+enum TsaKey {
+    Rsa(rsa::RsaPublicKey),
+    P256(p256::ecdsa::VerifyingKey),
+}
+
+impl TsaKey {
+    fn from_spki(spki: &[u8]) -> Result<Self> {
+        let invalid = |what: &str| SdkError::KeyError(format!("invalid TSA public key: {}", what));
+        Self::decode(spki).map_err(|e| match e {
+            SdkError::SerializationError(_) => invalid("malformed SubjectPublicKeyInfo"),
+            e => e,
+        })
+    }
+
+    fn decode(spki: &[u8]) -> Result<Self> {
+        let invalid = |what: &str| SdkError::KeyError(format!("invalid TSA public key: {}", what));
+        let mut spki = Der(Der(spki).expect(SEQUENCE)?);
+        let mut algorithm = Der(spki.expect(SEQUENCE)?);
+        let oid = algorithm.expect(OID)?;
+        let key = match spki.expect(BIT_STRING)? {
+            [0, key @ ..] => key,
+            _ => return Err(invalid("bad BIT STRING")),
+        };
+        match oid {
+            RSA_ENCRYPTION => {
+                let mut key = Der(Der(key).expect(SEQUENCE)?);
+                let n = rsa::BigUint::from_bytes_be(key.expect(INTEGER)?);
+                let e = rsa::BigUint::from_bytes_be(key.expect(INTEGER)?);
+                rsa::RsaPublicKey::new(n, e)
+                    .map(TsaKey::Rsa)
+                    .map_err(|e| invalid(&e.to_string()))
+            }
+            EC_PUBLIC_KEY if algorithm.expect(OID)? == PRIME256V1 => {
+                p256::ecdsa::VerifyingKey::from_sec1_bytes(key)
+                    .map(TsaKey::P256)
+                    .map_err(|_| invalid("not a P-256 point"))
+            }
+            _ => Err(invalid("only RSA and ECDSA P-256 keys are supported")),
+        }
+    }
+
+    /// Check `signature` over data whose `alg` digest is `hashed`.
+    fn verify(&self, alg: HashAlg, sig_alg: &[u8], hashed: &[u8], signature: &[u8]) -> Result<()> {
+        let mismatch = || {
+            SdkError::SignatureError("TSA signature algorithm does not match its key".to_string())
+        };
+        let invalid = || SdkError::SignatureError("TSA signature is invalid".to_string());
+        match self {
+            TsaKey::Rsa(key) => {
+                let expected = match alg {
+                    HashAlg::Sha256 => SHA256_WITH_RSA,
+                    HashAlg::Sha384 => SHA384_WITH_RSA,
+                    HashAlg::Sha512 => SHA512_WITH_RSA,
+                };
+                if sig_alg != RSA_ENCRYPTION && sig_alg != expected {
+                    return Err(mismatch());
+                }
+                let scheme = match alg {
+                    HashAlg::Sha256 => rsa::Pkcs1v15Sign::new::<sha2::Sha256>(),
+                    HashAlg::Sha384 => rsa::Pkcs1v15Sign::new::<sha2::Sha384>(),
+                    HashAlg::Sha512 => rsa::Pkcs1v15Sign::new::<sha2::Sha512>(),
+                };
+                key.verify(scheme, hashed, signature).map_err(|_| invalid())
+            }
+            TsaKey::P256(key) => {
+                use p256::ecdsa::signature::hazmat::PrehashVerifier;
+                let expected = match alg {
+                    HashAlg::Sha256 => ECDSA_WITH_SHA256,
+                    HashAlg::Sha384 => ECDSA_WITH_SHA384,
+                    HashAlg::Sha512 => ECDSA_WITH_SHA512,
+                };
+                if sig_alg != expected {
+                    return Err(mismatch());
+                }
+                // Ecdsa-Sig-Value ::= SEQUENCE { r INTEGER, s INTEGER }
+                let mut value = Der(Der(signature).expect(SEQUENCE)?);
+                let mut raw = [0u8; 64];
+                for half in raw.chunks_mut(32) {
+                    let int = value.expect(INTEGER)?;
+                    let int = int.strip_prefix(&[0]).unwrap_or(int);
+                    if int.len() > 32 {
+                        return Err(invalid());
+                    }
+                    half[32 - int.len()..].copy_from_slice(int);
+                }
+                let signature = p256::ecdsa::Signature::from_slice(&raw).map_err(|_| invalid())?;
+                key.verify_prehash(hashed, &signature)
+                    .map_err(|_| invalid())
+            }
+        }
+    }
+}
+
+/// Find the single value of the signed attribute `oid`, as tag and contents.
+fn signed_attribute<'a>(attributes: &'a [u8], oid: &[u8]) -> Result<(u8, &'a [u8])> {
+    let mut found = None;
+    let mut attributes = Der(attributes);
+    while !attributes.0.is_empty() {
+        let mut attribute = Der(attributes.expect(SEQUENCE)?);
+        if attribute.expect(OID)? != oid {
+            continue;
+        }
+        let mut values = Der(attribute.expect(SET)?);
+        let (tag, value, _) = values.next_element()?;
+        if found.is_some() || !values.0.is_empty() {
+            return Err(malformed("signed attribute must have exactly one value"));
+        }
+        found = Some((tag, value));
+    }
+    found.ok_or_else(|| malformed("missing signed attribute"))
+}
+
+/// The `TSTInfo` of an RFC 3161 token whose signature has been verified.
+///
+/// Only [`TsaToken::verify`] creates one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TsaToken {
+    gen_time: u64,
+    hash_alg: HashAlg,
+    message_imprint: Vec<u8>,
+    serial_number: Vec<u8>,
+}
+
+impl TsaToken {
+    /// Decode a DER timestamp token, or a `TimeStampResp` carrying one, and
+    /// verify its CMS signature against `tsa_public_key`, the authority's DER
+    /// `SubjectPublicKeyInfo`
+    ///
+    /// Fails with [`SdkError::SerializationError`] if the token is malformed
+    /// or the response did not grant it, [`SdkError::KeyError`] if the key
+    /// cannot be decoded and [`SdkError::SignatureError`] if the authority's
+    /// signature does not verify.
+    ///
+    /// # Example
+    /// ```
+    /// use provn_sdk::{HashAlg, TsaToken};
+    /// let token = TsaToken::verify(
+    ///     include_bytes!("../tests/fixtures/tsa/rsa_token.der"),
+    ///     include_bytes!("../tests/fixtures/tsa/rsa_key.der"),
+    /// ).unwrap();
+    /// assert_eq!(token.hash_alg(), HashAlg::Sha256);
+    /// assert!(TsaToken::verify(
+    ///     include_bytes!("../tests/fixtures/tsa/rsa_token.der"),
+    ///     include_bytes!("../tests/fixtures/tsa/ec_key.der"),
+    /// ).is_err());
+    /// ```
+    pub fn verify(der: &[u8], tsa_public_key: &[u8]) -> Result<Self> {
+        let key = TsaKey::from_spki(tsa_public_key)?;
+
+        let mut outer = Der(Der(der).expect(SEQUENCE)?);
+        let mut content_info = if outer.0.first() == Some(&SEQUENCE) {
+            // TimeStampResp ::= SEQUENCE { status PKIStatusInfo, timeStampToken }
+            let status = Der(outer.expect(SEQUENCE)?).expect(INTEGER)?;
+            if status != [0] && status != [1] {
+                return Err(malformed("the authority did not grant a token"));
+            }
+            Der(outer.expect(SEQUENCE)?)
+        } else {
+            outer
+        };
+        if content_info.expect(OID)? != SIGNED_DATA {
+            return Err(malformed("not CMS SignedData"));
+        }
+
+        let mut signed_data = Der(Der(content_info.expect(CONTEXT_0)?).expect(SEQUENCE)?);
+        signed_data.expect(INTEGER)?;
+        signed_data.expect(SET)?;
+        let mut encapsulated = Der(signed_data.expect(SEQUENCE)?);
+        if encapsulated.expect(OID)? != TST_INFO {
+            return Err(malformed("content is not a TSTInfo"));
+        }
+        let tst_info = Der(encapsulated.expect(CONTEXT_0)?).expect(OCTET_STRING)?;
+        signed_data.skip_if(CONTEXT_0)?;
+        signed_data.skip_if(0xa1)?;
+        let mut signer_infos = Der(signed_data.expect(SET)?);
+        let signer_info = signer_infos.expect(SEQUENCE)?;
+        if !signer_infos.0.is_empty() {
+            return Err(malformed("expected exactly one signer"));
+        }
+
+        let mut signer = Der(signer_info);
+        signer.expect(INTEGER)?;
+        signer.next_element()?;
+        let alg = hash_alg(signer.algorithm()?)?;
+        let (tag, attributes, encoded) = signer.next_element()?;
+        if tag != CONTEXT_0 {
+            return Err(malformed("signed attributes are required"));
+        }
+        let sig_alg = signer.algorithm()?;
+        let signature = signer.expect(OCTET_STRING)?;
+
+        match signed_attribute(attributes, CONTENT_TYPE)? {
+            (OID, TST_INFO) => {}
+            _ => return Err(malformed("signed content type is not TSTInfo")),
+        }
+        match signed_attribute(attributes, MESSAGE_DIGEST)? {
+            (OCTET_STRING, md) if md == digest(alg, tst_info) => {}
+            _ => {
+                return Err(SdkError::SignatureError(
+                    "TSA signed attributes do not match the TSTInfo".to_string(),
+                ))
+            }
+        }
+        // The signature covers the attributes DER-encoded as a SET OF
+        let mut signed = encoded.to_vec();
+        signed[0] = SET;
+        key.verify(alg, sig_alg, &digest(alg, &signed), signature)?;
+
+        let mut tst_info = Der(Der(tst_info).expect(SEQUENCE)?);
+        if tst_info.expect(INTEGER)? != [1] {
+            return Err(malformed("unsupported TSTInfo version"));
+        }
+        tst_info.expect(OID)?;
+        let mut imprint = Der(tst_info.expect(SEQUENCE)?);
+        let hash_alg = hash_alg(imprint.algorithm()?)?;
+        let message_imprint = imprint.expect(OCTET_STRING)?.to_vec();
+        let serial_number = tst_info.expect(INTEGER)?.to_vec();
+        let gen_time = generalized_time(tst_info.expect(GENERALIZED_TIME)?)?;
+
+        Ok(TsaToken {
+            gen_time,
+            hash_alg,
+            message_imprint,
+            serial_number,
+        })
+    }
+
+    /// `genTime`: when the authority issued the token (UTC seconds)
+    pub fn gen_time(&self) -> u64 {
+        self.gen_time
+    }
+
+    /// Hash algorithm of the message imprint
+    pub fn hash_alg(&self) -> HashAlg {
+        self.hash_alg
+    }
+
+    /// `messageImprint.hashedMessage`: the hash of the timestamped data
+    pub fn message_imprint(&self) -> &[u8] {
+        &self.message_imprint
+    }
+
+    /// `serialNumber` of the token, as big-endian bytes
+    pub fn serial_number(&self) -> &[u8] {
+        &self.serial_number
+    }
+}
+
+/// Verify a signed claim and check that `token` timestamps it
+///
+/// Uses a tolerance of [`TSA_TOLERANCE_SECS`].
+///
+/// # Example
+/// ```
+/// use ed25519_dalek::SigningKey;
+/// use provn_sdk::{Claim, TsaToken, sign_claim, verify_claim_with_tsa};
+/// let claim = Claim::new_with_timestamp("x".to_string(), 1791983554);
+/// let signed = sign_claim(&claim, &SigningKey::from_bytes(&[0u8; 32])).unwrap();
+/// let token = TsaToken::verify(
+///     include_bytes!("../tests/fixtures/tsa/rsa_token.der"),
+///     include_bytes!("../tests/fixtures/tsa/rsa_key.der"),
+/// ).unwrap();
+/// assert!(verify_claim_with_tsa(&signed, &token).unwrap());
+/// ```
+pub fn verify_claim_with_tsa<T: Serialize>(
+    signed_claim: &SignedClaim<T>,
+    token: &TsaToken,
+) -> Result<bool> {
+    verify_claim_with_tsa_within(signed_claim, token, TSA_TOLERANCE_SECS)
+}
+
+/// Like [`verify_claim_with_tsa`], allowing `gen_time` to trail the claim's
+/// timestamp by at most `tolerance` seconds
+///
+/// Fails with [`SdkError::ProofError`] if the token is for other data and
+/// with [`SdkError::TimeError`] if its time is inconsistent with the claim.
+pub fn verify_claim_with_tsa_within<T: Serialize>(
+    signed_claim: &SignedClaim<T>,
+    token: &TsaToken,
+    tolerance: u64,
+) -> Result<bool> {
+    verify_claim(signed_claim)?;
+    let imprint = digest(token.hash_alg, &signed_claim.claim.to_signable_bytes()?);
+    if token.message_imprint != imprint {
+        return Err(SdkError::ProofError(
+            "TSA token does not cover the claim hash".to_string(),
+        ));
+    }
+    let timestamp = signed_claim.claim.timestamp;
+    if token.gen_time < timestamp {
+        return Err(SdkError::TimeError(format!(
+            "TSA time {} precedes the claim timestamp {}",
+            token.gen_time, timestamp
+        )));
+    }
+    if token.gen_time - timestamp > tolerance {
+        return Err(SdkError::TimeError(format!(
+            "TSA time {} is more than {} seconds after the claim timestamp {}",
+            token.gen_time, tolerance, timestamp
+        )));
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, Claim};
+    use ed25519_dalek::SigningKey;
+
+    const RSA_TOKEN: &[u8] = include_bytes!("../tests/fixtures/tsa/rsa_token.der");
+    const RSA_KEY: &[u8] = include_bytes!("../tests/fixtures/tsa/rsa_key.der");
+    const EC_RESPONSE: &[u8] = include_bytes!("../tests/fixtures/tsa/ec_response.der");
+    const EC_KEY: &[u8] = include_bytes!("../tests/fixtures/tsa/ec_key.der");
+    /// Timestamp of the claim `{"data":"x",...}` the fixtures were issued for
+    const STAMPED_AT: u64 = 1_791_983_554;
+
+    fn signed(timestamp: u64) -> SignedClaim {
+        let claim = Claim::new_with_timestamp("x".to_string(), timestamp);
+        sign_claim(&claim, &SigningKey::from_bytes(&[0u8; 32])).unwrap()
+    }
+
+    #[test]
+    fn test_token_signature_is_verified() {
+        let rsa = TsaToken::verify(RSA_TOKEN, RSA_KEY).unwrap();
+        assert_eq!(rsa.hash_alg(), HashAlg::Sha256);
+        assert_eq!(
+            generalized_time(b"20261014131334Z").unwrap(),
+            rsa.gen_time()
+        );
+        let ec = TsaToken::verify(EC_RESPONSE, EC_KEY).unwrap();
+        assert_eq!(ec.hash_alg(), HashAlg::Sha384);
+        assert_eq!(ec.message_imprint().len(), 48);
+        assert_eq!(ec.serial_number(), [7]);
+
+        assert!(matches!(
+            TsaToken::verify(RSA_TOKEN, EC_KEY),
+            Err(SdkError::SignatureError(_))
+        ));
+        assert!(matches!(
+            TsaToken::verify(RSA_TOKEN, &RSA_KEY[1..]),
+            Err(SdkError::KeyError(_))
+        ));
+        // Flipping a byte of the TSTInfo or of the signature is detected
+        let imprint = RSA_TOKEN
+            .windows(rsa.message_imprint().len())
+            .position(|w| w == rsa.message_imprint())
+            .unwrap();
+        for at in [imprint, RSA_TOKEN.len() - 1] {
+            let mut tampered = RSA_TOKEN.to_vec();
+            tampered[at] ^= 1;
+            assert!(matches!(
+                TsaToken::verify(&tampered, RSA_KEY),
+                Err(SdkError::SignatureError(_))
+            ));
+        }
+        assert!(TsaToken::verify(&RSA_TOKEN[..RSA_TOKEN.len() - 1], RSA_KEY).is_err());
+    }
+
+    #[test]
+    fn test_token_consistency() {
+        for token in [
+            TsaToken::verify(RSA_TOKEN, RSA_KEY).unwrap(),
+            TsaToken::verify(EC_RESPONSE, EC_KEY).unwrap(),
+        ] {
+            let delay = token.gen_time() - STAMPED_AT;
+            assert!(verify_claim_with_tsa(&signed(STAMPED_AT), &token).unwrap());
+            assert!(verify_claim_with_tsa_within(&signed(STAMPED_AT), &token, delay).unwrap());
+            assert!(matches!(
+                verify_claim_with_tsa_within(&signed(STAMPED_AT), &token, delay - 1),
+                Err(SdkError::TimeError(_))
+            ));
+            // A different timestamp is a different claim, so a different imprint
+            assert!(matches!(
+                verify_claim_with_tsa(&signed(STAMPED_AT + 1), &token),
+                Err(SdkError::ProofError(_))
+            ));
+        }
+    }
+}
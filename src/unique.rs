@@ -0,0 +1,68 @@
+//! Unlinkable signatures for repeated claims.
+//!
+//! Ed25519 signing is deterministic: the same claim signed twice by the same
+//! key yields the same signature, so anyone holding both can tell that the
+//! two events were identical (the same door opened by the same badge at the
+//! same second, say). [`sign_claim_unique`] sets [`Claim::salt`] to fresh
+//! random bytes before signing, so identical claims produce distinct
+//! canonical bytes, hashes and signatures. The salt is part of the signed
+//! claim, so verification needs nothing special.
+
+use ed25519_dalek::SigningKey;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::Serialize;
+
+use crate::{sign_claim, Claim, Result, SignedClaim};
+
+/// Length of the salt added by [`sign_claim_unique`], in bytes.
+pub const CLAIM_SALT_BYTES: usize = 16;
+
+/// Sign a claim with a fresh random salt (requires "std")
+///
+/// Any salt already on the claim is replaced.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, sign_claim_unique, verify_claim, generate_keypair};
+/// let key = generate_keypair();
+/// let claim = Claim::new_with_timestamp("Door 4 opened".to_string(), 1700000000);
+/// let first = sign_claim_unique(&claim, &key).unwrap();
+/// let second = sign_claim_unique(&claim, &key).unwrap();
+/// assert_ne!(first.signature, second.signature);
+/// assert!(verify_claim(&first).unwrap() && verify_claim(&second).unwrap());
+/// ```
+pub fn sign_claim_unique<T: Serialize + Clone>(
+    claim: &Claim<T>,
+    key: &SigningKey,
+) -> Result<SignedClaim<T>> {
+    let mut salt = [0u8; CLAIM_SALT_BYTES];
+    OsRng.fill_bytes(&mut salt);
+    let mut salted = claim.clone();
+    salted.salt = Some(hex::encode(salt));
+    sign_claim(&salted, key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify_claim;
+
+    #[test]
+    fn test_salt_is_signed() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let claim = Claim::new_with_timestamp("x".to_string(), 1);
+        let signed = sign_claim_unique(&claim, &key).unwrap();
+        let salt = signed.claim.salt.clone().unwrap();
+        assert_eq!(salt.len(), 2 * CLAIM_SALT_BYTES);
+        assert_eq!(
+            signed.claim.to_signable_bytes().unwrap(),
+            format!(r#"{{"data":"x","salt":"{}","timestamp":1}}"#, salt).into_bytes()
+        );
+        assert!(verify_claim(&signed).unwrap());
+
+        let mut stripped = signed;
+        stripped.claim.salt = None;
+        assert!(verify_claim(&stripped).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! Verified claims as a type.
+//!
+//! A [`VerifiedClaim`] can only be obtained from [`verify_and_extract`], so a
+//! function taking `&VerifiedClaim` cannot be handed a claim that was never
+//! checked, or one that had already expired when it was:
+//!
+//! ```compile_fail
+//! use provn_sdk::{Claim, VerifiedClaim};
+//! let claim = Claim::new_with_timestamp("forged".to_string(), 1);
+//! let unchecked = VerifiedClaim { claim: &claim, public_key: "00" };
+//! ```
+
+use alloc::string::String;
+use core::ops::Deref;
+use serde::Serialize;
+
+use crate::{verify_with_policy, Claim, Result, SignedClaim, VerifyPolicy};
+
+/// A claim whose signature verified and which had not expired at the time
+/// of verification.
+///
+/// Derefs to the underlying [`Claim`].
+#[derive(Debug)]
+pub struct VerifiedClaim<'a, T = String> {
+    claim: &'a Claim<T>,
+    public_key: &'a str,
+}
+
+impl<'a, T> VerifiedClaim<'a, T> {
+    /// The verified claim
+    pub fn claim(&self) -> &'a Claim<T> {
+        self.claim
+    }
+
+    /// The public key that signed the claim (as encoded in the signed claim)
+    pub fn public_key(&self) -> &'a str {
+        self.public_key
+    }
+}
+
+// Copying a borrow needs no `T: Clone`, so these are not derived
+impl<T> Clone for VerifiedClaim<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for VerifiedClaim<'_, T> {}
+
+impl<T> Deref for VerifiedClaim<'_, T> {
+    type Target = Claim<T>;
+
+    fn deref(&self) -> &Claim<T> {
+        self.claim
+    }
+}
+
+/// Verify a signed claim and check it has not expired at `now`, returning
+/// its claim as a [`VerifiedClaim`]
+///
+/// The signature is checked with [`verify_claim_strict`](crate::verify_claim_strict),
+/// so malleable signatures and small-order keys are rejected.
+///
+/// # Example
+/// ```
+/// use provn_sdk::{Claim, VerifiedClaim, sign_claim, verify_and_extract, generate_keypair};
+///
+/// fn grant_access(ticket: &VerifiedClaim) -> bool {
+///     ticket.data == "door 4"
+/// }
+///
+/// let mut claim = Claim::new_with_timestamp("door 4".to_string(), 1000);
+/// claim.expires_at = Some(2000);
+/// let signed = sign_claim(&claim, &generate_keypair()).unwrap();
+///
+/// let ticket = verify_and_extract(&signed, 1500).unwrap();
+/// assert!(grant_access(&ticket));
+/// assert!(verify_and_extract(&signed, 2000).is_err());
+/// ```
+pub fn verify_and_extract<T: Serialize>(
+    signed: &SignedClaim<T>,
+    now: u64,
+) -> Result<VerifiedClaim<'_, T>> {
+    let policy = VerifyPolicy {
+        now: Some(now),
+        require_not_expired: true,
+        strict: true,
+        ..Default::default()
+    };
+    verify_with_policy(signed, &policy).into_result()?;
+    Ok(VerifiedClaim {
+        claim: &signed.claim,
+        public_key: &signed.public_key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sign_claim, verify_claim, SdkError};
+    use alloc::format;
+    use alloc::string::ToString;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_only_valid_unexpired_claims_are_extracted() {
+        let key = SigningKey::from_bytes(&[0u8; 32]);
+        let mut claim = Claim::new_with_timestamp("x".to_string(), 1);
+        claim.expires_at = Some(10);
+        let signed = sign_claim(&claim, &key).unwrap();
+
+        let verified = verify_and_extract(&signed, 9).unwrap();
+        assert_eq!(verified.claim(), &claim);
+        assert_eq!(verified.data, "x");
+        assert_eq!(verified.public_key(), signed.public_key);
+
+        assert!(matches!(
+            verify_and_extract(&signed, 10),
+            Err(SdkError::PolicyError(_))
+        ));
+        let mut tampered = signed.clone();
+        tampered.claim.data = "y".to_string();
+        assert!(matches!(
+            verify_and_extract(&tampered, 0),
+            Err(SdkError::SignatureError(_))
+        ));
+
+        // Identity key with R = identity, s = 0 passes lenient verification only
+        let mut forged = signed;
+        forged.public_key = format!("01{}", "00".repeat(31));
+        forged.signature = format!("01{}", "00".repeat(63));
+        assert!(verify_claim(&forged).unwrap());
+        assert!(verify_and_extract(&forged, 0).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+// Reference implementation used by tests/node_interop.rs.
+//
+// Reads one JSON document from stdin and writes the result to stdout:
+//
+//   node interop.mjs canonicalize   <any JSON>                 -> RFC 8785 text
+//   node interop.mjs sign           {"seed": hex, "claim": {}} -> SignedClaim JSON
+//   node interop.mjs verify         <SignedClaim JSON>         -> "true" | "false"
+//
+// Verification is strict RFC 8032 (zip215: false), matching verify_claim_strict.
+import canonicalize from 'canonicalize';
+import * as ed from '@noble/ed25519';
+import { readFileSync } from 'node:fs';
+
+const hex = (bytes) => Buffer.from(bytes).toString('hex');
+const unhex = (text) => Uint8Array.from(Buffer.from(text, 'hex'));
+const signable = (claim) => new TextEncoder().encode(canonicalize(claim));
+
+const input = JSON.parse(readFileSync(0, 'utf8'));
+
+switch (process.argv[2]) {
+  case 'canonicalize':
+    process.stdout.write(canonicalize(input));
+    break;
+  case 'sign': {
+    const seed = unhex(input.seed);
+    const signature = await ed.signAsync(signable(input.claim), seed);
+    const publicKey = await ed.getPublicKeyAsync(seed);
+    process.stdout.write(
+      JSON.stringify({ claim: input.claim, public_key: hex(publicKey), signature: hex(signature) }),
+    );
+    break;
+  }
+  case 'verify': {
+    const valid = await ed.verifyAsync(
+      unhex(input.signature),
+      signable(input.claim),
+      unhex(input.public_key),
+      { zip215: false },
+    );
+    process.stdout.write(valid ? 'true' : 'false');
+    break;
+  }
+  default:
+    console.error('usage: node interop.mjs canonicalize|sign|verify < input.json');
+    process.exit(2);
+}
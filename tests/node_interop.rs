@@ -0,0 +1,114 @@
+//! Interop against a reference JavaScript implementation.
+//!
+//! `tests/interop/interop.mjs` canonicalizes with the `canonicalize` package
+//! (RFC 8785) and signs and verifies with `@noble/ed25519`. These tests sign
+//! on one side and verify on the other, so they catch canonicalization or
+//! strict-verification divergences that self-consistency tests cannot.
+//!
+//! They need Node.js and are ignored by default. To run them:
+//!
+//! ```sh
+//! npm install --prefix tests/interop
+//! cargo test --test node_interop -- --ignored
+//! ```
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use ed25519_dalek::SigningKey;
+use provn_sdk::{sign_claim, verify_claim_strict, Claim, SignedClaim};
+use serde_json::{json, Value};
+
+const SEED: [u8; 32] = [0u8; 32];
+
+/// Run `interop.mjs <command>` with `input` on stdin and return its stdout.
+fn node(command: &str, input: &Value) -> String {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/interop");
+    assert!(
+        dir.join("node_modules").is_dir(),
+        "run `npm install --prefix tests/interop` first"
+    );
+    let mut child = Command::new("node")
+        .arg(dir.join("interop.mjs"))
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .expect("failed to start node");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.to_string().as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "node {} failed", command);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Claims exercising key ordering, string escaping and number formatting.
+fn claims() -> Vec<Claim<Value>> {
+    let mut with_metadata = Claim::new_with_timestamp(json!("Audit log sealed"), 1_700_000_000);
+    with_metadata.metadata = Some("batch 7".to_string());
+    with_metadata.expires_at = Some(1_800_000_000);
+    vec![
+        Claim::new_with_timestamp(json!("Test"), 1),
+        with_metadata,
+        Claim::new_with_timestamp(json!("é ü \u{2028} \"quoted\" \\ \n\t \u{1f600} \u{7f}"), 2),
+        Claim::new_with_timestamp(
+            json!({
+                "zeta": [0.1, 1e21, 1e-7, 123.456, -5, 9007199254740991_u64],
+                "alpha": { "\u{e9}": true, "b": null, "a": [] },
+                "\u{20ac}": "euro",
+                "\r": "cr",
+            }),
+            9_007_199_254_740_991,
+        ),
+    ]
+}
+
+#[test]
+#[ignore = "needs Node.js and `npm install --prefix tests/interop`"]
+fn test_canonical_bytes_match() {
+    for claim in claims() {
+        let rust = claim.to_signable_bytes().unwrap();
+        let js = node("canonicalize", &serde_json::to_value(&claim).unwrap());
+        assert_eq!(String::from_utf8(rust).unwrap(), js);
+    }
+}
+
+#[test]
+#[ignore = "needs Node.js and `npm install --prefix tests/interop`"]
+fn test_js_signed_claims_verify_in_rust() {
+    let key = SigningKey::from_bytes(&SEED);
+    for claim in claims() {
+        let input = json!({ "seed": hex::encode(SEED), "claim": claim });
+        let signed: SignedClaim<Value> = serde_json::from_str(&node("sign", &input)).unwrap();
+        assert!(verify_claim_strict(&signed).unwrap());
+        // Ed25519 is deterministic: both sides must produce the same signature
+        assert_eq!(
+            signed.signature,
+            sign_claim(&claim, &key).unwrap().signature
+        );
+    }
+}
+
+#[test]
+#[ignore = "needs Node.js and `npm install --prefix tests/interop`"]
+fn test_rust_signed_claims_verify_in_js() {
+    let key = SigningKey::from_bytes(&SEED);
+    for claim in claims() {
+        let signed = sign_claim(&claim, &key).unwrap();
+        let envelope = serde_json::to_value(&signed).unwrap();
+        assert_eq!(node("verify", &envelope), "true");
+
+        let mut tampered = signed.clone();
+        tampered.claim.timestamp += 1;
+        assert_eq!(
+            node("verify", &serde_json::to_value(&tampered).unwrap()),
+            "false"
+        );
+    }
+}
@@ -0,0 +1,82 @@
+//! Cross-language conformance vectors.
+//!
+//! `tests/vectors.json` pins the canonical bytes and signature of a set of
+//! claims signed with the all-zeros test key. Ed25519 signing is
+//! deterministic, so any change to canonicalization or signing that alters
+//! these outputs fails here. Other language SDKs should run the same file.
+
+use ed25519_dalek::SigningKey;
+use provn_sdk::{keypair_from_seed, sign_claim, verify_claim, Claim, SignedClaim};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Vector {
+    name: String,
+    claim: Claim,
+    canonical_bytes_hex: String,
+    signature_hex: String,
+    public_key_hex: String,
+}
+
+fn load_vectors() -> Vec<Vector> {
+    serde_json::from_str(include_str!("vectors.json")).expect("vectors.json is malformed")
+}
+
+#[test]
+fn test_vectors_match() {
+    let key = SigningKey::from_bytes(&[0u8; 32]);
+    let vectors = load_vectors();
+    assert!(!vectors.is_empty());
+
+    for v in vectors {
+        let canonical = v.claim.to_signable_bytes().unwrap();
+        assert_eq!(hex::encode(&canonical), v.canonical_bytes_hex, "{}", v.name);
+
+        let signed = sign_claim(&v.claim, &key).unwrap();
+        assert_eq!(signed.signature, v.signature_hex, "{}", v.name);
+        assert_eq!(signed.public_key, v.public_key_hex, "{}", v.name);
+    }
+}
+
+#[test]
+fn test_vectors_verify() {
+    for v in load_vectors() {
+        let signed = SignedClaim {
+            claim: v.claim,
+            public_key: v.public_key_hex,
+            signature: v.signature_hex,
+            signed_fields: None,
+            metadata_salt: None,
+            domain: None,
+        };
+        assert!(verify_claim(&signed).unwrap(), "{}", v.name);
+    }
+}
+
+/// Secret key of RFC 8032, section 7.1, TEST 1.
+const RFC8032_SEED: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+
+#[test]
+fn test_keypair_from_seed_is_deterministic() {
+    let seed: [u8; 32] = hex::decode(RFC8032_SEED).unwrap().try_into().unwrap();
+    let key = keypair_from_seed(&seed);
+    assert_eq!(
+        hex::encode(key.verifying_key().as_bytes()),
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+    );
+}
+
+#[test]
+fn test_signature_is_rng_independent() {
+    // Signing never touches an RNG, so a `rand`/`OsRng` upgrade must not
+    // change this output.
+    let seed: [u8; 32] = hex::decode(RFC8032_SEED).unwrap().try_into().unwrap();
+    let mut claim = Claim::new_with_timestamp("regression".to_string(), 1700000000);
+    claim.metadata = Some("rand-independent".to_string());
+    let signed = sign_claim(&claim, &keypair_from_seed(&seed)).unwrap();
+    assert_eq!(
+        signed.signature,
+        "89106d7faf66565aeead30afb25e940afaa048e26129b26f5368a7e2c1f0f029\
+         891fd7d4da3db8c4623204f90adc5c590f0a9cebe77e830966055ba3c8237507"
+    );
+}